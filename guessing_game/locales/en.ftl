@@ -0,0 +1,159 @@
+## English messages of the guessing game.
+
+## Guessing a number
+
+number-title = Guess the number between 0 and { $max }!
+numbers-title = Find the { $count } numbers between 0 and { $max }! ({ $found } found)
+too-small = Too small!
+too-big = Too big!
+you-win = You Win!
+found-one =
+    { $left ->
+        [one] Found one! 1 more to go.
+       *[other] Found one! { $left } more to go.
+    }
+already-found = you already found { $value }
+warmer = Warmer!
+colder = Colder!
+just-as-far = Just as far as last time.
+within = Within { $band } of the number.
+round-over = the round is over
+out-of-range = { $value } is out of range, the number is at most { $max }.
+only-digits = only digits can be typed, not '{ $char }'
+at-most = the number is at most { $max }
+not-a-number = { $input } is not a number
+number-reveal = the secret number is { $secret }
+numbers-reveal = the secret numbers are { $secrets }
+
+## Hints bought with points
+
+hint-parity = parity
+hint-digit-sum = digit sum
+hint-range = narrowed range
+hint-even = The number is even.
+hint-odd = The number is odd.
+hint-digits-add-up = Its digits add up to { $sum }.
+hint-between = The number is between { $low } and { $high }.
+hint-unavailable = there is no { $hint } hint in this mode
+hint-bought-already = you already bought the { $hint } hint
+hint-over-budget = the { $hint } hint costs { $cost } points, only { $left } are left to spend on hints
+
+## Guessing a word
+
+no-words = the word list has no words
+word-title = Guess the word: { $masked }  ({ $wrong } of { $lives } wrong)
+only-letters = only letters can be typed, not '{ $char }'
+one-letter = one letter at a time
+already-guessed = you already guessed '{ $letter }'
+letter-missing = No '{ $letter }' in the word.
+letter-found =
+    { $count ->
+        [one] Yes, once!
+       *[other] Yes, { $count } times!
+    }
+word-reveal = the word is { $word }
+
+## The screen
+
+input-title = Please input your guess.
+input-title-hints = Please input your guess. Hints: { $keys }
+hint-key = F{ $key } { $hint } ({ $cost })
+hint-line = Hint, { $hint } (-{ $cost }):{" "}
+come-back-tomorrow = Come back tomorrow! Press q to quit
+play-again = Press Enter to play again, q to quit
+status-won = You Win! { $reveal }, you needed { $guesses ->
+        [one] 1 guess
+       *[other] { $guesses } guesses
+    }{ $par }, { $points } points{ $place }
+status-par = , par is { $par }
+status-place = , high score #{ $place }!
+status-lost = You Lose! { $reveal }, you ran out of your { $guesses ->
+        [one] 1 guess
+       *[other] { $guesses } guesses
+    }
+status-playing = Round { $round }, { $guesses ->
+        [one] 1 guess
+       *[other] { $guesses } guesses
+    }{ $remaining }{ $spent }  Enter guess, Up/Down scroll, Esc quit
+status-remaining =
+    { $remaining ->
+        [one] , 1 guess left
+       *[other] , { $remaining } guesses left
+    }
+status-spent = , { $cost } points on hints
+
+## After quitting
+
+save-failed = could not save the high scores: { $error }
+session-summary = { $rounds } rounds, { $wins } won, { $guesses } guesses and { $points } points in total{ $best }
+session-best = , best round { $best ->
+        [one] 1 guess
+       *[other] { $best } guesses
+    }
+high-scores = high scores for { $table }: { $scores }
+high-score = { $points } points ({ $guesses ->
+        [one] 1 guess
+       *[other] { $guesses } guesses
+    })
+daily-share = Guessing game daily { $date }, { $table }: { $score }
+daily-guesses = { $guesses ->
+        [one] 1 guess
+       *[other] { $guesses } guesses
+    }
+
+## Multiplayer
+
+hosting = hosting on port { $port }, waiting for { $players ->
+        [one] 1 player
+       *[other] { $players } players
+    }
+host-joined = { $name } joined from { $addr }
+host-left = { $name } left
+host-everyone-left = everyone left, { $reveal }
+host-guessed = { $name } guessed { $guess }: { $verdict }
+host-winner = { $name } wins with { $guesses ->
+        [one] 1 guess
+       *[other] { $guesses } guesses
+    }
+host-lost = out of guesses, { $reveal }
+not-your-turn = not your turn
+expected-guess = expected GUESS <number>, got '{ $line }'
+client-connected = Connected, waiting for players. The number is between 0 and { $max }.
+client-welcome = You joined as { $name }.
+client-joined = { $name } joined.
+client-start = The game starts with { $players ->
+        [one] 1 player
+       *[other] { $players } players
+    }!
+client-your-turn = Your turn, please input your guess.
+client-turn = { $name }'s turn.
+client-too-small = { $name } guessed { $guess }: Too small!
+client-too-big = { $name } guessed { $guess }: Too big!
+client-correct = { $name } guessed { $guess }: correct!
+client-you-win = You Win with { $guesses ->
+        [one] 1 guess
+       *[other] { $guesses } guesses
+    }!
+client-winner = { $name } wins with { $guesses ->
+        [one] 1 guess
+       *[other] { $guesses } guesses
+    }.
+client-left = { $name } left.
+client-lost = Out of guesses, everyone loses! { $reveal }.
+client-closed = the host closed the connection
+
+## Reverse mode
+
+reverse-intro = Think of a number between 0 and { $max }, I'll guess it!
+reverse-answers = Answer h if yours is higher, l if it's lower and c when I'm correct.
+reverse-ask = Is it { $guess }?{" "}
+reverse-answer-hlc = Please answer h, l or c.
+reverse-got-it = I got it, your number is { $guess }! I needed { $guesses ->
+        [one] 1 guess
+       *[other] { $guesses } guesses
+    }.
+reverse-contradicts = That contradicts your answers: { $error }.
+reverse-only-one = only { $number } is left
+reverse-only-range = only { $low } to { $high } are left
+reverse-not-higher = it can't be higher than { $guess }, { $left }
+reverse-not-lower = it can't be lower than { $guess }, { $left }
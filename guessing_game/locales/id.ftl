@@ -0,0 +1,115 @@
+## Pesan permainan tebak angka dalam Bahasa Indonesia.
+
+## Menebak angka
+
+number-title = Tebak angka antara 0 dan { $max }!
+numbers-title = Temukan { $count } angka antara 0 dan { $max }! ({ $found } ketemu)
+too-small = Terlalu kecil!
+too-big = Terlalu besar!
+you-win = Kamu Menang!
+found-one = Ketemu satu! Tinggal { $left } lagi.
+already-found = kamu sudah menemukan { $value }
+warmer = Makin dekat!
+colder = Makin jauh!
+just-as-far = Sama jauhnya dengan tebakan sebelumnya.
+within = Selisihnya paling banyak { $band } dari angkanya.
+round-over = ronde sudah selesai
+out-of-range = { $value } di luar jangkauan, angkanya paling besar { $max }.
+only-digits = hanya angka yang bisa diketik, bukan '{ $char }'
+at-most = angkanya paling besar { $max }
+not-a-number = { $input } bukan angka
+number-reveal = angka rahasianya { $secret }
+numbers-reveal = angka-angka rahasianya { $secrets }
+
+## Petunjuk yang dibeli dengan poin
+
+hint-parity = ganjil genap
+hint-digit-sum = jumlah digit
+hint-range = rentang sempit
+hint-even = Angkanya genap.
+hint-odd = Angkanya ganjil.
+hint-digits-add-up = Jumlah digitnya { $sum }.
+hint-between = Angkanya antara { $low } dan { $high }.
+hint-unavailable = tidak ada petunjuk { $hint } di mode ini
+hint-bought-already = kamu sudah membeli petunjuk { $hint }
+hint-over-budget = petunjuk { $hint } seharga { $cost } poin, sisa poin untuk petunjuk hanya { $left }
+
+## Menebak kata
+
+no-words = daftar katanya tidak berisi kata
+word-title = Tebak katanya: { $masked }  ({ $wrong } dari { $lives } salah)
+only-letters = hanya huruf yang bisa diketik, bukan '{ $char }'
+one-letter = satu huruf setiap kali
+already-guessed = kamu sudah menebak '{ $letter }'
+letter-missing = Tidak ada '{ $letter }' di kata ini.
+letter-found =
+    { $count ->
+        [one] Ya, sekali!
+       *[other] Ya, { $count } kali!
+    }
+word-reveal = katanya { $word }
+
+## Layar
+
+input-title = Masukkan tebakanmu.
+input-title-hints = Masukkan tebakanmu. Petunjuk: { $keys }
+hint-key = F{ $key } { $hint } ({ $cost })
+hint-line = Petunjuk, { $hint } (-{ $cost }):{" "}
+come-back-tomorrow = Kembali lagi besok! Tekan q untuk keluar
+play-again = Tekan Enter untuk main lagi, q untuk keluar
+status-won = Kamu Menang! { $reveal }, kamu butuh { $guesses } tebakan{ $par }, { $points } poin{ $place }
+status-par = , par-nya { $par }
+status-place = , skor tertinggi #{ $place }!
+status-lost = Kamu Kalah! { $reveal }, { $guesses } tebakanmu sudah habis
+status-playing = Ronde { $round }, { $guesses } tebakan{ $remaining }{ $spent }  Enter menebak, Atas/Bawah gulir, Esc keluar
+status-remaining = , sisa { $remaining } tebakan
+status-spent = , { $cost } poin untuk petunjuk
+
+## Setelah keluar
+
+save-failed = gagal menyimpan skor tertinggi: { $error }
+session-summary = { $rounds } ronde, { $wins } menang, total { $guesses } tebakan dan { $points } poin{ $best }
+session-best = , ronde terbaik { $best } tebakan
+high-scores = skor tertinggi untuk { $table }: { $scores }
+high-score = { $points } poin ({ $guesses } tebakan)
+daily-share = Tebak angka harian { $date }, { $table }: { $score }
+daily-guesses = { $guesses } tebakan
+
+## Multipemain
+
+hosting = menjadi host di port { $port }, menunggu { $players } pemain
+host-joined = { $name } bergabung dari { $addr }
+host-left = { $name } keluar
+host-everyone-left = semua pemain keluar, { $reveal }
+host-guessed = { $name } menebak { $guess }: { $verdict }
+host-winner = { $name } menang dengan { $guesses } tebakan
+host-lost = tebakan habis, { $reveal }
+not-your-turn = bukan giliranmu
+expected-guess = seharusnya GUESS <angka>, bukan '{ $line }'
+client-connected = Tersambung, menunggu pemain. Angkanya antara 0 dan { $max }.
+client-welcome = Kamu bergabung sebagai { $name }.
+client-joined = { $name } bergabung.
+client-start = Permainan dimulai dengan { $players } pemain!
+client-your-turn = Giliranmu, masukkan tebakanmu.
+client-turn = Giliran { $name }.
+client-too-small = { $name } menebak { $guess }: Terlalu kecil!
+client-too-big = { $name } menebak { $guess }: Terlalu besar!
+client-correct = { $name } menebak { $guess }: benar!
+client-you-win = Kamu Menang dengan { $guesses } tebakan!
+client-winner = { $name } menang dengan { $guesses } tebakan.
+client-left = { $name } keluar.
+client-lost = Tebakan habis, semua kalah! { $reveal }.
+client-closed = host menutup sambungan
+
+## Mode terbalik
+
+reverse-intro = Pikirkan sebuah angka antara 0 dan { $max }, aku akan menebaknya!
+reverse-answers = Jawab h kalau angkamu lebih besar, l kalau lebih kecil dan c kalau tebakanku benar.
+reverse-ask = Apakah { $guess }?{" "}
+reverse-answer-hlc = Jawab dengan h, l atau c.
+reverse-got-it = Ketemu, angkamu { $guess }! Aku butuh { $guesses } tebakan.
+reverse-contradicts = Itu bertentangan dengan jawabanmu: { $error }.
+reverse-only-one = tinggal { $number } yang tersisa
+reverse-only-range = tinggal { $low } sampai { $high } yang tersisa
+reverse-not-higher = tidak mungkin lebih besar dari { $guess }, { $left }
+reverse-not-lower = tidak mungkin lebih kecil dari { $guess }, { $left }
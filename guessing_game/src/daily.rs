@@ -0,0 +1,39 @@
+//! Daily puzzle: the secret comes from the date, so everyone playing the
+//! same range or word list on the same day gets the same one.
+
+use crate::{
+    game::{Outcome, Round},
+    i18n::t,
+};
+use chrono::{NaiveDate, Utc};
+
+/// Today in UTC, so the puzzle changes at the same moment everywhere.
+pub fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+/// RNG seed for `date`: FNV-1a of the date, which unlike the standard
+/// library's hasher is the same on every platform and Rust version.
+pub fn seed(date: NaiveDate) -> u64 {
+    format!("guessing_game {date}")
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// Result of the day's round to paste to friends, one mark per guess.
+pub fn share(round: &dyn Round, date: NaiveDate) -> String {
+    let score = match round.outcome() {
+        Some(Outcome::Won) => t!("daily-guesses", guesses = round.attempts()),
+        _ => String::from("X"),
+    };
+    let marks: String = round.history().iter().map(|guess| guess.mark).collect();
+    let heading = t!(
+        "daily-share",
+        date = date.to_string(),
+        table = round.table(),
+        score = score,
+    );
+    format!("{heading}\n{marks}")
+}
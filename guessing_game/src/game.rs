@@ -0,0 +1,403 @@
+use crate::{
+    daily,
+    hangman::Hangman,
+    i18n::t,
+    score::HintKind,
+    state::{GameState, Verdict},
+};
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rand_distr::{Distribution as _, Normal};
+use std::{cmp::Ordering, collections::HashSet};
+
+pub use crate::state::Outcome;
+
+/// Guesses a binary search needs at most to find any number in `0..=max`.
+pub fn par(max: u32) -> u32 {
+    (u64::from(max) + 1).next_power_of_two().trailing_zeros()
+}
+
+/// The closest of the 5/10/25 bands `distance` falls in.
+fn proximity(distance: u32) -> Option<u32> {
+    [5, 10, 25].into_iter().find(|&band| distance <= band)
+}
+
+/// One guess and what the game said about it.
+pub struct Guess {
+    pub value: String,
+    pub feedback: Vec<String>,
+    /// Emoji standing for the guess in a daily share string.
+    pub mark: &'static str,
+}
+
+/// A round of either game mode, as the interface and the session see it.
+pub trait Round {
+    /// Heading of the guess history.
+    fn title(&self) -> String;
+
+    fn history(&self) -> &[Guess];
+
+    fn outcome(&self) -> Option<Outcome>;
+
+    fn attempts(&self) -> u32 {
+        self.history().len() as u32
+    }
+
+    /// Guesses left before the round is lost, if it can be lost.
+    fn remaining(&self) -> Option<u32>;
+
+    /// Checks the input as it is typed, so a bad keystroke is refused
+    /// right away.
+    fn check_input(&self, input: &str) -> Result<(), String>;
+
+    /// Evaluates the typed guess and records it.
+    fn submit(&mut self, input: &str) -> Result<(), String>;
+
+    /// Tells what the secret was.
+    fn reveal(&self) -> String;
+
+    /// Guesses a good player needs, if the mode has such a number.
+    fn par(&self) -> Option<u32> {
+        None
+    }
+
+    /// Which high-score table a won round goes into.
+    fn table(&self) -> String;
+
+    /// Hints that can be bought with points, in the order of their keys.
+    fn hint_kinds(&self) -> &'static [HintKind] {
+        &[]
+    }
+
+    /// Tells what a bought hint says about the secret.
+    fn hint(&self, kind: HintKind) -> Result<String, String> {
+        Err(t!("hint-unavailable", hint = kind.name()))
+    }
+}
+
+/// A round of guessing the secret numbers in `0..=max`.
+pub struct Game {
+    state: GameState,
+    distribution: Distribution,
+    /// Report warmer/colder and proximity after every guess.
+    hints: bool,
+    history: Vec<Guess>,
+}
+
+impl Game {
+    pub fn new(state: GameState, distribution: Distribution, hints: bool) -> Self {
+        Self {
+            state,
+            distribution,
+            hints,
+            history: Vec::new(),
+        }
+    }
+
+    /// Evaluates `value` and records it with what it revealed.
+    pub fn guess(&mut self, value: u32) -> Result<(), String> {
+        let evaluation = self.state.guess(value).map_err(|e| e.to_string())?;
+        let mut feedback = Vec::new();
+        let mark = match evaluation.verdict {
+            Verdict::Low => {
+                feedback.push(t!("too-small"));
+                "⬆️"
+            }
+            Verdict::High => {
+                feedback.push(t!("too-big"));
+                "⬇️"
+            }
+            Verdict::Found { left: 0 } => {
+                feedback.push(t!("you-win"));
+                "✅"
+            }
+            Verdict::Found { left } => {
+                feedback.push(t!("found-one", left = left));
+                "✅"
+            }
+        };
+        if self.hints && !matches!(evaluation.verdict, Verdict::Found { .. }) {
+            match evaluation.trend {
+                Some(Ordering::Less) => feedback.push(t!("warmer")),
+                Some(Ordering::Greater) => feedback.push(t!("colder")),
+                Some(Ordering::Equal) => feedback.push(t!("just-as-far")),
+                None => {}
+            }
+            if let Some(band) = proximity(evaluation.distance) {
+                feedback.push(t!("within", band = band));
+            }
+        }
+        self.history.push(Guess {
+            value: value.to_string(),
+            feedback,
+            mark,
+        });
+        Ok(())
+    }
+}
+
+impl Round for Game {
+    fn title(&self) -> String {
+        match self.state.secrets().len() {
+            1 => t!("number-title", max = self.state.max()),
+            count => t!(
+                "numbers-title",
+                count = count,
+                max = self.state.max(),
+                found = self.state.found(),
+            ),
+        }
+    }
+
+    fn history(&self) -> &[Guess] {
+        &self.history
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        self.state.outcome()
+    }
+
+    fn attempts(&self) -> u32 {
+        self.state.guesses()
+    }
+
+    fn remaining(&self) -> Option<u32> {
+        self.state.remaining()
+    }
+
+    fn check_input(&self, input: &str) -> Result<(), String> {
+        if let Some(c) = input.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(t!("only-digits", char = c.to_string()));
+        }
+        match input.parse::<u32>() {
+            Ok(guess) if guess <= self.state.max() => Ok(()),
+            _ => Err(t!("at-most", max = self.state.max())),
+        }
+    }
+
+    fn submit(&mut self, input: &str) -> Result<(), String> {
+        let guess = input
+            .parse()
+            .map_err(|_| t!("not-a-number", input = input))?;
+        self.guess(guess)
+    }
+
+    fn reveal(&self) -> String {
+        reveal_numbers(self.state.secrets())
+    }
+
+    /// A binary search for every secret.
+    fn par(&self) -> Option<u32> {
+        Some(par(self.state.max()) * self.state.secrets().len() as u32)
+    }
+
+    /// Rounds with more secrets or another distribution score apart,
+    /// e.g. `number 0-100 x3 normal`.
+    fn table(&self) -> String {
+        let mut table = format!("number 0-{}", self.state.max());
+        if self.state.secrets().len() > 1 {
+            table += &format!(" x{}", self.state.secrets().len());
+        }
+        if self.distribution != Distribution::Uniform {
+            table += &format!(" {}", self.distribution.name());
+        }
+        table
+    }
+
+    fn hint_kinds(&self) -> &'static [HintKind] {
+        &[HintKind::Parity, HintKind::DigitSum, HintKind::Range]
+    }
+
+    /// Hints are about the smallest secret still hidden.
+    fn hint(&self, kind: HintKind) -> Result<String, String> {
+        let Some(secret) = self.state.hidden().next() else {
+            return Err(t!("round-over"));
+        };
+        let max = self.state.max();
+        Ok(match kind {
+            HintKind::Parity if secret.is_multiple_of(2) => t!("hint-even"),
+            HintKind::Parity => t!("hint-odd"),
+            HintKind::DigitSum => {
+                let sum: u32 = secret
+                    .to_string()
+                    .chars()
+                    .filter_map(|c| c.to_digit(10))
+                    .sum();
+                t!("hint-digits-add-up", sum = sum)
+            }
+            HintKind::Range => {
+                // The quarter of the range the number is in
+                let width = max / 4 + 1;
+                let low = secret - secret % width;
+                let high = (low + width - 1).min(max);
+                t!("hint-between", low = low, high = high)
+            }
+        })
+    }
+}
+
+/// How the secret numbers are spread over the range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Distribution {
+    /// Every number is as likely
+    Uniform,
+    /// Bell-shaped around the middle of the range, the ends being rare
+    Normal,
+}
+
+impl Distribution {
+    fn name(self) -> &'static str {
+        match self {
+            Distribution::Uniform => "uniform",
+            Distribution::Normal => "normal",
+        }
+    }
+
+    /// A number in `0..=max`.
+    pub fn sample<R: Rng>(self, rng: &mut R, max: u32) -> u32 {
+        match self {
+            Distribution::Uniform => rng.gen_range(0..=max),
+            Distribution::Normal => {
+                // Three standard deviations either way reach the ends
+                let mean = f64::from(max) / 2.0;
+                let normal = Normal::new(mean, mean / 3.0).expect("valid deviation");
+                loop {
+                    let value = normal.sample(rng).round();
+                    if (0.0..=f64::from(max)).contains(&value) {
+                        return value as u32;
+                    }
+                }
+            }
+        }
+    }
+
+    /// `count` different numbers in `0..=max`.
+    pub fn sample_distinct<R: Rng>(self, rng: &mut R, max: u32, count: u32) -> Vec<u32> {
+        match self {
+            Distribution::Uniform => {
+                rand::seq::index::sample(rng, max as usize + 1, count as usize)
+                    .into_iter()
+                    .map(|secret| secret as u32)
+                    .collect()
+            }
+            Distribution::Normal => {
+                let mut seen = HashSet::new();
+                let mut secrets = Vec::new();
+                while secrets.len() < count as usize {
+                    let secret = self.sample(rng, max);
+                    if seen.insert(secret) {
+                        secrets.push(secret);
+                    }
+                }
+                secrets
+            }
+        }
+    }
+}
+
+/// Tells what the secret numbers were.
+pub fn reveal_numbers(secrets: &[u32]) -> String {
+    match secrets {
+        [secret] => t!("number-reveal", secret = *secret),
+        secrets => {
+            let secrets: Vec<String> = secrets.iter().map(u32::to_string).collect();
+            t!("numbers-reveal", secrets = secrets.join(", "))
+        }
+    }
+}
+
+/// Which game is played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Mode {
+    /// Guess a secret number
+    Number,
+    /// Guess a word letter by letter, hangman style
+    Word,
+}
+
+/// How every round of a session is played.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    pub mode: Mode,
+    pub max: u32,
+    /// Secret numbers to find in a round.
+    pub secrets: u32,
+    pub distribution: Distribution,
+    pub limit: Option<u32>,
+    pub hints: bool,
+    /// Points that may be spent on hints in a round.
+    pub hint_budget: u32,
+    /// Words the word mode picks from.
+    pub words: Vec<String>,
+    /// Derive the secret from this date instead of picking it at random.
+    pub daily: Option<NaiveDate>,
+}
+
+impl Rules {
+    /// A round with fresh random secrets, or the day's secrets with `daily`.
+    pub fn new_round(&self) -> Box<dyn Round> {
+        let mut rng = match self.daily {
+            // ChaCha8 is a fixed algorithm, unlike StdRng, so a rand
+            // upgrade doesn't change the day's puzzle
+            Some(date) => ChaCha8Rng::seed_from_u64(daily::seed(date)),
+            None => ChaCha8Rng::from_entropy(),
+        };
+        match self.mode {
+            Mode::Number => {
+                let secrets = self
+                    .distribution
+                    .sample_distinct(&mut rng, self.max, self.secrets);
+                let state = GameState::new(self.max, secrets, self.limit);
+                Box::new(Game::new(state, self.distribution, self.hints))
+            }
+            Mode::Word => {
+                let word = &self.words[rng.gen_range(0..self.words.len())];
+                Box::new(Hangman::new(word, self.limit))
+            }
+        }
+    }
+}
+
+/// Tally of the finished rounds since the game was started.
+#[derive(Debug, Default)]
+pub struct Session {
+    pub rounds: u32,
+    pub wins: u32,
+    pub guesses: u32,
+    /// Fewest guesses of a won round.
+    pub best: Option<u32>,
+    pub points: u32,
+}
+
+impl Session {
+    /// Tallies a finished round that scored `points`.
+    pub fn record(&mut self, round: &dyn Round, points: u32) {
+        self.rounds += 1;
+        self.guesses += round.attempts();
+        self.points += points;
+        if round.outcome() == Some(Outcome::Won) {
+            self.wins += 1;
+            self.best = Some(
+                self.best
+                    .map_or(round.attempts(), |best| best.min(round.attempts())),
+            );
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let best = match self.best {
+            Some(best) => t!("session-best", best = best),
+            None => String::new(),
+        };
+        t!(
+            "session-summary",
+            rounds = self.rounds,
+            wins = self.wins,
+            guesses = self.guesses,
+            points = self.points,
+            best = best,
+        )
+    }
+}
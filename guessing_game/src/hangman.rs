@@ -0,0 +1,163 @@
+//! Word mode: guess a secret word one letter at a time.
+
+use crate::{
+    game::{Guess, Outcome, Round},
+    i18n::t,
+};
+use std::{fs, io, path::Path};
+
+/// Wrong letters allowed when no --attempts is given.
+const LIVES: u32 = 6;
+
+/// Words picked from when no --words file is given.
+const WORDS: &str = include_str!("words.txt");
+
+/// The words of `path`, one per line, or the built-in list without one.
+/// Only words made of ASCII letters are kept.
+pub fn load_words(path: Option<&Path>) -> io::Result<Vec<String>> {
+    let text = match path {
+        Some(path) => fs::read_to_string(path)?,
+        None => String::from(WORDS),
+    };
+    let words: Vec<String> = text
+        .lines()
+        .map(|line| line.trim().to_lowercase())
+        .filter(|word| !word.is_empty() && word.chars().all(|c| c.is_ascii_alphabetic()))
+        .collect();
+    if words.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, t!("no-words")));
+    }
+    Ok(words)
+}
+
+/// A round of guessing the letters of one word.
+pub struct Hangman {
+    word: Vec<char>,
+    found: Vec<bool>,
+    lives: u32,
+    wrong: u32,
+    history: Vec<Guess>,
+    outcome: Option<Outcome>,
+}
+
+impl Hangman {
+    /// `lives` is the number of wrong letters allowed, six by default.
+    pub fn new(word: &str, lives: Option<u32>) -> Self {
+        let word: Vec<char> = word.chars().collect();
+        Self {
+            found: vec![false; word.len()],
+            word,
+            lives: lives.unwrap_or(LIVES),
+            wrong: 0,
+            history: Vec::new(),
+            outcome: None,
+        }
+    }
+
+    /// The word with the letters not found yet as `_`.
+    fn masked(&self) -> String {
+        self.word
+            .iter()
+            .zip(&self.found)
+            .map(|(c, found)| if *found { *c } else { '_' })
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn guessed(&self, letter: char) -> bool {
+        self.history
+            .iter()
+            .any(|guess| guess.value == letter.to_string())
+    }
+}
+
+impl Round for Hangman {
+    fn title(&self) -> String {
+        t!(
+            "word-title",
+            masked = self.masked(),
+            wrong = self.wrong,
+            lives = self.lives,
+        )
+    }
+
+    fn history(&self) -> &[Guess] {
+        &self.history
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    fn remaining(&self) -> Option<u32> {
+        Some(self.lives - self.wrong)
+    }
+
+    fn check_input(&self, input: &str) -> Result<(), String> {
+        let mut chars = input.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), _) if !c.is_ascii_alphabetic() => {
+                Err(t!("only-letters", char = c.to_string()))
+            }
+            (Some(_), Some(_)) => Err(t!("one-letter")),
+            _ => Ok(()),
+        }
+    }
+
+    fn submit(&mut self, input: &str) -> Result<(), String> {
+        if self.outcome.is_some() {
+            return Err(t!("round-over"));
+        }
+        self.check_input(input)?;
+        let Some(letter) = input.chars().next().map(|c| c.to_ascii_lowercase()) else {
+            return Ok(());
+        };
+        if self.guessed(letter) {
+            return Err(t!("already-guessed", letter = letter.to_string()));
+        }
+
+        let mut hits = 0;
+        for (c, found) in self.word.iter().zip(&mut self.found) {
+            if *c == letter {
+                *found = true;
+                hits += 1;
+            }
+        }
+        let feedback = match hits {
+            0 => {
+                self.wrong += 1;
+                t!("letter-missing", letter = letter.to_string())
+            }
+            n => t!("letter-found", count = n),
+        };
+        self.history.push(Guess {
+            value: letter.to_string(),
+            feedback: vec![feedback],
+            mark: if hits == 0 { "⬛" } else { "🟩" },
+        });
+
+        if self.found.iter().all(|found| *found) {
+            self.outcome = Some(Outcome::Won);
+        } else if self.wrong >= self.lives {
+            self.outcome = Some(Outcome::Lost);
+        }
+        Ok(())
+    }
+
+    fn reveal(&self) -> String {
+        t!("word-reveal", word = self.word.iter().collect::<String>())
+    }
+
+    /// Guessing every distinct letter of the word and nothing else.
+    fn par(&self) -> Option<u32> {
+        let mut letters = self.word.clone();
+        letters.sort_unstable();
+        letters.dedup();
+        Some(letters.len() as u32)
+    }
+
+    fn table(&self) -> String {
+        String::from("word")
+    }
+}
@@ -0,0 +1,92 @@
+//! Messages shown to the player, looked up in the Fluent catalog of the
+//! language picked with --lang. The catalogs are `locales/<lang>.ftl`.
+
+use clap::ValueEnum;
+use fluent::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+/// Language of the messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Lang {
+    /// English
+    En,
+    /// Bahasa Indonesia
+    Id,
+}
+
+impl Lang {
+    /// The language of the locale environment variables, English unless
+    /// they ask for Indonesian.
+    pub fn from_env() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(std::env::var_os)
+            .find(|value| !value.is_empty());
+        match locale {
+            Some(locale) if locale.to_string_lossy().starts_with("id") => Lang::Id,
+            _ => Lang::En,
+        }
+    }
+
+    fn tag(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Id => "id",
+        }
+    }
+
+    fn catalog(self) -> &'static str {
+        match self {
+            Lang::En => include_str!("../locales/en.ftl"),
+            Lang::Id => include_str!("../locales/id.ftl"),
+        }
+    }
+}
+
+static BUNDLE: OnceLock<FluentBundle<FluentResource>> = OnceLock::new();
+
+fn bundle(lang: Lang) -> FluentBundle<FluentResource> {
+    let tag: LanguageIdentifier = lang.tag().parse().expect("valid language tag");
+    let mut bundle = FluentBundle::new_concurrent(vec![tag]);
+    // Unicode isolation marks show up as junk in most terminals
+    bundle.set_use_isolating(false);
+    let resource = FluentResource::try_new(lang.catalog().to_string())
+        .unwrap_or_else(|(_, errors)| panic!("bad {} catalog: {errors:?}", lang.tag()));
+    bundle
+        .add_resource(resource)
+        .unwrap_or_else(|errors| panic!("bad {} catalog: {errors:?}", lang.tag()));
+    bundle
+}
+
+/// Picks the language of every message from now on; English is used if
+/// this isn't called before the first message.
+pub fn init(lang: Lang) {
+    let _ = BUNDLE.set(bundle(lang));
+}
+
+/// The message `id` with `args` filled in, or the id itself if the
+/// catalog lacks it.
+pub fn message(id: &str, args: Option<&FluentArgs>) -> String {
+    let bundle = BUNDLE.get_or_init(|| bundle(Lang::En));
+    let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+        return id.to_string();
+    };
+    let mut errors = Vec::new();
+    bundle
+        .format_pattern(pattern, args, &mut errors)
+        .into_owned()
+}
+
+/// Looks up a message in the catalog: `t!("number-title", max = 100)`.
+macro_rules! t {
+    ($id:literal) => {
+        $crate::i18n::message($id, None)
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::message($id, Some(&args))
+    }};
+}
+pub(crate) use t;
@@ -1,51 +1,404 @@
-use rand::Rng;
-use std::cmp::Ordering;
-use std::io;
-use std::process::Command;
+mod daily;
+mod game;
+mod hangman;
+mod i18n;
+mod net;
+mod reverse;
+mod score;
+mod scores;
+mod state;
+mod ui;
 
-fn clear_screen() {
-    if cfg!(target_os = "windows") {
-        Command::new("cmd").args(["/c", "cls"]).status().unwrap();
-    } else {
-        Command::new("clear").status().unwrap();
+use clap::{error::ErrorKind, CommandFactory, Parser, ValueEnum};
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use game::{par, Distribution, Mode, Outcome, Round, Rules, Session};
+use i18n::{t, Lang};
+use ratatui::{backend::CrosstermBackend, widgets::ListState, Terminal};
+use score::HintKind;
+use scores::{HighScores, Score};
+use state::GameState;
+use std::{io, path::PathBuf, time::Instant};
+
+/// Guess the secret number.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Play the day's puzzle, the same for everyone, once
+    #[arg(long, conflicts_with_all = ["reverse", "host", "join"])]
+    daily: bool,
+
+    /// Language of the messages; taken from LANG if not given
+    #[arg(long, value_enum)]
+    lang: Option<Lang>,
+
+    /// Game to play
+    #[arg(long, value_enum, default_value = "number")]
+    mode: Mode,
+
+    /// Word list for --mode word, one word per line; a built-in list if not given
+    #[arg(long, value_name = "PATH")]
+    words: Option<PathBuf>,
+
+    /// File the high scores are kept in; defaults to ~/.guessing_game_scores
+    #[arg(long, value_name = "PATH")]
+    scores: Option<PathBuf>,
+
+    /// Preset range for the secret number
+    #[arg(long, value_enum, default_value = "medium")]
+    difficulty: Difficulty,
+
+    /// Largest possible secret number, overriding the difficulty's range
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    max: Option<u32>,
+
+    /// How the secret numbers are spread over the range
+    #[arg(long, value_enum, default_value = "uniform")]
+    distribution: Distribution,
+
+    /// Different secret numbers to find in one round
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    secrets: u32,
+
+    /// Lose after as many guesses as a binary search needs for the range
+    #[arg(long)]
+    limited: bool,
+
+    /// Lose after this many guesses, or this many wrong letters in word mode
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    attempts: Option<u32>,
+
+    /// Also say whether a guess is warmer or colder than the last one and
+    /// whether it is within 5, 10 or 25 of the number
+    #[arg(long)]
+    hints: bool,
+
+    /// Points that may be spent per round on hints bought with F1, F2 and F3
+    #[arg(long, value_name = "POINTS", default_value_t = score::HINT_BUDGET)]
+    hint_budget: u32,
+
+    /// Think of a number yourself and let the computer guess it
+    #[arg(long, conflicts_with_all = ["host", "join"])]
+    reverse: bool,
+
+    /// Pick the secret and let --players remote players take turns guessing it
+    #[arg(long, conflicts_with = "join")]
+    host: bool,
+
+    /// Port to listen on with --host
+    #[arg(long, default_value_t = 7878)]
+    port: u16,
+
+    /// Players to wait for before a hosted game starts
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..))]
+    players: u8,
+
+    /// Play in the game hosted at this address, e.g. 192.168.1.10:7878
+    #[arg(long, value_name = "ADDR")]
+    join: Option<String>,
+
+    /// Name shown to the other players with --join
+    #[arg(long, default_value = "player")]
+    name: String,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Difficulty {
+    /// 0 to 10
+    Easy,
+    /// 0 to 100
+    Medium,
+    /// 0 to 1000
+    Hard,
+}
+
+impl Difficulty {
+    fn max(self) -> u32 {
+        match self {
+            Difficulty::Easy => 10,
+            Difficulty::Medium => 100,
+            Difficulty::Hard => 1000,
+        }
     }
 }
 
-fn main() {
-    let secret_number = rand::thread_rng().gen_range(0..100);
-    loop {
-        println!("Guess the number!");
+/// The round being played and what is typed into the input box.
+pub struct App {
+    pub rules: Rules,
+    pub game: Box<dyn Round>,
+    pub session: Session,
+    pub scores: HighScores,
+    /// When the round being played began.
+    pub started: Instant,
+    /// Hints bought this round and what they said.
+    pub hints: Vec<(HintKind, String)>,
+    /// Points of the round just won.
+    pub points: Option<u32>,
+    /// Place in the high scores of the round just won, if it made it in.
+    pub place: Option<usize>,
+    pub input: String,
+    /// Why the last input was rejected, shown in the status bar.
+    pub message: Option<String>,
+    pub history_state: ListState,
+}
 
-        let mut guess = String::new();
+impl App {
+    fn new(rules: Rules, scores: HighScores) -> Self {
+        Self {
+            game: rules.new_round(),
+            rules,
+            session: Session::default(),
+            scores,
+            started: Instant::now(),
+            hints: Vec::new(),
+            points: None,
+            place: None,
+            input: String::new(),
+            message: None,
+            history_state: ListState::default(),
+        }
+    }
 
-        println!("Please input your guess.");
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("failed to read line.");
+    fn submit(&mut self) {
+        let input = std::mem::take(&mut self.input);
+        if input.is_empty() {
+            return;
+        }
+        self.message = self.game.submit(&input).err();
+        self.history_state
+            .select(self.game.history().len().checked_sub(1));
+        let Some(outcome) = self.game.outcome() else {
+            return;
+        };
+        if outcome == Outcome::Won {
+            let attempts = self.game.attempts();
+            let points = score::points(
+                self.game.par().unwrap_or(attempts),
+                attempts,
+                self.started.elapsed(),
+                self.hint_cost(),
+            );
+            self.points = Some(points);
+            self.place = self.scores.add(
+                &self.game.table(),
+                Score {
+                    points,
+                    guesses: attempts,
+                },
+            );
+            if let Err(e) = self.scores.save() {
+                self.message = Some(t!("save-failed", error = e.to_string()));
+            }
+        }
+        self.session
+            .record(self.game.as_ref(), self.points.unwrap_or(0));
+    }
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
+    /// Points spent on hints this round.
+    pub fn hint_cost(&self) -> u32 {
+        self.hints.iter().map(|(kind, _)| kind.cost()).sum()
+    }
+
+    /// Buys the hint of the F-key `key`, if it's still within the budget.
+    fn buy_hint(&mut self, key: u8) {
+        let kinds = self.game.hint_kinds();
+        let Some(&kind) = usize::from(key).checked_sub(1).and_then(|i| kinds.get(i)) else {
+            return;
         };
+        if self.hints.iter().any(|(bought, _)| *bought == kind) {
+            self.message = Some(t!("hint-bought-already", hint = kind.name()));
+            return;
+        }
+        let left = self.rules.hint_budget.saturating_sub(self.hint_cost());
+        if kind.cost() > left {
+            self.message = Some(t!(
+                "hint-over-budget",
+                hint = kind.name(),
+                cost = kind.cost(),
+                left = left,
+            ));
+            return;
+        }
+        match self.game.hint(kind) {
+            Ok(hint) => {
+                self.hints.push((kind, hint));
+                self.message = None;
+            }
+            Err(e) => self.message = Some(e),
+        }
+    }
 
-        println!("your guess {guess}");
+    /// Adds `c` to the input, rejecting it right away if the round
+    /// wouldn't take it.
+    fn type_char(&mut self, c: char) {
+        let typed = format!("{}{c}", self.input);
+        match self.game.check_input(&typed) {
+            Ok(()) => {
+                self.input = typed;
+                self.message = None;
+            }
+            Err(e) => self.message = Some(e),
+        }
+    }
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => {
-                println!("To small!");
-                std::thread::sleep(std::time::Duration::from_secs(1));
-                clear_screen();
+    fn play_again(&mut self) {
+        self.game = self.rules.new_round();
+        self.started = Instant::now();
+        self.hints.clear();
+        self.points = None;
+        self.place = None;
+        self.message = None;
+        self.history_state = ListState::default();
+    }
+
+    fn scroll(&mut self, by: isize) {
+        let Some(last) = self.game.history().len().checked_sub(1) else {
+            return;
+        };
+        let selected = self.history_state.selected().unwrap_or(last);
+        self.history_state
+            .select(Some(selected.saturating_add_signed(by).min(last)));
+    }
+}
+
+fn main() -> io::Result<()> {
+    let cli = Cli::parse();
+    i18n::init(cli.lang.unwrap_or_else(Lang::from_env));
+    let max = cli.max.unwrap_or_else(|| cli.difficulty.max());
+    if u64::from(cli.secrets) > u64::from(max) + 1 {
+        Cli::command()
+            .error(
+                ErrorKind::ValueValidation,
+                format!(
+                    "--secrets {} is more than the {} numbers in 0..={max}",
+                    cli.secrets,
+                    u64::from(max) + 1
+                ),
+            )
+            .exit();
+    }
+    if cli.reverse {
+        return reverse::play(max);
+    }
+    let number_limit = cli.attempts.or(cli.limited.then(|| par(max) * cli.secrets));
+    if cli.host || cli.join.is_some() {
+        let runtime = tokio::runtime::Runtime::new()?;
+        let result = match &cli.join {
+            Some(addr) => runtime.block_on(net::join(addr, &cli.name)),
+            None => {
+                let secrets =
+                    cli.distribution
+                        .sample_distinct(&mut rand::thread_rng(), max, cli.secrets);
+                let game = GameState::new(max, secrets, number_limit);
+                runtime.block_on(net::host(cli.port, cli.players as usize, game))
+            }
+        };
+        // Don't wait for the blocking read of stdin to finish
+        runtime.shutdown_background();
+        return result;
+    }
+    let limit = match cli.mode {
+        Mode::Number => number_limit,
+        Mode::Word => cli.attempts,
+    };
+    let words = match cli.mode {
+        Mode::Number => Vec::new(),
+        Mode::Word => hangman::load_words(cli.words.as_deref())?,
+    };
+    let scores = HighScores::load(cli.scores.unwrap_or_else(HighScores::default_path))?;
+    let mut app = App::new(
+        Rules {
+            mode: cli.mode,
+            max,
+            secrets: cli.secrets,
+            distribution: cli.distribution,
+            limit,
+            hints: cli.hints,
+            hint_budget: cli.hint_budget,
+            words,
+            daily: cli.daily.then(daily::today),
+        },
+        scores,
+    );
+
+    // Give the terminal back before a panic message is printed
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        default_hook(info);
+    }));
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = run(&mut terminal, &mut app);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result?;
+
+    if app.game.outcome().is_none() {
+        println!("{}", app.game.reveal());
+    } else if let Some(date) = app.rules.daily {
+        println!("{}", daily::share(app.game.as_ref(), date));
+    }
+    println!("{}", app.session.summary());
+    let table = app.game.table();
+    let best = app.scores.table(&table);
+    if !best.is_empty() {
+        let best: Vec<String> = best
+            .iter()
+            .map(|score| t!("high-score", points = score.points, guesses = score.guesses))
+            .collect();
+        println!(
+            "{}",
+            t!("high-scores", table = table, scores = best.join(", "))
+        );
+    }
+    Ok(())
+}
+
+fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> io::Result<()> {
+    loop {
+        terminal.draw(|f| ui::draw(f, app))?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Esc => return Ok(()),
+            // In a word round 'q' is a letter, not quit
+            KeyCode::Char('q')
+                if app.rules.mode == Mode::Number || app.game.outcome().is_some() =>
+            {
+                return Ok(())
             }
-            Ordering::Greater => {
-                println!("To big!");
-                std::thread::sleep(std::time::Duration::from_secs(1));
-                clear_screen();
+            // The day has one puzzle only
+            KeyCode::Enter if app.game.outcome().is_some() && app.rules.daily.is_some() => {
+                return Ok(())
             }
-            Ordering::Equal => {
-                println!("You Win!");
-                break;
+            KeyCode::Enter if app.game.outcome().is_some() => app.play_again(),
+            KeyCode::Enter => app.submit(),
+            KeyCode::Char(c) if app.game.outcome().is_none() => app.type_char(c),
+            KeyCode::F(key) if app.game.outcome().is_none() => app.buy_hint(key),
+            KeyCode::Backspace => {
+                app.input.pop();
             }
+            KeyCode::Up => app.scroll(-1),
+            KeyCode::Down => app.scroll(1),
+            KeyCode::PageUp => app.scroll(-10),
+            KeyCode::PageDown => app.scroll(10),
+            _ => {}
         }
     }
-    println!("the secret number is {secret_number}");
 }
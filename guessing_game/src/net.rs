@@ -0,0 +1,319 @@
+//! Multiplayer over TCP: the host picks the secrets and the players who
+//! `--join` take turns guessing them, sharing any limit on the guesses.
+//!
+//! The protocol is one line per message, so `nc` works as a client too.
+//! A client sends `NAME <name>` once and then `GUESS <number>` on its turn.
+//! The host sends `HELLO <max>`, `WELCOME <name>` with the name it gave
+//! the player, `JOINED <name>`, `START <max> <players>`,
+//! `TURN <name>`, `RESULT <name> <number> LOW|HIGH|CORRECT`,
+//! `WINNER <name> <guesses>`, `LOST <secret>,<secret>...`, `LEFT <name>`
+//! and `ERROR <message>`.
+
+use crate::{
+    game::reveal_numbers,
+    i18n::t,
+    state::{GameState, Outcome, Verdict},
+};
+use fluent::FluentValue;
+use std::{io, net::SocketAddr};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpListener, TcpStream,
+    },
+    sync::mpsc,
+};
+
+struct Player {
+    name: String,
+    writer: OwnedWriteHalf,
+    guesses: u32,
+}
+
+/// A line from player `.0`, or `None` once it disconnected.
+type Incoming = (usize, Option<String>);
+
+/// A client that sent its name: its lines, its writer, the name it asked
+/// for and where it connected from.
+type Greeted = (
+    Lines<BufReader<OwnedReadHalf>>,
+    OwnedWriteHalf,
+    String,
+    SocketAddr,
+);
+
+/// Waits for `players` to join on `port`, then runs one round of `game`.
+pub async fn host(port: u16, players: usize, mut game: GameState) -> io::Result<()> {
+    let max = game.max();
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    println!("{}", t!("hosting", port = port, players = players));
+
+    let (incoming_tx, mut incoming) = mpsc::unbounded_channel::<Incoming>();
+    let (greeted_tx, mut greeted) = mpsc::unbounded_channel::<Greeted>();
+    let mut joined: Vec<Option<Player>> = Vec::new();
+    while joined.len() < players {
+        // Each client is greeted on its own task so one that never sends
+        // its name doesn't keep the others out
+        let (mut lines, mut writer, name, addr) = tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, addr) = accepted?;
+                tokio::spawn(greet(stream, addr, max, greeted_tx.clone()));
+                continue;
+            }
+            Some(client) = greeted.recv() => client,
+        };
+        let name = unique_name(&joined, name);
+        if writer
+            .write_all(format!("WELCOME {name}\n").as_bytes())
+            .await
+            .is_err()
+        {
+            continue;
+        }
+        println!(
+            "{}",
+            t!("host-joined", name = &name, addr = addr.to_string())
+        );
+        broadcast(&mut joined, &format!("JOINED {name}")).await;
+
+        let id = joined.len();
+        let tx = incoming_tx.clone();
+        tokio::spawn(async move {
+            while let Ok(Some(line)) = lines.next_line().await {
+                if tx.send((id, Some(line))).is_err() {
+                    return;
+                }
+            }
+            let _ = tx.send((id, None));
+        });
+        joined.push(Some(Player {
+            name,
+            writer,
+            guesses: 0,
+        }));
+    }
+    drop(incoming_tx);
+
+    broadcast(&mut joined, &format!("START {max} {players}")).await;
+    let mut turn = 0;
+    announce_turn(&mut joined, turn).await;
+
+    while let Some((id, line)) = incoming.recv().await {
+        let Some(line) = line else {
+            if let Some(player) = joined[id].take() {
+                println!("{}", t!("host-left", name = &player.name));
+                broadcast(&mut joined, &format!("LEFT {}", player.name)).await;
+            }
+            if joined.iter().all(Option::is_none) {
+                let reveal = reveal_numbers(game.secrets());
+                println!("{}", t!("host-everyone-left", reveal = reveal));
+                return Ok(());
+            }
+            if id == turn {
+                turn = next_turn(&joined, turn);
+                announce_turn(&mut joined, turn).await;
+            }
+            continue;
+        };
+
+        let guess = match line.strip_prefix("GUESS ").map(|n| n.trim().parse::<u32>()) {
+            _ if id != turn => Err(t!("not-your-turn")),
+            Some(Ok(guess)) => Ok(guess),
+            _ => Err(t!("expected-guess", line = &line)),
+        };
+        let result = guess.and_then(|guess| {
+            game.guess(guess)
+                .map(|evaluation| (guess, evaluation.verdict))
+                .map_err(|e| e.to_string())
+        });
+        let (guess, verdict) = match result {
+            Ok(result) => result,
+            Err(e) => {
+                if let Some(player) = &mut joined[id] {
+                    let _ = send(player, &format!("ERROR {e}")).await;
+                }
+                continue;
+            }
+        };
+
+        let Some(player) = &mut joined[id] else {
+            continue;
+        };
+        player.guesses += 1;
+        let name = player.name.clone();
+        let guesses = player.guesses;
+        let verdict = match verdict {
+            Verdict::Low => "LOW",
+            Verdict::High => "HIGH",
+            Verdict::Found { .. } => "CORRECT",
+        };
+        println!(
+            "{}",
+            t!(
+                "host-guessed",
+                name = &name,
+                guess = guess,
+                verdict = verdict
+            )
+        );
+        broadcast(&mut joined, &format!("RESULT {name} {guess} {verdict}")).await;
+
+        if game.outcome() == Some(Outcome::Won) {
+            println!("{}", t!("host-winner", name = &name, guesses = guesses));
+            broadcast(&mut joined, &format!("WINNER {name} {guesses}")).await;
+            return Ok(());
+        }
+        if game.outcome() == Some(Outcome::Lost) {
+            let reveal = reveal_numbers(game.secrets());
+            println!("{}", t!("host-lost", reveal = reveal));
+            let secrets: Vec<String> = game.secrets().iter().map(u32::to_string).collect();
+            broadcast(&mut joined, &format!("LOST {}", secrets.join(","))).await;
+            return Ok(());
+        }
+        turn = next_turn(&joined, turn);
+        announce_turn(&mut joined, turn).await;
+    }
+    Ok(())
+}
+
+/// Says hello to a new client and hands it to the lobby once it sent its
+/// name; one that disconnects first is dropped.
+async fn greet(
+    stream: TcpStream,
+    addr: SocketAddr,
+    max: u32,
+    lobby: mpsc::UnboundedSender<Greeted>,
+) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    if writer
+        .write_all(format!("HELLO {max}\n").as_bytes())
+        .await
+        .is_err()
+    {
+        return;
+    }
+    let Ok(Some(line)) = lines.next_line().await else {
+        return;
+    };
+    let name = line
+        .strip_prefix("NAME ")
+        .map(single_word)
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| String::from("player"));
+    let _ = lobby.send((lines, writer, name, addr));
+}
+
+/// `name`, or `name` with the first number after it that nobody has yet.
+fn unique_name(players: &[Option<Player>], name: String) -> String {
+    let taken = |candidate: &str| players.iter().flatten().any(|p| p.name == candidate);
+    if !taken(&name) {
+        return name;
+    }
+    (players.len() + 1..)
+        .map(|n| format!("{name}{n}"))
+        .find(|candidate| !taken(candidate))
+        .expect("fewer players than numbers")
+}
+
+/// Names are single words so they can't break up a message.
+fn single_word(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+async fn send(player: &mut Player, message: &str) -> io::Result<()> {
+    player
+        .writer
+        .write_all(format!("{message}\n").as_bytes())
+        .await
+}
+
+/// Sends `message` to everyone still connected; a failed write is left to
+/// that player's reader to notice.
+async fn broadcast(players: &mut [Option<Player>], message: &str) {
+    for player in players.iter_mut().flatten() {
+        let _ = send(player, message).await;
+    }
+}
+
+/// The next connected player after `turn`, in join order.
+fn next_turn(players: &[Option<Player>], turn: usize) -> usize {
+    (1..=players.len())
+        .map(|step| (turn + step) % players.len())
+        .find(|&id| players[id].is_some())
+        .unwrap_or(turn)
+}
+
+async fn announce_turn(players: &mut [Option<Player>], turn: usize) {
+    if let Some(name) = players[turn].as_ref().map(|p| p.name.clone()) {
+        broadcast(players, &format!("TURN {name}")).await;
+    }
+}
+
+/// Plays as `name` against the host at `addr`, reading guesses from stdin.
+pub async fn join(addr: &str, name: &str) -> io::Result<()> {
+    let mut name = single_word(name);
+    let stream = TcpStream::connect(addr).await?;
+    let (reader, mut writer) = stream.into_split();
+    writer
+        .write_all(format!("NAME {name}\n").as_bytes())
+        .await?;
+
+    tokio::spawn(async move {
+        let mut stdin = BufReader::new(tokio::io::stdin()).lines();
+        while let Ok(Some(line)) = stdin.next_line().await {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let message = format!("GUESS {line}\n");
+            if writer.write_all(message.as_bytes()).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    let mut lines = BufReader::new(reader).lines();
+    // Counts are passed as numbers so the messages can pick singular or plural
+    while let Some(line) = lines.next_line().await? {
+        let (kind, rest) = line.split_once(' ').unwrap_or((&line, ""));
+        let words: Vec<&str> = rest.split_whitespace().collect();
+        let text = match (kind, words.as_slice()) {
+            ("HELLO", [max]) => t!("client-connected", max = *max),
+            ("WELCOME", [given]) => {
+                name = given.to_string();
+                t!("client-welcome", name = *given)
+            }
+            ("JOINED", [who]) => t!("client-joined", name = *who),
+            ("START", [_, players]) => {
+                t!("client-start", players = FluentValue::try_number(players))
+            }
+            ("TURN", [who]) if *who == name => t!("client-your-turn"),
+            ("TURN", [who]) => t!("client-turn", name = *who),
+            ("RESULT", [who, guess, "LOW"]) => t!("client-too-small", name = *who, guess = *guess),
+            ("RESULT", [who, guess, "HIGH"]) => t!("client-too-big", name = *who, guess = *guess),
+            ("RESULT", [who, guess, _]) => t!("client-correct", name = *who, guess = *guess),
+            ("WINNER", [who, guesses]) => {
+                let guesses = FluentValue::try_number(guesses);
+                if *who == name {
+                    println!("{}", t!("client-you-win", guesses = guesses.clone()));
+                } else {
+                    println!("{}", t!("client-winner", name = *who, guesses = guesses));
+                }
+                return Ok(());
+            }
+            ("LOST", [secrets]) => {
+                let secrets: Vec<u32> = secrets.split(',').filter_map(|s| s.parse().ok()).collect();
+                println!("{}", t!("client-lost", reveal = reveal_numbers(&secrets)));
+                return Ok(());
+            }
+            ("LEFT", [who]) => t!("client-left", name = *who),
+            ("ERROR", _) => rest.to_string(),
+            _ => line.clone(),
+        };
+        println!("{text}");
+    }
+    println!("{}", t!("client-closed"));
+    Ok(())
+}
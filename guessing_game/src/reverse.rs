@@ -0,0 +1,107 @@
+//! Reverse mode: the player thinks of a number and the computer
+//! binary-searches for it.
+
+use crate::i18n::t;
+use std::io::{self, BufRead, Write};
+
+enum Answer {
+    Higher,
+    Lower,
+    Correct,
+}
+
+impl Answer {
+    fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_lowercase().as_str() {
+            "h" | "higher" | "+" => Some(Answer::Higher),
+            "l" | "lower" | "-" => Some(Answer::Lower),
+            "c" | "correct" | "=" => Some(Answer::Correct),
+            _ => None,
+        }
+    }
+}
+
+/// What is still possible after the answers so far.
+struct Search {
+    low: u32,
+    high: u32,
+    guesses: u32,
+}
+
+impl Search {
+    fn guess(&self) -> u32 {
+        self.low + (self.high - self.low) / 2
+    }
+
+    fn left(&self) -> String {
+        if self.low == self.high {
+            t!("reverse-only-one", number = self.low)
+        } else {
+            t!("reverse-only-range", low = self.low, high = self.high)
+        }
+    }
+
+    /// Narrows the range to the side `higher` says, refusing an answer no
+    /// number still in it could give.
+    fn narrow(&mut self, higher: bool) -> Result<(), String> {
+        let guess = self.guess();
+        match higher {
+            true if guess == self.high => {
+                Err(t!("reverse-not-higher", guess = guess, left = self.left()))
+            }
+            false if guess == self.low => {
+                Err(t!("reverse-not-lower", guess = guess, left = self.left()))
+            }
+            true => {
+                self.low = guess + 1;
+                Ok(())
+            }
+            false => {
+                self.high = guess - 1;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Asks about guesses on stdin until the player says one is correct.
+pub fn play(max: u32) -> io::Result<()> {
+    println!("{}", t!("reverse-intro", max = max));
+    println!("{}", t!("reverse-answers"));
+    let mut search = Search {
+        low: 0,
+        high: max,
+        guesses: 0,
+    };
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        let guess = search.guess();
+        search.guesses += 1;
+        loop {
+            print!("{}", t!("reverse-ask", guess = guess));
+            io::stdout().flush()?;
+            let Some(line) = lines.next().transpose()? else {
+                return Ok(());
+            };
+            let Some(answer) = Answer::parse(&line) else {
+                println!("{}", t!("reverse-answer-hlc"));
+                continue;
+            };
+            let narrowed = match answer {
+                Answer::Higher => search.narrow(true),
+                Answer::Lower => search.narrow(false),
+                Answer::Correct => {
+                    println!(
+                        "{}",
+                        t!("reverse-got-it", guess = guess, guesses = search.guesses)
+                    );
+                    return Ok(());
+                }
+            };
+            match narrowed {
+                Ok(()) => break,
+                Err(e) => println!("{}", t!("reverse-contradicts", error = e)),
+            }
+        }
+    }
+}
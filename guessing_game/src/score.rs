@@ -0,0 +1,52 @@
+//! Points for a won round and the hints that cost some of them.
+
+use crate::i18n::t;
+use std::time::Duration;
+
+/// Points a round is worth per guess of par, so larger ranges pay more.
+const POINTS_PER_PAR: u32 = 100;
+/// Bonus per guess under par, and penalty per guess over it.
+const POINTS_PER_GUESS: u32 = 50;
+/// Penalty per second the round took.
+const POINTS_PER_SECOND: u32 = 1;
+
+/// Points spent on hints allowed per round when no --hint-budget is given.
+pub const HINT_BUDGET: u32 = 200;
+
+/// What a hint tells about the secret number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintKind {
+    /// Whether it's even or odd
+    Parity,
+    /// The sum of its digits
+    DigitSum,
+    /// A range a quarter as wide as the whole one that holds it
+    Range,
+}
+
+impl HintKind {
+    pub fn cost(self) -> u32 {
+        match self {
+            HintKind::Parity => 50,
+            HintKind::DigitSum => 75,
+            HintKind::Range => 100,
+        }
+    }
+
+    pub fn name(self) -> String {
+        match self {
+            HintKind::Parity => t!("hint-parity"),
+            HintKind::DigitSum => t!("hint-digit-sum"),
+            HintKind::Range => t!("hint-range"),
+        }
+    }
+}
+
+/// Points of a won round: worth `par` guesses, more for guessing under par,
+/// less for every guess over it, every second taken and the hints bought.
+pub fn points(par: u32, attempts: u32, elapsed: Duration, hint_cost: u32) -> u32 {
+    let earned = POINTS_PER_PAR * par + POINTS_PER_GUESS * par;
+    let lost =
+        POINTS_PER_GUESS * attempts + POINTS_PER_SECOND * elapsed.as_secs() as u32 + hint_cost;
+    earned.saturating_sub(lost)
+}
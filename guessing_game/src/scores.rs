@@ -0,0 +1,118 @@
+//! High scores kept between runs: the points of won rounds, per table such
+//! as `number 0-100` or `word`.
+
+use std::{
+    cmp::Reverse,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Scores kept per table.
+const KEEP: usize = 5;
+
+/// Points of a won round and the guesses it took.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Score {
+    pub points: u32,
+    pub guesses: u32,
+}
+
+impl Score {
+    /// Most points first, fewest guesses breaking ties.
+    fn rank(&self) -> (Reverse<u32>, u32) {
+        (Reverse(self.points), self.guesses)
+    }
+}
+
+struct Entry {
+    table: String,
+    score: Score,
+}
+
+pub struct HighScores {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl HighScores {
+    /// `$HOME/.guessing_game_scores`, or the current directory without a home.
+    pub fn default_path() -> PathBuf {
+        let file = ".guessing_game_scores";
+        match std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+            Some(home) => Path::new(&home).join(file),
+            None => PathBuf::from(file),
+        }
+    }
+
+    /// Reads the table at `path`; a missing file is an empty table. Lines
+    /// from before points were kept count as no points.
+    pub fn load(path: PathBuf) -> io::Result<Self> {
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        let entries = text
+            .lines()
+            .filter_map(|line| {
+                let (table, points, guesses) = match line.split('\t').collect::<Vec<_>>()[..] {
+                    [table, points, guesses] => (table, points.parse().ok()?, guesses),
+                    [table, guesses] => (table, 0, guesses),
+                    _ => return None,
+                };
+                Some(Entry {
+                    table: table.to_string(),
+                    score: Score {
+                        points,
+                        guesses: guesses.parse().ok()?,
+                    },
+                })
+            })
+            .collect();
+        Ok(Self { path, entries })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let text: String = self
+            .entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}\t{}\t{}\n",
+                    entry.table, entry.score.points, entry.score.guesses
+                )
+            })
+            .collect();
+        fs::write(&self.path, text)
+    }
+
+    /// The scores of `table`, best first.
+    pub fn table(&self, table: &str) -> Vec<Score> {
+        let mut scores: Vec<Score> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.table == table)
+            .map(|entry| entry.score)
+            .collect();
+        scores.sort_unstable_by_key(Score::rank);
+        scores
+    }
+
+    /// Records a won round and returns its place in the table, 1 being
+    /// the best, if it made it in.
+    pub fn add(&mut self, table: &str, score: Score) -> Option<usize> {
+        let mut scores = self.table(table);
+        let place = scores.partition_point(|other| other.rank() <= score.rank());
+        if place >= KEEP {
+            return None;
+        }
+        scores.insert(place, score);
+        scores.truncate(KEEP);
+        self.entries.retain(|entry| entry.table != table);
+        self.entries.extend(scores.into_iter().map(|score| Entry {
+            table: table.to_string(),
+            score,
+        }));
+        Some(place + 1)
+    }
+}
@@ -0,0 +1,283 @@
+//! The rules of guessing numbers, without any of the wording: which
+//! secrets are still hidden, what a guess says about them and when the
+//! round is over. Shared by the local game and the multiplayer host.
+
+use crate::i18n::t;
+use std::{cmp::Ordering, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Won,
+    Lost,
+}
+
+/// How a guess compares to the nearest secret still hidden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Low,
+    High,
+    /// Hit a secret; `left` are still hidden.
+    Found {
+        left: usize,
+    },
+}
+
+/// What one guess revealed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Evaluation {
+    pub verdict: Verdict,
+    /// How far the guess was from the nearest hidden secret.
+    pub distance: u32,
+    /// Whether `distance` is smaller, larger or the same as the last
+    /// guess's, which is `None` for the first guess and after a find.
+    pub trend: Option<Ordering>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuessError {
+    RoundOver,
+    OutOfRange { value: u32, max: u32 },
+    AlreadyFound(u32),
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match *self {
+            GuessError::RoundOver => t!("round-over"),
+            GuessError::OutOfRange { value, max } => t!("out-of-range", value = value, max = max),
+            GuessError::AlreadyFound(value) => t!("already-found", value = value),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+/// One round of finding every secret in `0..=max`.
+#[derive(Debug, Clone)]
+pub struct GameState {
+    max: u32,
+    /// Sorted and distinct.
+    secrets: Vec<u32>,
+    found: Vec<bool>,
+    /// Guesses allowed before the round is lost.
+    limit: Option<u32>,
+    guesses: u32,
+    last_distance: Option<u32>,
+    outcome: Option<Outcome>,
+}
+
+impl GameState {
+    /// # Panics
+    ///
+    /// If there are no secrets or one of them is above `max`.
+    pub fn new(max: u32, mut secrets: Vec<u32>, limit: Option<u32>) -> Self {
+        secrets.sort_unstable();
+        secrets.dedup();
+        assert!(!secrets.is_empty(), "a round needs a secret");
+        assert!(
+            secrets.iter().all(|&secret| secret <= max),
+            "secret out of range"
+        );
+        Self {
+            max,
+            found: vec![false; secrets.len()],
+            secrets,
+            limit,
+            guesses: 0,
+            last_distance: None,
+            outcome: None,
+        }
+    }
+
+    pub fn max(&self) -> u32 {
+        self.max
+    }
+
+    pub fn secrets(&self) -> &[u32] {
+        &self.secrets
+    }
+
+    /// The secrets not found yet, smallest first.
+    pub fn hidden(&self) -> impl Iterator<Item = u32> + '_ {
+        self.secrets
+            .iter()
+            .zip(&self.found)
+            .filter(|(_, found)| !**found)
+            .map(|(secret, _)| *secret)
+    }
+
+    pub fn found(&self) -> usize {
+        self.found.iter().filter(|found| **found).count()
+    }
+
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+
+    /// Guesses left before the round is lost, if it can be lost.
+    pub fn remaining(&self) -> Option<u32> {
+        self.limit.map(|limit| limit.saturating_sub(self.guesses))
+    }
+
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    /// Evaluates `value` against the hidden secrets and counts it; a guess
+    /// outside the range or at a secret already found is rejected without
+    /// costing an attempt.
+    pub fn guess(&mut self, value: u32) -> Result<Evaluation, GuessError> {
+        if self.outcome.is_some() {
+            return Err(GuessError::RoundOver);
+        }
+        if value > self.max {
+            return Err(GuessError::OutOfRange {
+                value,
+                max: self.max,
+            });
+        }
+        if let Ok(i) = self.secrets.binary_search(&value) {
+            if self.found[i] {
+                return Err(GuessError::AlreadyFound(value));
+            }
+        }
+
+        // Ties go to the smaller secret
+        let nearest = self
+            .hidden()
+            .min_by_key(|secret| secret.abs_diff(value))
+            .expect("a round in play has a hidden secret");
+        let distance = nearest.abs_diff(value);
+        let trend = self.last_distance.map(|last| distance.cmp(&last));
+        self.guesses += 1;
+        let verdict = match value.cmp(&nearest) {
+            Ordering::Less => Verdict::Low,
+            Ordering::Greater => Verdict::High,
+            Ordering::Equal => {
+                let i = self.secrets.binary_search(&value).unwrap_or_default();
+                self.found[i] = true;
+                Verdict::Found {
+                    left: self.secrets.len() - self.found(),
+                }
+            }
+        };
+        self.last_distance = match verdict {
+            // The next guess is after another secret
+            Verdict::Found { .. } => None,
+            _ => Some(distance),
+        };
+
+        if verdict == (Verdict::Found { left: 0 }) {
+            self.outcome = Some(Outcome::Won);
+        } else if self.remaining() == Some(0) {
+            self.outcome = Some(Outcome::Lost);
+        }
+        Ok(Evaluation {
+            verdict,
+            distance,
+            trend,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_low_high_and_win() {
+        let mut state = GameState::new(100, vec![42], None);
+        assert_eq!(state.guess(10).unwrap().verdict, Verdict::Low);
+        assert_eq!(state.guess(90).unwrap().verdict, Verdict::High);
+        assert_eq!(state.outcome(), None);
+        assert_eq!(state.guess(42).unwrap().verdict, Verdict::Found { left: 0 });
+        assert_eq!(state.outcome(), Some(Outcome::Won));
+        assert_eq!(state.guesses(), 3);
+    }
+
+    #[test]
+    fn rejects_guesses_without_counting_them() {
+        let mut state = GameState::new(10, vec![3], None);
+        assert_eq!(
+            state.guess(11),
+            Err(GuessError::OutOfRange { value: 11, max: 10 })
+        );
+        assert_eq!(state.guesses(), 0);
+        state.guess(3).unwrap();
+        assert_eq!(state.guess(3), Err(GuessError::RoundOver));
+        assert_eq!(state.guesses(), 1);
+    }
+
+    #[test]
+    fn loses_when_the_limit_runs_out() {
+        let mut state = GameState::new(10, vec![3], Some(2));
+        state.guess(0).unwrap();
+        assert_eq!(state.remaining(), Some(1));
+        assert_eq!(state.outcome(), None);
+        state.guess(1).unwrap();
+        assert_eq!(state.remaining(), Some(0));
+        assert_eq!(state.outcome(), Some(Outcome::Lost));
+    }
+
+    #[test]
+    fn finding_the_last_secret_on_the_last_guess_wins() {
+        let mut state = GameState::new(10, vec![3], Some(1));
+        state.guess(3).unwrap();
+        assert_eq!(state.outcome(), Some(Outcome::Won));
+    }
+
+    #[test]
+    fn tracks_the_distance_trend() {
+        let mut state = GameState::new(100, vec![50], None);
+        let first = state.guess(10).unwrap();
+        assert_eq!((first.distance, first.trend), (40, None));
+        assert_eq!(state.guess(30).unwrap().trend, Some(Ordering::Less));
+        assert_eq!(state.guess(90).unwrap().trend, Some(Ordering::Greater));
+        assert_eq!(state.guess(10).unwrap().trend, Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn compares_with_the_nearest_hidden_secret() {
+        let mut state = GameState::new(100, vec![80, 20, 50], None);
+        assert_eq!(state.secrets(), [20, 50, 80]);
+        assert_eq!(state.guess(30).unwrap().verdict, Verdict::High);
+        assert_eq!(state.guess(40).unwrap().verdict, Verdict::Low);
+        assert_eq!(state.guess(50).unwrap().verdict, Verdict::Found { left: 2 });
+        // 50 is found, so 45 is nearest to 20 now
+        assert_eq!(state.guess(45).unwrap().verdict, Verdict::High);
+        assert_eq!(state.hidden().collect::<Vec<_>>(), [20, 80]);
+    }
+
+    #[test]
+    fn ties_go_to_the_smaller_secret() {
+        let mut state = GameState::new(100, vec![40, 60], None);
+        assert_eq!(state.guess(50).unwrap().verdict, Verdict::High);
+    }
+
+    #[test]
+    fn wins_once_every_secret_is_found() {
+        let mut state = GameState::new(10, vec![2, 7], None);
+        assert_eq!(state.guess(7).unwrap().verdict, Verdict::Found { left: 1 });
+        assert_eq!(state.outcome(), None);
+        assert_eq!(state.guess(7), Err(GuessError::AlreadyFound(7)));
+        let last = state.guess(2).unwrap();
+        assert_eq!(last.verdict, Verdict::Found { left: 0 });
+        assert_eq!(state.outcome(), Some(Outcome::Won));
+        assert_eq!(state.found(), 2);
+    }
+
+    #[test]
+    fn a_find_resets_the_trend() {
+        let mut state = GameState::new(100, vec![10, 90], None);
+        state.guess(0).unwrap();
+        state.guess(10).unwrap();
+        assert_eq!(state.guess(50).unwrap().trend, None);
+    }
+
+    #[test]
+    fn duplicate_secrets_count_once() {
+        let state = GameState::new(10, vec![4, 4], None);
+        assert_eq!(state.secrets(), [4]);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{game::Outcome, i18n::t, App};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph},
+    Frame,
+};
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(3),
+            Constraint::Length(app.hints.len() as u16),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .split(frame.size());
+
+    draw_history(frame, app, chunks[0]);
+    draw_hints(frame, app, chunks[1]);
+    draw_input(frame, app, chunks[2]);
+    draw_status(frame, app, chunks[3]);
+}
+
+fn draw_history(frame: &mut Frame, app: &mut App, area: Rect) {
+    let history = app.game.history();
+    let items: Vec<ListItem> = history
+        .iter()
+        .enumerate()
+        .map(|(i, guess)| {
+            let color = match app.game.outcome() {
+                Some(Outcome::Won) if i + 1 == history.len() => Color::Green,
+                _ => Color::Yellow,
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:>3}. ", i + 1),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(format!("{:>6}  ", guess.value), Style::default().fg(color)),
+                Span::raw(guess.feedback.join(" ")),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(app.game.title()),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    frame.render_stateful_widget(list, area, &mut app.history_state);
+}
+
+/// The hints bought this round, one per line.
+fn draw_hints(frame: &mut Frame, app: &App, area: Rect) {
+    let lines: Vec<Line> = app
+        .hints
+        .iter()
+        .map(|(kind, hint)| {
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        " {}",
+                        t!("hint-line", hint = kind.name(), cost = kind.cost())
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(hint.clone(), Style::default().fg(Color::Cyan)),
+            ])
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+/// Keys of the hints of the round and what they cost, e.g. `F1 parity (50)`.
+fn hint_keys(app: &App) -> String {
+    app.game
+        .hint_kinds()
+        .iter()
+        .enumerate()
+        .map(|(i, kind)| {
+            t!(
+                "hint-key",
+                key = i + 1,
+                hint = kind.name(),
+                cost = kind.cost()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn draw_input(frame: &mut Frame, app: &App, area: Rect) {
+    let (title, text) = match app.game.outcome() {
+        None if app.game.hint_kinds().is_empty() => (t!("input-title"), app.input.as_str()),
+        None => (
+            t!("input-title-hints", keys = hint_keys(app)),
+            app.input.as_str(),
+        ),
+        Some(_) if app.rules.daily.is_some() => (t!("come-back-tomorrow"), ""),
+        Some(_) => (t!("play-again"), ""),
+    };
+    let input = Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(input, area);
+    if app.game.outcome().is_none() {
+        frame.set_cursor(area.x + 1 + app.input.len() as u16, area.y + 1);
+    }
+}
+
+fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
+    let game = &app.game;
+    let status = match (game.outcome(), &app.message) {
+        (Some(_), Some(message)) | (None, Some(message)) => {
+            Span::styled(message.clone(), Style::default().fg(Color::Red))
+        }
+        (Some(Outcome::Won), None) => {
+            let par = match game.par() {
+                Some(par) => t!("status-par", par = par),
+                None => String::new(),
+            };
+            let place = match app.place {
+                Some(place) => t!("status-place", place = place),
+                None => String::new(),
+            };
+            Span::styled(
+                t!(
+                    "status-won",
+                    reveal = game.reveal(),
+                    guesses = game.attempts(),
+                    par = par,
+                    points = app.points.unwrap_or(0),
+                    place = place,
+                ),
+                Style::default().fg(Color::Green),
+            )
+        }
+        (Some(Outcome::Lost), None) => Span::styled(
+            t!(
+                "status-lost",
+                reveal = game.reveal(),
+                guesses = game.attempts(),
+            ),
+            Style::default().fg(Color::Red),
+        ),
+        (None, None) => {
+            let remaining = match game.remaining() {
+                Some(remaining) => t!("status-remaining", remaining = remaining),
+                None => String::new(),
+            };
+            let spent = match app.hint_cost() {
+                0 => String::new(),
+                cost => t!("status-spent", cost = cost),
+            };
+            Span::raw(t!(
+                "status-playing",
+                round = app.session.rounds + 1,
+                guesses = game.attempts(),
+                remaining = remaining,
+                spent = spent,
+            ))
+        }
+    };
+    frame.render_widget(Paragraph::new(Line::from(status)), area);
+}
@@ -0,0 +1,74 @@
+use crate::client::ApiError;
+use futures::{stream, StreamExt};
+use std::{
+    future::Future,
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
+/// Request yang berjalan bersamaan kalau tidak diatur dengan --concurrency.
+pub const CONCURRENCY: usize = 10;
+
+/// Hasil mengambil banyak id sekaligus.
+pub struct Report<T> {
+    /// Yang berhasil, urut berdasarkan id.
+    pub fetched: Vec<(i32, T)>,
+    /// Yang gagal, urut berdasarkan id.
+    pub failed: Vec<(i32, ApiError)>,
+    pub elapsed: Duration,
+}
+
+impl<T> Report<T> {
+    /// Request per detik, untuk membandingkan --concurrency yang berbeda.
+    pub fn rate(&self) -> f64 {
+        let requests = (self.fetched.len() + self.failed.len()) as f64;
+        requests / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Ambil setiap id dengan `fetch`, paling banyak `concurrency` sekaligus.
+pub async fn fetch_all<T, F, Fut>(ids: Vec<i32>, concurrency: usize, fetch: F) -> Report<T>
+where
+    F: Fn(i32) -> Fut,
+    Fut: Future<Output = Result<T, ApiError>>,
+{
+    let start = Instant::now();
+    let results: Vec<(i32, Result<T, ApiError>)> = stream::iter(ids)
+        .map(|id| {
+            let request = fetch(id);
+            async move { (id, request.await) }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+
+    let mut report = Report {
+        fetched: Vec::new(),
+        failed: Vec::new(),
+        elapsed: start.elapsed(),
+    };
+    for (id, result) in results {
+        match result {
+            Ok(item) => report.fetched.push((id, item)),
+            Err(e) => report.failed.push((id, e)),
+        }
+    }
+    report.fetched.sort_by_key(|(id, _)| *id);
+    report.failed.sort_by_key(|(id, _)| *id);
+    report
+}
+
+/// Id seperti `7` atau rentang seperti `1-20`.
+pub fn parse_ids(arg: &str) -> Result<RangeInclusive<i32>, String> {
+    let (first, last) = arg.split_once('-').unwrap_or((arg, arg));
+    let parse = |id: &str| {
+        id.trim()
+            .parse::<i32>()
+            .map_err(|e| format!("invalid id '{id}': {e}"))
+    };
+    let (first, last) = (parse(first)?, parse(last)?);
+    if first > last {
+        return Err(format!("{first}-{last} is an empty range"));
+    }
+    Ok(first..=last)
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// Body response GET yang disimpan, dengan validator untuk request berikutnya.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Dijawab 304, body diambil dari cache.
+    pub hits: u32,
+    /// Dijawab dengan body lengkap.
+    pub misses: u32,
+    /// Entry yang ditulis ke disk.
+    pub stored: u32,
+}
+
+/// Cache di disk, satu file JSON per URL.
+#[derive(Debug)]
+pub struct Cache {
+    dir: PathBuf,
+    hits: AtomicU32,
+    misses: AtomicU32,
+    stored: AtomicU32,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            hits: AtomicU32::new(0),
+            misses: AtomicU32::new(0),
+            stored: AtomicU32::new(0),
+        }
+    }
+
+    /// `$XDG_CACHE_HOME/http`, atau `~/.cache/http`.
+    pub fn default_dir() -> PathBuf {
+        if let Some(dir) = std::env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+            return Path::new(&dir).join("http");
+        }
+        match std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")) {
+            Some(home) => Path::new(&home).join(".cache").join("http"),
+            None => std::env::temp_dir().join("http-cache"),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    // Nama file dari FNV-1a URL-nya
+    fn path(&self, url: &str) -> PathBuf {
+        let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+        self.dir.join(format!("{hash:016x}.json"))
+    }
+
+    /// Entry untuk `url`; file yang rusak atau milik URL lain dianggap tidak ada.
+    pub fn lookup(&self, url: &str) -> Option<Entry> {
+        let text = fs::read_to_string(self.path(url)).ok()?;
+        let entry: Entry = serde_json::from_str(&text).ok()?;
+        (entry.url == url).then_some(entry)
+    }
+
+    /// Simpan `entry`; gagal menulis cache tidak menggagalkan request.
+    pub fn store(&self, entry: &Entry) {
+        let written = fs::create_dir_all(&self.dir).and_then(|()| {
+            let text = serde_json::to_string(entry)?;
+            fs::write(self.path(&entry.url), text)
+        });
+        match written {
+            Ok(()) => {
+                self.stored.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(e) => eprintln!("warning: could not cache {}: {e}", entry.url),
+        }
+    }
+
+    pub fn hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            stored: self.stored.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,367 @@
+use crate::{
+    cache::{Cache, Entry},
+    graphql,
+};
+use futures::{stream, Stream};
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use reqwest::{
+    header::{HeaderValue, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    Client, Method, Request, RequestBuilder, Response, StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{fmt, num::NonZeroU32, sync::Arc, time::Duration};
+use thiserror::Error;
+
+/// Alamat JSONPlaceholder, API todo yang dipakai secara default.
+pub const BASE_URL: &str = "https://jsonplaceholder.typicode.com";
+
+#[derive(Debug, Error)]
+pub enum ApiError {
+    /// Request gagal terkirim atau response-nya terputus.
+    #[error("request failed: {0}")]
+    Network(#[from] reqwest::Error),
+    /// Body response bukan JSON yang diharapkan.
+    #[error("invalid response body: {0}")]
+    Decode(#[from] serde_json::Error),
+    /// Server menjawab dengan status error.
+    #[error("{status}")]
+    Status { status: StatusCode, body: String },
+    /// Query GraphQL dijawab dengan daftar error.
+    #[error("{}", .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    Graphql(Vec<graphql::Error>),
+}
+
+// Definisikan struct untuk response JSON
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Todo {
+    pub id: i32,
+    #[serde(rename = "userId")]
+    pub user_id: i32,
+    pub title: String,
+    pub completed: bool,
+}
+
+impl fmt::Display for Todo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mark = if self.completed { 'x' } else { ' ' };
+        write!(
+            f,
+            "{:>4} [{mark}] {} (user {})",
+            self.id, self.title, self.user_id
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct User {
+    pub id: i32,
+    pub name: String,
+    pub username: String,
+    pub email: String,
+}
+
+impl fmt::Display for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>4} {} ({}, {})",
+            self.id, self.name, self.username, self.email
+        )
+    }
+}
+
+// Body untuk POST, id dibuat oleh server
+#[derive(Debug, Clone, Serialize)]
+pub struct NewTodo {
+    #[serde(rename = "userId")]
+    pub user_id: i32,
+    pub title: String,
+    pub completed: bool,
+}
+
+// Body untuk PATCH, hanya field yang diisi yang dikirim
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TodoChanges {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed: Option<bool>,
+}
+
+/// Client untuk endpoint `/todos`, dan `/users` pemiliknya.
+#[derive(Debug, Clone)]
+pub struct TodoClient {
+    http: Client,
+    base_url: String,
+    /// Dikirim sebagai `Authorization: Bearer <token>`.
+    token: Option<String>,
+    cache: Option<Arc<Cache>>,
+    /// Batas waktu satu percobaan request, sampai body-nya selesai dibaca.
+    timeout: Option<Duration>,
+    retries: u32,
+    /// Jeda sebelum percobaan ulang pertama, berlipat dua setiap kali.
+    retry_delay: Duration,
+    /// Dipakai bersama oleh semua salinan client, termasuk untuk percobaan ulang.
+    limiter: Option<Arc<DefaultDirectRateLimiter>>,
+}
+
+impl TodoClient {
+    /// Request dikirim lewat `http`, misalnya dari [`Profile::client`].
+    ///
+    /// [`Profile::client`]: crate::profile::Profile::client
+    pub fn new(http: Client, base_url: impl Into<String>) -> Self {
+        Self {
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: None,
+            cache: None,
+            timeout: None,
+            retries: 0,
+            retry_delay: Duration::ZERO,
+            limiter: None,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Ulangi GET dan DELETE sampai `retries` kali kalau gagal tersambung,
+    /// kehabisan waktu, atau dijawab 5xx. POST dan PATCH tidak diulang
+    /// supaya perubahannya tidak terjadi dua kali.
+    pub fn with_retries(mut self, retries: u32, delay: Duration) -> Self {
+        self.retries = retries;
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Kirim `token` sebagai bearer token di setiap request.
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Simpan response GET di `cache` dan validasi ulang dengan server.
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Kirim paling banyak `rps` request per detik, dengan token bucket
+    /// yang boleh dipakai sekaligus sampai `rps` request.
+    pub fn with_rate_limit(mut self, rps: NonZeroU32) -> Self {
+        self.limiter = Some(Arc::new(RateLimiter::direct(Quota::per_second(rps))));
+        self
+    }
+
+    /// Tunggu sampai request berikutnya boleh dikirim.
+    pub async fn throttle(&self) {
+        if let Some(limiter) = &self.limiter {
+            limiter.until_ready().await;
+        }
+    }
+
+    /// Client HTTP-nya, untuk request di luar API todo; panggil
+    /// [`throttle`](Self::throttle) sebelum mengirim.
+    pub fn http(&self) -> &Client {
+        &self.http
+    }
+
+    pub fn cache(&self) -> Option<&Cache> {
+        self.cache.as_deref()
+    }
+
+    /// Semua todo, atau hanya milik `user`.
+    pub async fn list(&self, user: Option<i32>) -> Result<Vec<Todo>, ApiError> {
+        self.json(self.list_request(user)).await
+    }
+
+    /// Halaman `page` (mulai dari 1) berisi paling banyak `limit` todo.
+    pub async fn list_page(
+        &self,
+        user: Option<i32>,
+        page: u32,
+        limit: u32,
+    ) -> Result<Vec<Todo>, ApiError> {
+        let request = self
+            .list_request(user)
+            .query(&[("_page", page), ("_limit", limit)]);
+        self.json(request).await
+    }
+
+    /// Semua halaman berurutan, diminta satu per satu saat dibutuhkan dan
+    /// berhenti setelah halaman yang tidak penuh.
+    pub fn pages(
+        &self,
+        user: Option<i32>,
+        limit: u32,
+    ) -> impl Stream<Item = Result<Vec<Todo>, ApiError>> + '_ {
+        stream::try_unfold(Some(1), move |page| async move {
+            let Some(page) = page else {
+                return Ok(None);
+            };
+            let todos = self.list_page(user, page, limit).await?;
+            if todos.is_empty() {
+                return Ok(None);
+            }
+            let next = (todos.len() >= limit as usize).then_some(page + 1);
+            Ok(Some((todos, next)))
+        })
+    }
+
+    fn list_request(&self, user: Option<i32>) -> RequestBuilder {
+        let request = self.http.get(format!("{}/todos", self.base_url));
+        match user {
+            Some(user) => request.query(&[("userId", user)]),
+            None => request,
+        }
+    }
+
+    pub async fn get(&self, id: i32) -> Result<Todo, ApiError> {
+        self.json(self.http.get(self.todo_url(id))).await
+    }
+
+    /// Todo yang dibuat, dengan id dari server.
+    pub async fn create(&self, todo: &NewTodo) -> Result<Todo, ApiError> {
+        self.json(
+            self.http
+                .post(format!("{}/todos", self.base_url))
+                .json(todo),
+        )
+        .await
+    }
+
+    /// Todo setelah `changes` diterapkan.
+    pub async fn update(&self, id: i32, changes: &TodoChanges) -> Result<Todo, ApiError> {
+        self.json(self.http.patch(self.todo_url(id)).json(changes))
+            .await
+    }
+
+    pub async fn delete(&self, id: i32) -> Result<(), ApiError> {
+        self.body(self.http.delete(self.todo_url(id))).await?;
+        Ok(())
+    }
+
+    pub async fn user(&self, id: i32) -> Result<User, ApiError> {
+        self.json(self.http.get(format!("{}/users/{id}", self.base_url)))
+            .await
+    }
+
+    /// Kirim `request` ke endpoint GraphQL di `url`, `data` dibaca sebagai `T`.
+    pub async fn graphql<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        request: &graphql::Request,
+    ) -> Result<graphql::Response<T>, ApiError> {
+        match self.json(self.http.post(url).json(request)).await {
+            // Query yang salah sering dijawab 400 dengan daftar error di body
+            Err(ApiError::Status { status, body }) => {
+                match serde_json::from_str::<graphql::Response<serde::de::IgnoredAny>>(&body) {
+                    Ok(response) if !response.errors.is_empty() => {
+                        Err(ApiError::Graphql(response.errors))
+                    }
+                    _ => Err(ApiError::Status { status, body }),
+                }
+            }
+            result => result,
+        }
+    }
+
+    fn todo_url(&self, id: i32) -> String {
+        format!("{}/todos/{id}", self.base_url)
+    }
+
+    // Kirim request dan ambil body-nya, status error jadi ApiError::Status.
+    // GET memakai cache: validator dikirim, dan 304 dijawab dari disk.
+    async fn body(&self, request: RequestBuilder) -> Result<String, ApiError> {
+        let request = match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        };
+        let request = match self.timeout {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
+        };
+        let mut request = request.build()?;
+        let cache = self
+            .cache
+            .as_deref()
+            .filter(|_| request.method() == Method::GET);
+        let url = request.url().to_string();
+        let cached = cache.and_then(|cache| cache.lookup(&url));
+        if let Some(entry) = &cached {
+            let headers = request.headers_mut();
+            let validators = [
+                (IF_NONE_MATCH, &entry.etag),
+                (IF_MODIFIED_SINCE, &entry.last_modified),
+            ];
+            for (name, value) in validators {
+                if let Some(value) = value.as_deref().and_then(|v| HeaderValue::from_str(v).ok()) {
+                    headers.insert(name, value);
+                }
+            }
+        }
+
+        let response = self.execute(request).await?;
+        let status = response.status();
+        if let (Some(cache), Some(entry), StatusCode::NOT_MODIFIED) = (cache, cached, status) {
+            cache.hit();
+            return Ok(entry.body);
+        }
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value: &HeaderValue| value.to_str().ok())
+                .map(String::from)
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let body = response.text().await?;
+        if !status.is_success() {
+            return Err(ApiError::Status { status, body });
+        }
+        if let Some(cache) = cache {
+            cache.miss();
+            if etag.is_some() || last_modified.is_some() {
+                cache.store(&Entry {
+                    url,
+                    etag,
+                    last_modified,
+                    body: body.clone(),
+                });
+            }
+        }
+        Ok(body)
+    }
+
+    async fn execute(&self, request: Request) -> Result<Response, reqwest::Error> {
+        let idempotent = matches!(*request.method(), Method::GET | Method::DELETE);
+        let retries = if idempotent { self.retries } else { 0 };
+        let mut delay = self.retry_delay;
+        for _ in 0..retries {
+            let Some(attempt) = request.try_clone() else {
+                break;
+            };
+            self.throttle().await;
+            match self.http.execute(attempt).await {
+                Ok(response) if !response.status().is_server_error() => return Ok(response),
+                Err(e) if !e.is_timeout() && !e.is_connect() => return Err(e),
+                _ => {}
+            }
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+        self.throttle().await;
+        self.http.execute(request).await
+    }
+
+    async fn json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, ApiError> {
+        let body = self.body(request).await?;
+        Ok(serde_json::from_str(&body)?)
+    }
+}
+
+#[cfg(test)]
+mod tests;
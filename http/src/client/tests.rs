@@ -0,0 +1,474 @@
+use super::*;
+use futures::TryStreamExt;
+use serde_json::{json, Value};
+use wiremock::{
+    matchers::{body_json, header, method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+const TIMEOUT: Duration = Duration::from_millis(200);
+
+fn client(server: &MockServer) -> TodoClient {
+    TodoClient::new(Client::new(), server.uri())
+        .with_timeout(TIMEOUT)
+        .with_retries(2, Duration::from_millis(10))
+}
+
+fn todo(id: i32) -> Value {
+    json!({ "id": id, "userId": 1, "title": format!("todo {id}"), "completed": false })
+}
+
+/// Setiap method TodoClient, hasilnya dibuang supaya bisa diuji bersama.
+#[derive(Debug, Clone, Copy)]
+enum Call {
+    List,
+    ListPage,
+    Pages,
+    Get,
+    Create,
+    Update,
+    Delete,
+    User,
+}
+
+impl Call {
+    const ALL: [Call; 8] = [
+        Call::List,
+        Call::ListPage,
+        Call::Pages,
+        Call::Get,
+        Call::Create,
+        Call::Update,
+        Call::Delete,
+        Call::User,
+    ];
+
+    fn method(self) -> &'static str {
+        match self {
+            Call::Create => "POST",
+            Call::Update => "PATCH",
+            Call::Delete => "DELETE",
+            _ => "GET",
+        }
+    }
+
+    fn path(self) -> &'static str {
+        match self {
+            Call::List | Call::ListPage | Call::Pages | Call::Create => "/todos",
+            Call::Get | Call::Update | Call::Delete => "/todos/1",
+            Call::User => "/users/1",
+        }
+    }
+
+    fn retried(self) -> bool {
+        !matches!(self, Call::Create | Call::Update)
+    }
+
+    /// Body yang benar untuk response sukses.
+    fn body(self) -> Value {
+        match self {
+            Call::List | Call::ListPage | Call::Pages => json!([todo(1)]),
+            Call::Get | Call::Create | Call::Update => todo(1),
+            Call::Delete => json!({}),
+            Call::User => {
+                json!({ "id": 1, "name": "Ann", "username": "ann", "email": "ann@example.com" })
+            }
+        }
+    }
+
+    fn mock(self) -> wiremock::MockBuilder {
+        Mock::given(method(self.method())).and(path(self.path()))
+    }
+
+    async fn send(self, client: &TodoClient) -> Result<(), ApiError> {
+        match self {
+            Call::List => client.list(None).await.map(drop),
+            Call::ListPage => client.list_page(None, 1, 2).await.map(drop),
+            Call::Pages => client
+                .pages(None, 2)
+                .try_collect::<Vec<_>>()
+                .await
+                .map(drop),
+            Call::Get => client.get(1).await.map(drop),
+            Call::Create => {
+                let todo = NewTodo {
+                    user_id: 1,
+                    title: String::from("todo 1"),
+                    completed: false,
+                };
+                client.create(&todo).await.map(drop)
+            }
+            Call::Update => client.update(1, &TodoChanges::default()).await.map(drop),
+            Call::Delete => client.delete(1).await,
+            Call::User => client.user(1).await.map(drop),
+        }
+    }
+}
+
+fn assert_status(call: Call, result: Result<(), ApiError>, expected: StatusCode) {
+    match result {
+        Err(ApiError::Status { status, .. }) => assert_eq!(status, expected, "{call:?}"),
+        other => panic!("{call:?}: expected {expected}, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn list_returns_todos_of_user() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/todos"))
+        .and(query_param("userId", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([todo(1), todo(2)])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let todos = client(&server).list(Some(3)).await.unwrap();
+
+    assert_eq!(todos.len(), 2);
+    assert_eq!(todos[1].title, "todo 2");
+}
+
+#[tokio::test]
+async fn list_page_sends_page_and_limit() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/todos"))
+        .and(query_param("_page", "2"))
+        .and(query_param("_limit", "5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([todo(6)])))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let todos = client(&server).list_page(None, 2, 5).await.unwrap();
+
+    assert_eq!(todos[0].id, 6);
+}
+
+#[tokio::test]
+async fn pages_stop_after_short_page() {
+    let server = MockServer::start().await;
+    for (page, body) in [("1", json!([todo(1), todo(2)])), ("2", json!([todo(3)]))] {
+        Mock::given(method("GET"))
+            .and(path("/todos"))
+            .and(query_param("_page", page))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let client = client(&server);
+    let pages: Vec<Vec<Todo>> = client.pages(None, 2).try_collect().await.unwrap();
+
+    let ids: Vec<Vec<i32>> = pages
+        .iter()
+        .map(|page| page.iter().map(|todo| todo.id).collect())
+        .collect();
+    assert_eq!(ids, [vec![1, 2], vec![3]]);
+}
+
+#[tokio::test]
+async fn get_returns_todo() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/todos/7"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(todo(7)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let todo = client(&server).get(7).await.unwrap();
+
+    assert_eq!(
+        todo,
+        Todo {
+            id: 7,
+            user_id: 1,
+            title: String::from("todo 7"),
+            completed: false,
+        }
+    );
+}
+
+#[tokio::test]
+async fn create_posts_new_todo() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/todos"))
+        .and(body_json(
+            json!({ "userId": 2, "title": "write tests", "completed": true }),
+        ))
+        .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+            "id": 201, "userId": 2, "title": "write tests", "completed": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let new_todo = NewTodo {
+        user_id: 2,
+        title: String::from("write tests"),
+        completed: true,
+    };
+    let todo = client(&server).create(&new_todo).await.unwrap();
+
+    assert_eq!(todo.id, 201);
+    assert!(todo.completed);
+}
+
+#[tokio::test]
+async fn update_sends_only_changed_fields() {
+    let server = MockServer::start().await;
+    Mock::given(method("PATCH"))
+        .and(path("/todos/1"))
+        .and(body_json(json!({ "completed": true })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 1, "userId": 1, "title": "todo 1", "completed": true
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let changes = TodoChanges {
+        title: None,
+        completed: Some(true),
+    };
+    let todo = client(&server).update(1, &changes).await.unwrap();
+
+    assert!(todo.completed);
+}
+
+#[tokio::test]
+async fn delete_ignores_body() {
+    let server = MockServer::start().await;
+    Mock::given(method("DELETE"))
+        .and(path("/todos/1"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("not json"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server).delete(1).await.unwrap();
+}
+
+#[tokio::test]
+async fn user_returns_user() {
+    let server = MockServer::start().await;
+    Call::User
+        .mock()
+        .respond_with(ResponseTemplate::new(200).set_body_json(Call::User.body()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let user = client(&server).user(1).await.unwrap();
+
+    assert_eq!(user.username, "ann");
+}
+
+#[tokio::test]
+async fn token_is_sent_as_bearer() {
+    let server = MockServer::start().await;
+    Mock::given(header("Authorization", "Bearer secret"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(todo(1)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    client(&server).with_token("secret").get(1).await.unwrap();
+}
+
+#[tokio::test]
+async fn rate_limit_spaces_out_requests() {
+    let server = MockServer::start().await;
+    Call::Get
+        .mock()
+        .respond_with(ResponseTemplate::new(200).set_body_json(todo(1)))
+        .expect(4)
+        .mount(&server)
+        .await;
+
+    // Dua yang pertama langsung dari bucket, berikutnya satu per setengah detik
+    let client = client(&server).with_rate_limit(NonZeroU32::new(2).unwrap());
+    let start = std::time::Instant::now();
+    let calls = futures::future::join_all((0..4).map(|_| client.get(1))).await;
+
+    assert!(calls.iter().all(Result::is_ok));
+    assert!(start.elapsed() >= Duration::from_millis(900));
+}
+
+#[tokio::test]
+async fn not_found_is_status_error() {
+    for call in Call::ALL {
+        let server = MockServer::start().await;
+        call.mock()
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        assert_status(
+            call,
+            call.send(&client(&server)).await,
+            StatusCode::NOT_FOUND,
+        );
+    }
+}
+
+#[tokio::test]
+async fn server_error_is_retried_until_success() {
+    for call in Call::ALL.into_iter().filter(|call| call.retried()) {
+        let server = MockServer::start().await;
+        call.mock()
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(2)
+            .expect(2)
+            .mount(&server)
+            .await;
+        call.mock()
+            .respond_with(ResponseTemplate::new(200).set_body_json(call.body()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        call.send(&client(&server)).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn server_error_is_returned_after_last_retry() {
+    for call in Call::ALL {
+        let server = MockServer::start().await;
+        let attempts = if call.retried() { 3 } else { 1 };
+        call.mock()
+            .respond_with(ResponseTemplate::new(500))
+            .expect(attempts)
+            .mount(&server)
+            .await;
+
+        let result = call.send(&client(&server)).await;
+
+        assert_status(call, result, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
+
+#[tokio::test]
+async fn malformed_json_is_decode_error() {
+    for call in Call::ALL
+        .into_iter()
+        .filter(|call| !matches!(call, Call::Delete))
+    {
+        let server = MockServer::start().await;
+        call.mock()
+            .respond_with(ResponseTemplate::new(200).set_body_string("{\"id\": 1,"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        match call.send(&client(&server)).await {
+            Err(ApiError::Decode(_)) => {}
+            other => panic!("{call:?}: expected decode error, got {other:?}"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn slow_response_times_out() {
+    for call in Call::ALL {
+        let server = MockServer::start().await;
+        let attempts = if call.retried() { 3 } else { 1 };
+        call.mock()
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(call.body())
+                    .set_delay(TIMEOUT * 5),
+            )
+            .expect(attempts)
+            .mount(&server)
+            .await;
+
+        match call.send(&client(&server)).await {
+            Err(ApiError::Network(e)) => assert!(e.is_timeout(), "{call:?}: {e}"),
+            other => panic!("{call:?}: expected timeout, got {other:?}"),
+        }
+    }
+}
+
+#[tokio::test]
+async fn slow_response_is_retried() {
+    let server = MockServer::start().await;
+    Call::Get
+        .mock()
+        .respond_with(ResponseTemplate::new(200).set_delay(TIMEOUT * 5))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Call::Get
+        .mock()
+        .respond_with(ResponseTemplate::new(200).set_body_json(todo(1)))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    assert_eq!(client(&server).get(1).await.unwrap().id, 1);
+}
+
+#[tokio::test]
+async fn graphql_returns_data_and_errors() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/graphql"))
+        .and(body_json(
+            json!({ "query": "{ todo { id } }", "variables": { "id": 1 } }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "todo": { "id": "1" } },
+            "errors": [{ "message": "slow field", "path": ["todo", "user"] }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = graphql::Request {
+        query: String::from("{ todo { id } }"),
+        variables: Some(json!({ "id": 1 })),
+        operation_name: None,
+    };
+    let url = format!("{}/graphql", server.uri());
+    let response = client(&server)
+        .graphql::<Value>(&url, &request)
+        .await
+        .unwrap();
+
+    assert_eq!(response.data, Some(json!({ "todo": { "id": "1" } })));
+    assert_eq!(response.errors[0].to_string(), "slow field in todo.user");
+}
+
+#[tokio::test]
+async fn graphql_errors_in_bad_request_are_typed() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+            "errors": [{ "message": "Syntax Error", "locations": [{ "line": 1, "column": 2 }] }]
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let request = graphql::Request {
+        query: String::from("{"),
+        variables: None,
+        operation_name: None,
+    };
+    let result = client(&server)
+        .graphql::<Value>(&server.uri(), &request)
+        .await;
+
+    match result {
+        Err(ApiError::Graphql(errors)) => assert_eq!(errors[0].to_string(), "Syntax Error at 1:2"),
+        other => panic!("expected GraphQL errors, got {other:?}"),
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{client::BASE_URL, graphql::GRAPHQL_URL};
+use serde::Deserialize;
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Variabel lingkungan untuk token, dipakai kalau --token tidak diberikan.
+pub const TOKEN_VAR: &str = "TODO_API_TOKEN";
+/// Variabel lingkungan untuk alamat API, dipakai kalau --base-url tidak diberikan.
+pub const BASE_URL_VAR: &str = "TODO_API_URL";
+/// Variabel lingkungan untuk endpoint GraphQL, dipakai kalau --endpoint tidak diberikan.
+pub const GRAPHQL_URL_VAR: &str = "TODO_GRAPHQL_URL";
+
+/// Isi file config, semuanya opsional:
+///
+/// ```toml
+/// base_url = "https://api.example.com"
+/// graphql_url = "https://api.example.com/graphql"
+/// token = "..."
+/// rps = 5
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub base_url: Option<String>,
+    pub graphql_url: Option<String>,
+    pub token: Option<String>,
+    /// Request per detik kalau --rps tidak diberikan, 0 untuk tanpa batas.
+    pub rps: Option<u32>,
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/http/config.toml`, atau `~/.config/http/config.toml`.
+    pub fn default_path() -> PathBuf {
+        if let Some(dir) = env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+            return Path::new(&dir).join("http").join("config.toml");
+        }
+        let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+        let home = home.map(PathBuf::from).unwrap_or_default();
+        home.join(".config").join("http").join("config.toml")
+    }
+
+    /// File di `path`; kalau tidak ada, semuanya kosong.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path)?;
+        toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Token dari `flag`, lalu dari [`TOKEN_VAR`], lalu dari file config.
+    pub fn token(&self, flag: Option<String>) -> Option<String> {
+        layered(flag, TOKEN_VAR, &self.token)
+    }
+
+    /// Alamat API dengan urutan yang sama, JSONPlaceholder kalau tidak ada.
+    pub fn base_url(&self, flag: Option<String>) -> String {
+        layered(flag, BASE_URL_VAR, &self.base_url).unwrap_or_else(|| String::from(BASE_URL))
+    }
+
+    /// Endpoint GraphQL dengan urutan yang sama, GraphQLZero kalau tidak ada.
+    pub fn graphql_url(&self, flag: Option<String>) -> String {
+        layered(flag, GRAPHQL_URL_VAR, &self.graphql_url)
+            .unwrap_or_else(|| String::from(GRAPHQL_URL))
+    }
+}
+
+// Nilai kosong dianggap tidak diisi
+fn layered(flag: Option<String>, var: &str, file: &Option<String>) -> Option<String> {
+    flag.or_else(|| env::var(var).ok())
+        .or_else(|| file.clone())
+        .filter(|value| !value.is_empty())
+}
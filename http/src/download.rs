@@ -0,0 +1,134 @@
+use crate::client::{ApiError, TodoClient};
+use indicatif::{ProgressBar, ProgressStyle};
+use reqwest::{
+    header::{CONTENT_RANGE, RANGE},
+    StatusCode,
+};
+use sha2::{Digest, Sha256};
+use std::{io, path::Path};
+use thiserror::Error;
+use tokio::{
+    fs::{self, File, OpenOptions},
+    io::{AsyncReadExt, AsyncWriteExt},
+};
+
+#[derive(Debug, Error)]
+pub enum DownloadError {
+    #[error(transparent)]
+    Request(#[from] ApiError),
+    #[error("{path}: {source}")]
+    Io { path: String, source: io::Error },
+    /// File sudah lengkap tapi isinya tidak sesuai; file dibiarkan di disk.
+    #[error("checksum mismatch: expected sha256 {expected}, got {actual}")]
+    Checksum { expected: String, actual: String },
+}
+
+/// Simpan body `url` ke `path`. Kalau `path` sudah berisi sebagian, sisanya
+/// diminta dengan header Range; server yang tidak mendukungnya mengirim ulang
+/// semuanya dari awal.
+pub async fn download(
+    client: &TodoClient,
+    url: &str,
+    path: &Path,
+    sha256: Option<&str>,
+) -> Result<(), DownloadError> {
+    let io_error = |source| DownloadError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+    let existing = match fs::metadata(path).await {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+        Err(e) => return Err(io_error(e)),
+    };
+
+    let mut request = client.http().get(url);
+    if existing > 0 {
+        request = request.header(RANGE, format!("bytes={existing}-"));
+    }
+    client.throttle().await;
+    let mut response = request.send().await.map_err(ApiError::from)?;
+    let status = response.status();
+    let (offset, total) = match status {
+        // Bagian yang diminta sudah ada semua
+        StatusCode::RANGE_NOT_SATISFIABLE if existing > 0 => {
+            eprintln!("{} is already complete", path.display());
+            return verify(path, sha256).await;
+        }
+        StatusCode::PARTIAL_CONTENT => {
+            let total = response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|range| range.rsplit_once('/'))
+                .and_then(|(_, total)| total.parse().ok());
+            (existing, total)
+        }
+        status if status.is_success() => (0, response.content_length()),
+        status => {
+            let body = response.text().await.map_err(ApiError::from)?;
+            return Err(ApiError::Status { status, body }.into());
+        }
+    };
+
+    let mut file = if offset > 0 {
+        eprintln!("resuming {} at {offset} bytes", path.display());
+        OpenOptions::new().append(true).open(path).await
+    } else {
+        File::create(path).await
+    }
+    .map_err(io_error)?;
+
+    let progress = match total {
+        Some(total) => ProgressBar::new(total).with_style(
+            ProgressStyle::with_template(
+                "{bar:40} {bytes}/{total_bytes} {bytes_per_sec} eta {eta}",
+            )
+            .expect("valid progress template"),
+        ),
+        None => ProgressBar::new_spinner().with_style(
+            ProgressStyle::with_template("{spinner} {bytes} {bytes_per_sec}")
+                .expect("valid progress template"),
+        ),
+    };
+    progress.set_position(offset);
+    while let Some(chunk) = response.chunk().await.map_err(ApiError::from)? {
+        file.write_all(&chunk).await.map_err(io_error)?;
+        progress.inc(chunk.len() as u64);
+    }
+    file.flush().await.map_err(io_error)?;
+    progress.finish();
+
+    eprintln!("saved {} ({} bytes)", path.display(), progress.position());
+    verify(path, sha256).await
+}
+
+// Bandingkan SHA-256 seluruh isi file, termasuk bagian dari unduhan sebelumnya
+async fn verify(path: &Path, expected: Option<&str>) -> Result<(), DownloadError> {
+    let Some(expected) = expected else {
+        return Ok(());
+    };
+    let io_error = |source| DownloadError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+    let mut file = File::open(path).await.map_err(io_error)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await.map_err(io_error)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    let actual = format!("{:x}", hasher.finalize());
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        return Err(DownloadError::Checksum {
+            expected: expected.trim().to_lowercase(),
+            actual,
+        });
+    }
+    eprintln!("sha256 ok");
+    Ok(())
+}
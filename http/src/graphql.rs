@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{fmt, fs};
+
+/// GraphQLZero, data JSONPlaceholder dalam bentuk GraphQL.
+pub const GRAPHQL_URL: &str = "https://graphqlzero.almansi.me/api";
+
+/// Body request GraphQL.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    pub query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_name: Option<String>,
+}
+
+/// Response GraphQL; `data` bisa ada sebagian walaupun `errors` tidak kosong.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Response<T> {
+    pub data: Option<T>,
+    #[serde(default)]
+    pub errors: Vec<Error>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Error {
+    pub message: String,
+    #[serde(default)]
+    pub locations: Vec<Location>,
+    /// Field tempat error terjadi, berisi nama field dan indeks list.
+    #[serde(default)]
+    pub path: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Location {
+    pub line: u32,
+    pub column: u32,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        if let Some(location) = self.locations.first() {
+            write!(f, " at {}:{}", location.line, location.column)?;
+        }
+        if !self.path.is_empty() {
+            let path: Vec<String> = self
+                .path
+                .iter()
+                .map(|segment| match segment {
+                    Value::String(field) => field.clone(),
+                    other => other.to_string(),
+                })
+                .collect();
+            write!(f, " in {}", path.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+/// Isi file query untuk --file.
+pub fn read_document(path: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("could not read {path}: {e}"))
+}
+
+/// Objek JSON untuk --variables.
+pub fn parse_variables(arg: &str) -> Result<Value, String> {
+    match serde_json::from_str(arg) {
+        Ok(Value::Object(variables)) => Ok(Value::Object(variables)),
+        Ok(_) => Err(String::from("variables must be a JSON object")),
+        Err(e) => Err(format!("invalid JSON: {e}")),
+    }
+}
@@ -1,44 +1,464 @@
-use reqwest;
-use serde::{Deserialize, Serialize};
-use std::error::Error;
+mod batch;
+mod cache;
+mod client;
+mod config;
+mod download;
+mod graphql;
+mod ndjson;
+mod output;
+mod profile;
+mod upload;
 
-// Definisikan struct untuk response JSON
-#[derive(Debug, Deserialize, Serialize)]
-struct Todo {
-    id: i32,
-    title: String,
-    completed: bool,
+use cache::Cache;
+use clap::{builder::TypedValueParser as _, Parser, Subcommand, ValueEnum};
+use client::{ApiError, NewTodo, Todo, TodoChanges, TodoClient, User};
+use config::Config;
+use download::DownloadError;
+use futures::{StreamExt, TryStreamExt};
+use output::{Format, Row};
+use profile::Profile;
+use reqwest::StatusCode;
+use std::{
+    num::NonZeroU32, ops::RangeInclusive, path::PathBuf, pin::pin, process::ExitCode,
+    time::Duration,
+};
+use upload::UploadError;
+
+// Kode keluar, selain 0 untuk sukses dan 2 dari clap untuk argumen yang salah
+/// Request gagal terkirim atau response-nya tidak bisa dibaca.
+const EXIT_REQUEST_FAILED: u8 = 1;
+/// Todo yang diminta tidak ada.
+const EXIT_NOT_FOUND: u8 = 3;
+/// Server menolak request dengan status error lain, atau query GraphQL-nya.
+const EXIT_REJECTED: u8 = 4;
+/// File config tidak bisa dibaca.
+const EXIT_CONFIG: u8 = 5;
+/// File yang diunduh tidak sesuai dengan --sha256.
+const EXIT_CHECKSUM: u8 = 6;
+
+/// Jeda sebelum mengulang request yang gagal, berlipat dua setiap kali.
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// Request per detik kalau --rps dan file config tidak mengaturnya.
+const RPS: u32 = 10;
+
+/// Todo per halaman kalau --page atau --all dipakai tanpa --limit.
+const PAGE_SIZE: u32 = 20;
+
+/// Kelola todo di JSONPlaceholder.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Alamat API; kalau tidak ada, dari TODO_API_URL lalu dari file config
+    #[arg(long, global = true, value_name = "URL")]
+    base_url: Option<String>,
+
+    /// Bearer token; kalau tidak ada, dari TODO_API_TOKEN lalu dari file config
+    #[arg(long, global = true)]
+    token: Option<String>,
+
+    /// File config; defaultnya ~/.config/http/config.toml
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Batas waktu, ukuran pool koneksi, dan jumlah redirect
+    #[arg(long, global = true, value_enum, default_value = "default")]
+    profile: Profile,
+
+    /// Ulangi GET dan DELETE yang gagal karena koneksi atau status 5xx
+    #[arg(long, global = true, default_value_t = 2)]
+    retries: u32,
+
+    /// Request per detik, 0 untuk tanpa batas; defaultnya dari file config, atau 10
+    #[arg(long, global = true)]
+    rps: Option<u32>,
+
+    /// Jangan pakai dan jangan isi cache response di disk
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// Format daftar dari list dan batch
+    #[arg(long, global = true, value_enum, default_value = "table")]
+    output: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Tampilkan semua todo
+    List {
+        /// Hanya todo milik user ini
+        #[arg(long)]
+        user: Option<i32>,
+        /// Hanya halaman ini, mulai dari 1
+        #[arg(long, conflicts_with = "all", value_parser = clap::value_parser!(u32).range(1..))]
+        page: Option<u32>,
+        /// Todo per halaman
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        limit: Option<u32>,
+        /// Ambil semua halaman satu per satu dan tampilkan begitu datang
+        #[arg(long)]
+        all: bool,
+    },
+    /// Tampilkan satu todo
+    Get { id: i32 },
+    /// Buat todo baru
+    Create {
+        title: String,
+        /// User pemilik todo
+        #[arg(long, default_value_t = 1)]
+        user: i32,
+        /// Tandai langsung sebagai selesai
+        #[arg(long)]
+        completed: bool,
+    },
+    /// Ubah judul atau status todo
+    Update {
+        id: i32,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        completed: Option<bool>,
+    },
+    /// Hapus todo
+    Delete { id: i32 },
+    /// Ambil banyak todo atau user sekaligus dan ukur lamanya
+    Batch {
+        resource: Resource,
+        /// Id atau rentang id, misalnya 1-50 7 9
+        #[arg(required = true, value_parser = batch::parse_ids)]
+        ids: Vec<RangeInclusive<i32>>,
+        /// Request yang berjalan bersamaan
+        #[arg(long, default_value_t = batch::CONCURRENCY, value_parser = clap::value_parser!(u16).range(1..).map(usize::from))]
+        concurrency: usize,
+    },
+    /// Unduh URL ke file, dan lanjutkan kalau file-nya baru sebagian
+    Download {
+        url: String,
+        path: PathBuf,
+        /// Checksum SHA-256 yang diharapkan, dalam hex
+        #[arg(long, value_name = "HEX")]
+        sha256: Option<String>,
+    },
+    /// Kirim file sebagai multipart/form-data dan tampilkan response-nya
+    Upload {
+        url: String,
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Field form tambahan, boleh berulang
+        #[arg(long = "field", value_name = "KEY=VALUE", value_parser = upload::parse_field)]
+        fields: Vec<(String, String)>,
+        /// Nama field untuk file
+        #[arg(long, default_value = "file")]
+        name: String,
+    },
+    /// Baca todo atau user dari URL NDJSON dan tampilkan begitu datang
+    Stream { resource: Resource, url: String },
+    /// Kirim query GraphQL dan tampilkan data-nya sebagai JSON
+    Graphql {
+        #[arg(required_unless_present = "file")]
+        query: Option<String>,
+        /// Baca query dari file
+        #[arg(long, value_name = "PATH", conflicts_with = "query", value_parser = graphql::read_document)]
+        file: Option<String>,
+        /// Objek JSON untuk variabel query
+        #[arg(long, value_name = "JSON", value_parser = graphql::parse_variables)]
+        variables: Option<serde_json::Value>,
+        /// Operasi yang dijalankan kalau query berisi lebih dari satu
+        #[arg(long, value_name = "NAME")]
+        operation: Option<String>,
+        /// Endpoint; kalau tidak ada, dari TODO_GRAPHQL_URL lalu dari file config
+        #[arg(long, value_name = "URL")]
+        endpoint: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Resource {
+    Todos,
+    Users,
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn Error>> {
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
     // Buat client
-    let client = reqwest::Client::new();
-
-    // GET request
-    let todos: Vec<Todo> = client
-        .get("https://jsonplaceholder.typicode.com/todos")
-        .send()
-        .await?
-        .json()
-        .await?;
-
-    println!("Todos: {:?}", todos);
-
-    // POST request dengan data
-    let new_todo = Todo {
-        id: 1,
-        title: String::from("Belajar Rust"),
-        completed: false,
+    let path = cli.config.unwrap_or_else(Config::default_path);
+    let config = match Config::load(&path) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("error: could not read {}: {e}", path.display());
+            return ExitCode::from(EXIT_CONFIG);
+        }
+    };
+    let http = match cli.profile.client() {
+        Ok(http) => http,
+        Err(e) => {
+            eprintln!("error: could not create HTTP client: {e}");
+            return ExitCode::from(EXIT_REQUEST_FAILED);
+        }
     };
+    let mut client = TodoClient::new(http, config.base_url(cli.base_url))
+        .with_timeout(cli.profile.timeout())
+        .with_retries(cli.retries, RETRY_DELAY);
+    if let Some(rps) = NonZeroU32::new(cli.rps.or(config.rps).unwrap_or(RPS)) {
+        client = client.with_rate_limit(rps);
+    }
+    if let Some(token) = config.token(cli.token) {
+        client = client.with_token(token);
+    }
+    if !cli.no_cache {
+        client = client.with_cache(Cache::new(Cache::default_dir()));
+    }
+
+    // Unduhan dan unggahan tidak memakai API todo maupun cache-nya
+    match cli.command {
+        Command::Download { url, path, sha256 } => {
+            let result = download::download(&client, &url, &path, sha256.as_deref()).await;
+            return match result {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(DownloadError::Request(e)) => fail(&e),
+                Err(e @ DownloadError::Io { .. }) => {
+                    eprintln!("error: {e}");
+                    ExitCode::from(EXIT_REQUEST_FAILED)
+                }
+                Err(e @ DownloadError::Checksum { .. }) => {
+                    eprintln!("error: {e}");
+                    ExitCode::from(EXIT_CHECKSUM)
+                }
+            };
+        }
+        Command::Upload {
+            url,
+            files,
+            fields,
+            name,
+        } => {
+            return match upload::upload(&client, &url, &name, &files, &fields).await {
+                Ok(body) => {
+                    println!("{}", body.trim_end());
+                    ExitCode::SUCCESS
+                }
+                Err(UploadError::Request(e)) => fail(&e),
+                Err(e @ UploadError::Io { .. }) => {
+                    eprintln!("error: {e}");
+                    ExitCode::from(EXIT_REQUEST_FAILED)
+                }
+            };
+        }
+        _ => {}
+    }
+
+    let result = run(&client, &config, cli.command, cli.output).await;
+    if let Some(cache) = client.cache() {
+        let stats = cache.stats();
+        if stats.hits + stats.misses > 0 {
+            eprintln!(
+                "cache: {} hits, {} misses, {} stored in {}",
+                stats.hits,
+                stats.misses,
+                stats.stored,
+                cache.dir().display()
+            );
+        }
+    }
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => fail(&e),
+    }
+}
 
-    let response = client
-        .post("https://jsonplaceholder.typicode.com/todos")
-        .json(&new_todo)
-        .send()
-        .await?;
+fn fail(error: &ApiError) -> ExitCode {
+    eprintln!("error: {error}");
+    // Tampilkan body dari response yang gagal, kalau ada isinya
+    if let ApiError::Status { body, .. } = error {
+        if !body.trim().is_empty() && body.trim() != "{}" {
+            eprintln!("{}", body.trim());
+        }
+    }
+    ExitCode::from(exit_code(error))
+}
 
-    println!("Status: {}", response.status());
+async fn run(
+    client: &TodoClient,
+    config: &Config,
+    command: Command,
+    format: Format,
+) -> Result<(), ApiError> {
+    match command {
+        Command::List {
+            user,
+            page,
+            limit,
+            all,
+        } => {
+            if all {
+                let mut pages = pin!(client.pages(user, limit.unwrap_or(PAGE_SIZE)));
+                let mut streamer = output::Streamer::new(format);
+                let mut count = 0;
+                while let Some(todos) = pages.try_next().await? {
+                    count += todos.len();
+                    // Pembacanya sudah berhenti, halaman berikutnya tidak perlu diminta
+                    if streamer.write(&todos).is_err() {
+                        return Ok(());
+                    }
+                }
+                if streamer.finish().is_ok() {
+                    eprintln!("{count} todos");
+                }
+                return Ok(());
+            }
+            let todos = match (page, limit) {
+                (None, None) => client.list(user).await?,
+                (page, limit) => {
+                    let page = page.unwrap_or(1);
+                    let limit = limit.unwrap_or(PAGE_SIZE);
+                    client.list_page(user, page, limit).await?
+                }
+            };
+            print!("{}", output::render(&todos, format));
+            eprintln!("{} todos", todos.len());
+        }
+        Command::Get { id } => println!("{}", client.get(id).await?),
+        Command::Create {
+            title,
+            user,
+            completed,
+        } => {
+            let new_todo = NewTodo {
+                user_id: user,
+                title,
+                completed,
+            };
+            println!("created {}", client.create(&new_todo).await?);
+        }
+        Command::Update {
+            id,
+            title,
+            completed,
+        } => {
+            let changes = TodoChanges { title, completed };
+            println!("updated {}", client.update(id, &changes).await?);
+        }
+        Command::Delete { id } => {
+            client.delete(id).await?;
+            println!("deleted todo {id}");
+        }
+        Command::Batch {
+            resource,
+            ids,
+            concurrency,
+        } => {
+            let ids: Vec<i32> = ids.into_iter().flatten().collect();
+            match resource {
+                Resource::Todos => {
+                    let report = batch::fetch_all(ids, concurrency, |id| client.get(id)).await;
+                    print_report(&report, concurrency, format)
+                }
+                Resource::Users => {
+                    let report = batch::fetch_all(ids, concurrency, |id| client.user(id)).await;
+                    print_report(&report, concurrency, format)
+                }
+            }
+        }
+        Command::Stream { resource, url } => match resource {
+            Resource::Todos => print_stream::<Todo>(client, &url, format).await?,
+            Resource::Users => print_stream::<User>(client, &url, format).await?,
+        },
+        Command::Graphql {
+            query,
+            file,
+            variables,
+            operation,
+            endpoint,
+        } => {
+            let request = graphql::Request {
+                query: query.or(file).unwrap_or_default(),
+                variables,
+                operation_name: operation,
+            };
+            let endpoint = config.graphql_url(endpoint);
+            let response = client
+                .graphql::<serde_json::Value>(&endpoint, &request)
+                .await?;
+            // Data sebagian tetap ditampilkan sebelum error-nya
+            if let Some(data) = response.data.filter(|data| !data.is_null()) {
+                let json = serde_json::to_string_pretty(&data).expect("JSON value serializes");
+                println!("{json}");
+            }
+            if !response.errors.is_empty() {
+                return Err(ApiError::Graphql(response.errors));
+            }
+        }
+        Command::Download { .. } | Command::Upload { .. } => unreachable!("handled in main"),
+    }
+    Ok(())
+}
 
+// Daftar ke stdout, kegagalan dan ringkasannya ke stderr
+fn print_report<T: Row>(report: &batch::Report<T>, concurrency: usize, format: Format) {
+    let items = report.fetched.iter().map(|(_, item)| item);
+    print!("{}", output::render(items, format));
+    for (id, e) in &report.failed {
+        eprintln!("{id:>4} failed: {e}");
+    }
+    eprintln!(
+        "{} fetched, {} failed in {:.2?} with {concurrency} in flight ({:.1} requests/s)",
+        report.fetched.len(),
+        report.failed.len(),
+        report.elapsed,
+        report.rate()
+    );
+}
+
+// Record yang sudah datang ditulis bersama, paling banyak satu halaman
+async fn print_stream<T>(client: &TodoClient, url: &str, format: Format) -> Result<(), ApiError>
+where
+    T: Row + serde::de::DeserializeOwned,
+{
+    let records = ndjson::records::<T>(client, url).await?;
+    let mut batches = pin!(records.ready_chunks(PAGE_SIZE as usize));
+    let mut streamer = output::Streamer::new(format);
+    let mut count = 0;
+    while let Some(batch) = batches.next().await {
+        let mut items = Vec::with_capacity(batch.len());
+        let mut failure = None;
+        for record in batch {
+            match record {
+                Ok(item) => items.push(item),
+                Err(e) => {
+                    failure = Some(e);
+                    break;
+                }
+            }
+        }
+        count += items.len();
+        if !items.is_empty() && streamer.write(&items).is_err() {
+            return Ok(());
+        }
+        // Yang sudah tertulis tetap jadi output yang utuh
+        if let Some(e) = failure {
+            if streamer.finish().is_ok() {
+                eprintln!("stopped after {count} records");
+            }
+            return Err(e);
+        }
+    }
+    if streamer.finish().is_ok() {
+        eprintln!("{count} records");
+    }
     Ok(())
 }
+
+fn exit_code(error: &ApiError) -> u8 {
+    match error {
+        ApiError::Network(_) | ApiError::Decode(_) => EXIT_REQUEST_FAILED,
+        ApiError::Status {
+            status: StatusCode::NOT_FOUND,
+            ..
+        } => EXIT_NOT_FOUND,
+        ApiError::Status { .. } | ApiError::Graphql(_) => EXIT_REJECTED,
+    }
+}
@@ -0,0 +1,53 @@
+use crate::client::{ApiError, TodoClient};
+use bytes::Bytes;
+use futures::{stream, Stream, StreamExt};
+use serde::de::DeserializeOwned;
+
+/// Minta `url` dan baca body-nya sebagai NDJSON, satu record per baris.
+/// Yang disimpan hanya baris yang belum lengkap, jadi memori tetap kecil
+/// sebesar apa pun response-nya.
+pub async fn records<T: DeserializeOwned>(
+    client: &TodoClient,
+    url: &str,
+) -> Result<impl Stream<Item = Result<T, ApiError>>, ApiError> {
+    client.throttle().await;
+    let response = client.http().get(url).send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await?;
+        return Err(ApiError::Status { status, body });
+    }
+    Ok(lines(response.bytes_stream()).map(|line| Ok(serde_json::from_slice(&line?)?)))
+}
+
+// Potong chunk jadi baris tanpa '\n', baris kosong dilewati
+fn lines(
+    chunks: impl Stream<Item = reqwest::Result<Bytes>>,
+) -> impl Stream<Item = Result<Vec<u8>, ApiError>> {
+    stream::try_unfold(
+        (Box::pin(chunks), Vec::new(), false),
+        |(mut chunks, mut buffer, mut ended)| async move {
+            loop {
+                if let Some(end) = buffer.iter().position(|&byte| byte == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=end).collect();
+                    if line.trim_ascii().is_empty() {
+                        continue;
+                    }
+                    return Ok(Some((line, (chunks, buffer, ended))));
+                }
+                if ended {
+                    // Baris terakhir boleh tanpa '\n'
+                    if buffer.trim_ascii().is_empty() {
+                        return Ok(None);
+                    }
+                    let line = std::mem::take(&mut buffer);
+                    return Ok(Some((line, (chunks, buffer, ended))));
+                }
+                match chunks.next().await {
+                    Some(chunk) => buffer.extend_from_slice(&chunk?),
+                    None => ended = true,
+                }
+            }
+        },
+    )
+}
@@ -0,0 +1,128 @@
+use crate::client::{Todo, User};
+use clap::ValueEnum;
+use comfy_table::{presets::UTF8_FULL, ContentArrangement, Table};
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// Cara menampilkan hasil perintah yang mendaftar banyak item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Tabel yang rapi untuk dibaca
+    Table,
+    /// Array JSON apa adanya
+    Json,
+    /// CSV dengan header, untuk diteruskan ke program lain
+    Csv,
+}
+
+/// Item yang bisa jadi satu baris tabel.
+pub trait Row: Serialize {
+    const HEADERS: &'static [&'static str];
+
+    fn cells(&self) -> Vec<String>;
+}
+
+impl Row for Todo {
+    const HEADERS: &'static [&'static str] = &["id", "user", "done", "title"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.user_id.to_string(),
+            String::from(if self.completed { "x" } else { "" }),
+            self.title.clone(),
+        ]
+    }
+}
+
+impl Row for User {
+    const HEADERS: &'static [&'static str] = &["id", "name", "username", "email"];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.name.clone(),
+            self.username.clone(),
+            self.email.clone(),
+        ]
+    }
+}
+
+pub fn render<'a, T: Row + 'a>(items: impl IntoIterator<Item = &'a T>, format: Format) -> String {
+    let items: Vec<&T> = items.into_iter().collect();
+    match format {
+        Format::Table => {
+            let mut table = Table::new();
+            table
+                .load_preset(UTF8_FULL)
+                .set_content_arrangement(ContentArrangement::Dynamic)
+                .set_header(T::HEADERS.to_vec());
+            for item in &items {
+                table.add_row(item.cells());
+            }
+            format!("{table}\n")
+        }
+        Format::Json => {
+            let json = serde_json::to_string_pretty(&items).expect("items serialize to JSON");
+            format!("{json}\n")
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            for item in &items {
+                writer.serialize(item).expect("items serialize to CSV");
+            }
+            let bytes = writer.into_inner().expect("writing to memory");
+            String::from_utf8(bytes).expect("CSV of strings is UTF-8")
+        }
+    }
+}
+
+/// Menulis daftar sedikit demi sedikit, untuk item yang datang per halaman:
+/// tabel per halaman, satu array JSON dan satu CSV dengan satu header.
+pub struct Streamer {
+    format: Format,
+    written: usize,
+    csv: csv::Writer<io::Stdout>,
+}
+
+impl Streamer {
+    pub fn new(format: Format) -> Self {
+        Self {
+            format,
+            written: 0,
+            csv: csv::Writer::from_writer(io::stdout()),
+        }
+    }
+
+    /// Gagal kalau stdout tertutup, misalnya saat diteruskan ke `head`.
+    pub fn write<T: Row>(&mut self, items: &[T]) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        match self.format {
+            Format::Table => write!(stdout, "{}", render(items, Format::Table))?,
+            Format::Json => {
+                for item in items {
+                    let json = serde_json::to_string_pretty(item)?;
+                    let separator = if self.written == 0 { "[" } else { "," };
+                    self.written += 1;
+                    writeln!(stdout, "{separator}\n  {}", json.replace('\n', "\n  "))?;
+                }
+            }
+            Format::Csv => {
+                for item in items {
+                    self.csv.serialize(item)?;
+                }
+                self.csv.flush()?;
+            }
+        }
+        stdout.flush()
+    }
+
+    /// Menutup array JSON.
+    pub fn finish(self) -> io::Result<()> {
+        if self.format == Format::Json {
+            let end = if self.written == 0 { "[]" } else { "]" };
+            writeln!(io::stdout(), "{end}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use clap::ValueEnum;
+use reqwest::{redirect, Client};
+use std::time::Duration;
+
+/// Pengaturan koneksi client HTTP, dipilih dengan --profile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Profile {
+    /// Cepat menyerah, untuk server lokal atau skrip
+    Fast,
+    /// Cukup untuk API publik seperti JSONPlaceholder
+    #[default]
+    Default,
+    /// Sabar menunggu jaringan atau server yang lambat
+    Patient,
+}
+
+impl Profile {
+    /// Batas waktu membuka koneksi, termasuk TLS.
+    pub fn connect_timeout(self) -> Duration {
+        match self {
+            Profile::Fast => Duration::from_secs(2),
+            Profile::Default => Duration::from_secs(10),
+            Profile::Patient => Duration::from_secs(30),
+        }
+    }
+
+    /// Batas waktu satu request API sampai body-nya selesai dibaca. Unduhan
+    /// tidak dibatasi supaya file besar tidak terputus.
+    pub fn timeout(self) -> Duration {
+        match self {
+            Profile::Fast => Duration::from_secs(5),
+            Profile::Default => Duration::from_secs(30),
+            Profile::Patient => Duration::from_secs(120),
+        }
+    }
+
+    /// Koneksi menganggur yang disimpan per host untuk dipakai lagi.
+    pub fn pool_size(self) -> usize {
+        match self {
+            Profile::Fast => 4,
+            Profile::Default => 16,
+            Profile::Patient => 32,
+        }
+    }
+
+    /// Lama koneksi menganggur disimpan sebelum ditutup.
+    pub fn pool_idle_timeout(self) -> Duration {
+        match self {
+            Profile::Fast => Duration::from_secs(15),
+            Profile::Default => Duration::from_secs(90),
+            Profile::Patient => Duration::from_secs(300),
+        }
+    }
+
+    /// Redirect yang diikuti sebelum request dianggap gagal.
+    pub fn max_redirects(self) -> usize {
+        match self {
+            Profile::Fast => 2,
+            Profile::Default => 10,
+            Profile::Patient => 20,
+        }
+    }
+
+    pub fn client(self) -> reqwest::Result<Client> {
+        Client::builder()
+            .connect_timeout(self.connect_timeout())
+            .pool_max_idle_per_host(self.pool_size())
+            .pool_idle_timeout(self.pool_idle_timeout())
+            .redirect(redirect::Policy::limited(self.max_redirects()))
+            .build()
+    }
+}
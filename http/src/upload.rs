@@ -0,0 +1,79 @@
+use crate::client::{ApiError, TodoClient};
+use reqwest::{
+    multipart::{Form, Part},
+    Body,
+};
+use std::{io, path::Path};
+use thiserror::Error;
+use tokio::fs::File;
+use tokio_util::io::ReaderStream;
+
+#[derive(Debug, Error)]
+pub enum UploadError {
+    #[error(transparent)]
+    Request(#[from] ApiError),
+    #[error("{path}: {source}")]
+    Io { path: String, source: io::Error },
+}
+
+/// Kirim `files` sebagai multipart/form-data, masing-masing di field `name`,
+/// bersama `fields`. Isi file dibaca sedikit demi sedikit saat dikirim.
+/// Hasilnya body response dari server.
+pub async fn upload(
+    client: &TodoClient,
+    url: &str,
+    name: &str,
+    files: &[impl AsRef<Path>],
+    fields: &[(String, String)],
+) -> Result<String, UploadError> {
+    let mut form = Form::new();
+    for (key, value) in fields {
+        form = form.text(key.clone(), value.clone());
+    }
+    for path in files {
+        form = form.part(name.to_string(), file_part(path.as_ref()).await?);
+    }
+
+    client.throttle().await;
+    let response = client
+        .http()
+        .post(url)
+        .multipart(form)
+        .send()
+        .await
+        .map_err(ApiError::from)?;
+    let status = response.status();
+    let body = response.text().await.map_err(ApiError::from)?;
+    if !status.is_success() {
+        return Err(ApiError::Status { status, body }.into());
+    }
+    Ok(body)
+}
+
+// Panjangnya dikirim di awal supaya server tidak perlu menerima chunked body
+async fn file_part(path: &Path) -> Result<Part, UploadError> {
+    let io_error = |source| UploadError::Io {
+        path: path.display().to_string(),
+        source,
+    };
+    let file = File::open(path).await.map_err(io_error)?;
+    let length = file.metadata().await.map_err(io_error)?.len();
+    let body = Body::wrap_stream(ReaderStream::new(file));
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let part = Part::stream_with_length(body, length)
+        .file_name(file_name)
+        .mime_str("application/octet-stream")
+        .expect("valid MIME type");
+    Ok(part)
+}
+
+/// Field form seperti `key=value`.
+pub fn parse_field(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected key=value, got '{arg}'")),
+    }
+}
@@ -0,0 +1,110 @@
+//! Correlated standard normal shocks for simulating several assets at once.
+
+use rand::{rngs::StdRng, Rng};
+use rand_distr::StandardNormal;
+
+/// Draws one shock per asset with a given correlation matrix, by applying
+/// its Cholesky factor to independent normals.
+#[derive(Debug, Clone)]
+pub struct CorrelatedShocks {
+    /// Lower-triangular `L` with `L * Lᵀ` equal to the correlation matrix.
+    factor: Vec<Vec<f64>>,
+}
+
+impl CorrelatedShocks {
+    pub fn new(correlation: &[Vec<f64>]) -> Result<Self, String> {
+        let n = correlation.len();
+        if correlation.iter().any(|row| row.len() != n) {
+            return Err(String::from("the correlation matrix must be square"));
+        }
+        if correlation.iter().flatten().any(|value| !value.is_finite()) {
+            return Err(String::from(
+                "the correlation matrix must only hold finite numbers",
+            ));
+        }
+        for (i, row) in correlation.iter().enumerate() {
+            if let Some(j) = (0..n).find(|&j| j != i && !(-1.0..=1.0).contains(&row[j])) {
+                return Err(format!(
+                    "the correlation of assets {} and {} is {}, outside -1 to 1",
+                    i + 1,
+                    j + 1,
+                    row[j]
+                ));
+            }
+            if (row[i] - 1.0).abs() > 1e-9 {
+                return Err(String::from(
+                    "the correlation matrix must have 1 on its diagonal",
+                ));
+            }
+            if (0..i).any(|j| (row[j] - correlation[j][i]).abs() > 1e-9) {
+                return Err(String::from("the correlation matrix must be symmetric"));
+            }
+        }
+
+        let mut factor = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for j in 0..=i {
+                let sum: f64 = (0..j).map(|k| factor[i][k] * factor[j][k]).sum();
+                if i == j {
+                    let pivot = correlation[i][i] - sum;
+                    if pivot <= 0.0 {
+                        return Err(String::from(
+                            "the correlation matrix is not positive definite",
+                        ));
+                    }
+                    factor[i][j] = pivot.sqrt();
+                } else {
+                    factor[i][j] = (correlation[i][j] - sum) / factor[j][j];
+                }
+            }
+        }
+        Ok(Self { factor })
+    }
+
+    /// One correlated standard normal shock per asset.
+    pub fn sample(&self, rng: &mut StdRng) -> Vec<f64> {
+        let independent: Vec<f64> = self
+            .factor
+            .iter()
+            .map(|_| rng.sample(StandardNormal))
+            .collect();
+        self.factor
+            .iter()
+            .map(|row| row.iter().zip(&independent).map(|(l, z)| l * z).sum())
+            .collect()
+    }
+}
+
+/// Parses `--correlation`: a single number used for every pair, or the full
+/// matrix as rows separated by `;`, e.g. `1,0.8;0.8,1`.
+pub fn parse_matrix(spec: &str, assets: usize) -> Result<Vec<Vec<f64>>, String> {
+    let number = |text: &str| {
+        text.trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| format!("'{}' is not a finite number", text.trim()))
+    };
+    if !spec.contains([',', ';']) {
+        let rho = number(spec)?;
+        return Ok((0..assets)
+            .map(|i| {
+                (0..assets)
+                    .map(|j| if i == j { 1.0 } else { rho })
+                    .collect()
+            })
+            .collect());
+    }
+    let matrix = spec
+        .split(';')
+        .map(|row| row.split(',').map(number).collect())
+        .collect::<Result<Vec<Vec<f64>>, _>>()?;
+    if matrix.len() != assets {
+        return Err(format!(
+            "the correlation matrix has {} rows for {} assets",
+            matrix.len(),
+            assets
+        ));
+    }
+    Ok(matrix)
+}
@@ -0,0 +1,78 @@
+//! Saves the generated candles to CSV so a path can be replayed later with
+//! `--replay`.
+
+use crate::Candle;
+use std::{
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+};
+
+/// Writes every candle of one chart to a CSV with `open,high,low,close,volume`
+/// columns.
+pub struct Recorder {
+    path: PathBuf,
+    writer: csv::Writer<File>,
+    /// Flush after every candle instead of only on exit.
+    live: bool,
+    /// First write error, reported when the recorder is finished so a full
+    /// disk does not tear down the chart.
+    error: Option<io::Error>,
+}
+
+impl Recorder {
+    pub fn create(path: PathBuf, live: bool) -> io::Result<Self> {
+        let writer = csv::Writer::from_path(&path).map_err(io::Error::other)?;
+        Ok(Self {
+            path,
+            writer,
+            live,
+            error: None,
+        })
+    }
+
+    pub fn record(&mut self, candle: &Candle) {
+        if self.error.is_some() {
+            return;
+        }
+        let written = self
+            .writer
+            .serialize(candle)
+            .map_err(io::Error::other)
+            .and_then(|()| {
+                if self.live {
+                    self.writer.flush()
+                } else {
+                    Ok(())
+                }
+            });
+        if let Err(e) = written {
+            self.error = Some(e);
+        }
+    }
+
+    /// Flushes what is left and reports the first error, if any.
+    pub fn finish(mut self) -> io::Result<()> {
+        let result = match self.error.take() {
+            Some(e) => Err(e),
+            None => self.writer.flush(),
+        };
+        result.map_err(|e| io::Error::new(e.kind(), format!("{}: {}", self.path.display(), e)))
+    }
+}
+
+/// File for the asset `name`: `path` itself with one asset, otherwise
+/// the name added to the file stem, e.g. `paths-SIM2.csv`.
+pub fn asset_path(path: &Path, name: &str, assets: usize) -> PathBuf {
+    if assets == 1 {
+        return path.to_path_buf();
+    }
+    let stem = path
+        .file_stem()
+        .map_or_else(String::new, |s| s.to_string_lossy().into_owned());
+    let file = match path.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, name, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, name),
+    };
+    path.with_file_name(file)
+}
@@ -0,0 +1,57 @@
+//! Monte Carlo fan: many paths simulated forward from the current price,
+//! summarised as percentile bands.
+
+use crate::model::{Jumps, PriceModel};
+use rand::{rngs::StdRng, Rng};
+use rand_distr::StandardNormal;
+
+/// Percentiles drawn as the fan, outermost first.
+pub const PERCENTILES: [f64; 5] = [5.0, 25.0, 50.0, 75.0, 95.0];
+
+/// Settings and random source of the fan of one chart.
+pub struct MonteCarlo {
+    pub paths: usize,
+    /// Candles simulated ahead of the last one.
+    pub horizon: usize,
+    /// Separate from the chart's generator so the fan does not change the path.
+    pub rng: StdRng,
+}
+
+impl MonteCarlo {
+    /// Runs the paths from `last` and returns one band per step ahead, each
+    /// holding the prices at `PERCENTILES`.
+    pub fn bands(
+        &mut self,
+        model: &PriceModel,
+        jumps: Option<&Jumps>,
+        last: f64,
+    ) -> Vec<[f64; PERCENTILES.len()]> {
+        let mut steps = vec![Vec::with_capacity(self.paths); self.horizon];
+        for _ in 0..self.paths {
+            let mut model = *model;
+            let mut price = last;
+            for step in &mut steps {
+                let open = match jumps {
+                    Some(jumps) => price * jumps.sample_factor(&mut self.rng),
+                    None => price,
+                };
+                let shock = self.rng.sample(StandardNormal);
+                price = model.next_close(open, shock, &mut self.rng);
+                step.push(price);
+            }
+        }
+        steps
+            .into_iter()
+            .map(|mut prices| {
+                prices.sort_by(f64::total_cmp);
+                PERCENTILES.map(|p| percentile(&prices, p))
+            })
+            .collect()
+    }
+}
+
+/// Nearest-rank percentile of sorted `prices`.
+fn percentile(prices: &[f64], p: f64) -> f64 {
+    let rank = (p / 100.0 * (prices.len() - 1) as f64).round() as usize;
+    prices[rank]
+}
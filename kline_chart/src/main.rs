@@ -1,53 +1,242 @@
 //! A terminal-based candlestick chart implementation with MA50 indicator
 //! using ratatui and crossterm.
 
+mod correlation;
+mod export;
+mod fan;
+mod model;
+mod replay;
+mod source;
+#[cfg(test)]
+mod tests;
+
+use clap::Parser;
+use correlation::CorrelatedShocks;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use rand::Rng;
+use export::Recorder;
+use fan::{MonteCarlo, PERCENTILES};
+use kline_core::{bybit::BybitSource, chart, Candle, DataSource};
+use model::{
+    Jumps, ModelKind, OrnsteinUhlenbeck, PriceModel, Regime, RegimeParams, RegimeSwitching,
+};
+use rand::{rngs::StdRng, SeedableRng};
 use ratatui::{
     backend::CrosstermBackend,
-    layout::Rect,
-    style::Color,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
     widgets::{
-        canvas::{Canvas, Context, Line, Points},
+        canvas::{Canvas, Context, Line},
         Block, Borders,
     },
     Frame, Terminal,
 };
-use std::{collections::VecDeque, io, time::Duration};
+use replay::Replay;
+use source::{ChartSource, SimulatedSource, SourceKind};
+use std::{collections::VecDeque, io, path::PathBuf, time::Duration};
 
 // Constants
 const UPDATE_INTERVAL_MS: u64 = 500;
 const VISIBLE_RANGE: usize = 50;
 const MA_WINDOW_SIZE: usize = 50;
 const PRICE_VOLATILITY_FACTOR: f64 = 0.01;
-const PRICE_CHANGE_RANGE: (f64, f64) = (-2.0, 2.0);
 const INITIAL_PRICE: f64 = 100.0;
+/// Added to the seed for the fan generators, far from the per-asset seeds.
+const FAN_SEED_OFFSET: u64 = 1000;
+/// Candle intervals '+' and '-' step through, in milliseconds.
+const SPEED_STEPS_MS: [u64; 10] = [100, 200, 300, 500, 750, 1000, 1500, 2000, 3000, 5000];
+/// Volume of a candle that doesn't move.
+const BASE_VOLUME: f64 = 1000.0;
+/// Extra volume per percent the price moves, as a fraction of the base.
+const VOLUME_PER_PERCENT_MOVE: f64 = 0.8;
+/// Spread of the lognormal noise on simulated volume.
+const VOLUME_NOISE: f64 = 0.3;
+/// Floor of a simulated price, however far a move throws it.
+const MIN_PRICE: f64 = 0.01;
+
+/// Simulated candlestick chart in the terminal.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Seed the price path so a run can be reproduced exactly; random if not given
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Replay the candles of an OHLCV CSV one per --interval instead of simulating
+    #[arg(long, value_name = "PATH")]
+    replay: Option<PathBuf>,
+
+    /// Where the candles come from when not replaying
+    #[arg(long, value_enum, default_value = "sim", conflicts_with = "replay")]
+    source: SourceKind,
+
+    /// Bybit linear contract charted by --source bybit
+    #[arg(long, default_value = "BTCUSDT")]
+    symbol: String,
+
+    /// Bybit kline interval for --source bybit: minutes (1, 5, 60, ...), D, W or M
+    #[arg(long, default_value = "1")]
+    kline_interval: String,
+
+    /// Simulate this many assets at once; Tab switches between them, 'o' overlays them
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=4), conflicts_with = "replay")]
+    assets: u8,
+
+    /// Correlation of the assets' moves: one value for every pair, or a matrix like "1,0.8;0.8,1"
+    #[arg(long, default_value = "0.5", allow_hyphen_values = true)]
+    correlation: String,
+
+    /// Save every candle to this CSV on exit, to replay the path later; with
+    /// several assets each gets its own file named after it
+    #[arg(long, value_name = "PATH")]
+    export: Option<PathBuf>,
+
+    /// Flush --export after every candle instead of only on exit
+    #[arg(long, requires = "export")]
+    export_live: bool,
+
+    /// Simulate this many paths ahead of the last candle and draw their
+    /// 5/25/50/75/95th percentile bands
+    #[arg(long, value_name = "PATHS", value_parser = clap::value_parser!(u64).range(1..))]
+    fan: Option<u64>,
+
+    /// Candles the --fan paths run ahead
+    #[arg(long, default_value_t = 20, value_parser = clap::value_parser!(u64).range(1..))]
+    fan_horizon: u64,
+
+    /// Price process to simulate
+    #[arg(long, value_enum, default_value = "random")]
+    model: ModelKind,
+
+    /// Price of the first candle
+    #[arg(long, default_value_t = INITIAL_PRICE, value_parser = parse_price)]
+    initial_price: f64,
+
+    /// Milliseconds between new candles
+    #[arg(long, value_name = "MS", default_value_t = UPDATE_INTERVAL_MS)]
+    interval: u64,
+
+    /// Candles shown on screen
+    #[arg(long, default_value_t = VISIBLE_RANGE)]
+    visible_range: usize,
+
+    /// Largest percent move per candle of the random model, either way
+    #[arg(long, default_value_t = 2.0, value_parser = parse_volatility)]
+    volatility: f64,
+
+    /// Percent added to every candle's move by the random model, e.g. 0.1 for an uptrend
+    #[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+    drift: f64,
+
+    /// Level the ou model reverts to; defaults to the initial price
+    #[arg(long)]
+    ou_mean: Option<f64>,
+
+    /// Fraction of the distance to the mean the ou model closes per candle
+    #[arg(long, default_value_t = 0.1)]
+    ou_speed: f64,
+
+    /// Standard deviation of the ou model's per-candle move, in price units
+    #[arg(long, default_value_t = 1.0)]
+    ou_volatility: f64,
+
+    /// Percent drift per candle of the regime model's trending state
+    #[arg(long, default_value_t = 0.3, allow_hyphen_values = true)]
+    trend_drift: f64,
+
+    /// Percent volatility per candle of the trending state
+    #[arg(long, default_value_t = 0.8)]
+    trend_volatility: f64,
 
-/// Represents a single candlestick with OHLC data
-#[derive(Debug, Clone)]
-struct Candle {
-    open: f64,
-    high: f64,
-    low: f64,
-    close: f64,
+    /// Percent drift per candle of the choppy state
+    #[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+    chop_drift: f64,
+
+    /// Percent volatility per candle of the choppy state
+    #[arg(long, default_value_t = 1.5)]
+    chop_volatility: f64,
+
+    /// Chance per candle that the trending state continues
+    #[arg(long, default_value_t = 0.95)]
+    trend_persistence: f64,
+
+    /// Chance per candle that the choppy state continues
+    #[arg(long, default_value_t = 0.9)]
+    chop_persistence: f64,
+
+    /// Show the regime model's current state on the chart
+    #[arg(long)]
+    show_regime: bool,
+
+    /// Expected price jumps per candle on top of the model; 0 disables them
+    #[arg(long, default_value_t = 0.0)]
+    jump_intensity: f64,
+
+    /// Mean log size of a jump, e.g. -0.02 for jumps that are mostly 2% drops
+    #[arg(long, default_value_t = 0.0, allow_hyphen_values = true)]
+    jump_mean: f64,
+
+    /// Standard deviation of the log jump size
+    #[arg(long, default_value_t = 0.05)]
+    jump_volatility: f64,
 }
 
-impl Candle {
-    fn new(open: f64, high: f64, low: f64, close: f64) -> Self {
-        Self {
-            open,
-            high,
-            low,
-            close,
+impl Cli {
+    fn price_model(&self) -> PriceModel {
+        match self.model {
+            ModelKind::Random => PriceModel::Random {
+                drift: self.drift,
+                volatility: self.volatility,
+            },
+            ModelKind::Ou => PriceModel::MeanReverting(OrnsteinUhlenbeck {
+                mean: self.ou_mean.unwrap_or(self.initial_price),
+                speed: self.ou_speed,
+                volatility: self.ou_volatility,
+            }),
+            ModelKind::Regime => PriceModel::RegimeSwitching(RegimeSwitching {
+                trending: RegimeParams {
+                    drift: self.trend_drift,
+                    volatility: self.trend_volatility,
+                    persistence: self.trend_persistence,
+                },
+                choppy: RegimeParams {
+                    drift: self.chop_drift,
+                    volatility: self.chop_volatility,
+                    persistence: self.chop_persistence,
+                },
+                regime: Regime::Trending,
+            }),
         }
     }
 
-    fn is_bullish(&self) -> bool {
-        self.close >= self.open
+    fn jumps(&self) -> Result<Option<Jumps>, String> {
+        if self.jump_intensity <= 0.0 {
+            return Ok(None);
+        }
+        Jumps::new(self.jump_intensity, self.jump_mean, self.jump_volatility).map(Some)
+    }
+}
+
+/// A price above zero, for --initial-price.
+fn parse_price(arg: &str) -> Result<f64, String> {
+    let price: f64 = arg.parse().map_err(|e| format!("{}", e))?;
+    if price > 0.0 && price.is_finite() {
+        Ok(price)
+    } else {
+        Err(String::from("must be greater than 0"))
+    }
+}
+
+/// A percent move strictly between 0 and 100, for --volatility.
+fn parse_volatility(arg: &str) -> Result<f64, String> {
+    let volatility: f64 = arg.parse().map_err(|e| format!("{}", e))?;
+    if volatility > 0.0 && volatility < 100.0 {
+        Ok(volatility)
+    } else {
+        Err(String::from("must be greater than 0 and less than 100"))
     }
 }
 
@@ -57,16 +246,34 @@ struct CandlestickChart {
     visible_range: usize,
     last_price: f64,
     ma50_values: VecDeque<f64>,
+    name: String,
+    source: Box<dyn ChartSource>,
+    show_regime: bool,
+    recorder: Option<Recorder>,
+    fan: Option<MonteCarlo>,
+    /// Percentile prices per candle ahead, recomputed with every candle.
+    fan_bands: Vec<[f64; PERCENTILES.len()]>,
 }
 
 impl CandlestickChart {
-    fn new(candles: Vec<Candle>, visible_range: usize) -> Self {
+    fn new(
+        name: String,
+        candles: Vec<Candle>,
+        visible_range: usize,
+        source: Box<dyn ChartSource>,
+    ) -> Self {
         let last_price = candles.last().map_or(INITIAL_PRICE, |c| c.close);
         Self {
             candles,
             visible_range,
             last_price,
             ma50_values: VecDeque::new(),
+            name,
+            source,
+            show_regime: false,
+            recorder: None,
+            fan: None,
+            fan_bands: Vec::new(),
         }
     }
 
@@ -82,165 +289,177 @@ impl CandlestickChart {
         }
     }
 
-    fn generate_new_candle(&mut self) {
-        let mut rng = rand::thread_rng();
-        let price_change_percent = rng.gen_range(PRICE_CHANGE_RANGE.0..PRICE_CHANGE_RANGE.1);
-        let movement = self.last_price * (price_change_percent / 100.0);
-        let new_price = self.last_price + movement;
-
-        let volatility = self.last_price * PRICE_VOLATILITY_FACTOR;
-        let high = new_price + rng.gen_range(0.0..volatility);
-        let low = new_price - rng.gen_range(0.0..volatility);
-
-        let new_candle = Candle::new(self.last_price, high, low, new_price);
+    /// Adds the next candle from the source, given the standard normal
+    /// `shock` of this step; once the source runs out the chart stays as it is.
+    fn next_candle(&mut self, shock: f64) {
+        if let Some(candle) = self.source.next_candle(shock) {
+            self.last_price = candle.close;
+            self.push_candle(candle);
+        }
+    }
 
-        self.last_price = new_price;
-        self.candles.push(new_candle);
+    fn push_candle(&mut self, candle: Candle) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(&candle);
+        }
+        self.candles.push(candle);
 
         if self.candles.len() > self.visible_range {
             self.candles.remove(0);
         }
 
         self.calculate_ma50();
+        self.update_fan();
     }
 
-    fn draw(&self, frame: &mut Frame, area: Rect) {
-        let chart_block = Block::default()
-            .borders(Borders::ALL)
-            .title("Live Candlestick Chart with MA50 (Press 'q' to quit)");
+    fn update_fan(&mut self) {
+        if let Some(fan) = &mut self.fan {
+            self.fan_bands = self.source.forecast(fan, self.last_price);
+        }
+    }
+
+    fn visible_candles(&self) -> &[Candle] {
+        &self.candles[self.candles.len().saturating_sub(self.visible_range)..]
+    }
 
-        let visible_candles =
-            &self.candles[self.candles.len().saturating_sub(self.visible_range)..];
-        let (min_price, max_price) = self.calculate_price_range(visible_candles);
-        let (y_min, y_max) = self.calculate_y_bounds(min_price, max_price);
+    /// Draws the chart titled with `source`, with `overlays` drawn as lines
+    /// over the candles.
+    fn draw(&self, frame: &mut Frame, area: Rect, source: &str, overlays: &[Overlay]) {
+        let chart_block = Block::default().borders(Borders::ALL).title(format!(
+            "Live Candlestick Chart with MA50, {} (Press 'q' to quit)",
+            source
+        ));
+
+        let visible_candles = self.visible_candles();
+        // Room to the right of the last candle for the fan, then about
+        // eight cells for the price labels
+        let ahead = self.fan_bands.len();
+        let x_max = if ahead == 0 {
+            (visible_candles.len() + 2) as f64
+        } else {
+            let cells = f64::from(area.width.saturating_sub(2).max(16));
+            (visible_candles.len() + ahead) as f64 * cells / (cells - 8.0)
+        };
+        let (mut min_price, mut max_price) = chart::price_range(visible_candles);
+        for price in overlays
+            .iter()
+            .flat_map(|o| &o.closes)
+            .chain(self.fan_bands.iter().flatten())
+        {
+            min_price = min_price.min(*price);
+            max_price = max_price.max(*price);
+        }
+        let (y_min, y_max) = chart::y_bounds(min_price, max_price);
 
         let canvas = Canvas::default()
             .block(chart_block)
             .paint(|ctx| {
-                self.draw_price_labels(ctx, visible_candles.len() as f64, y_min, y_max);
-                self.draw_candlesticks(ctx, visible_candles);
+                chart::draw_price_labels(ctx, (visible_candles.len() + ahead) as f64, y_min, y_max);
+                chart::draw_candlesticks(ctx, visible_candles, |candle| {
+                    if candle.is_bullish() {
+                        Color::Green
+                    } else {
+                        Color::Red
+                    }
+                });
                 self.draw_ma50_line(ctx);
+                for overlay in overlays {
+                    self.draw_overlay(ctx, overlay);
+                }
+                self.draw_fan(ctx, visible_candles.len());
                 self.draw_indicators(ctx, visible_candles, y_max);
             })
-            .x_bounds([0.0, (visible_candles.len() + 2) as f64])
+            .x_bounds([0.0, x_max])
             .y_bounds([y_min, y_max]);
 
-        frame.render_widget(canvas, area);
-    }
-
-    fn calculate_price_range(&self, candles: &[Candle]) -> (f64, f64) {
-        let min_price = candles
-            .iter()
-            .map(|c| c.low)
-            .fold(f64::INFINITY, |a, b| a.min(b));
-        let max_price = candles
-            .iter()
-            .map(|c| c.high)
-            .fold(f64::NEG_INFINITY, |a, b| a.max(b));
-        (min_price, max_price)
-    }
-
-    fn calculate_y_bounds(&self, min_price: f64, max_price: f64) -> (f64, f64) {
-        let price_range = max_price - min_price;
-        let padding = price_range * 0.1;
-        (min_price - padding, max_price + padding)
-    }
-
-    fn draw_price_labels(&self, ctx: &mut Context, x: f64, y_min: f64, y_max: f64) {
-        let num_labels = 5;
-        for i in 0..=num_labels {
-            let price = y_min + (y_max - y_min) * (i as f64 / num_labels as f64);
-            ctx.print(x + 0.5, price, format!("{:.2}", price));
-        }
+        let [price_area, volume_area] = *Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(75), Constraint::Percentage(25)])
+            .split(area)
+        else {
+            return;
+        };
+        frame.render_widget(canvas, price_area);
+        self.draw_volume(frame, volume_area, visible_candles, x_max);
     }
 
-    fn draw_candlesticks(&self, ctx: &mut Context, candles: &[Candle]) {
+    /// Draws the volume bars on the same `x_max` as the candles so they
+    /// line up.
+    fn draw_volume(&self, frame: &mut Frame, area: Rect, candles: &[Candle], x_max: f64) {
+        let max_volume = candles.iter().map(|c| c.volume).fold(0.0, f64::max);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Volume (max {:.0})", max_volume));
         let candle_width = 0.8;
-        for (i, candle) in candles.iter().enumerate() {
-            let x = i as f64;
-            let color = if candle.is_bullish() {
-                Color::Green
-            } else {
-                Color::Red
-            };
-
-            self.draw_candle_wick(ctx, x, candle_width, candle, color);
-            self.draw_candle_body(ctx, x, candle_width, candle, color);
-        }
-    }
-
-    fn draw_candle_wick(
-        &self,
-        ctx: &mut Context,
-        x: f64,
-        width: f64,
-        candle: &Candle,
-        color: Color,
-    ) {
-        ctx.draw(&Line {
-            x1: x + width / 2.0,
-            y1: candle.low,
-            x2: x + width / 2.0,
-            y2: candle.high,
-            color,
-        });
+        let canvas = Canvas::default()
+            .block(block)
+            .paint(|ctx| {
+                for (i, candle) in candles.iter().enumerate() {
+                    let color = if candle.is_bullish() {
+                        Color::Green
+                    } else {
+                        Color::Red
+                    };
+                    for step in 0..=4 {
+                        let x = i as f64 + candle_width * step as f64 / 4.0;
+                        ctx.draw(&Line {
+                            x1: x,
+                            y1: 0.0,
+                            x2: x,
+                            y2: candle.volume,
+                            color,
+                        });
+                    }
+                }
+            })
+            .x_bounds([0.0, x_max])
+            .y_bounds([0.0, max_volume.max(1.0)]);
+        frame.render_widget(canvas, area);
     }
 
-    fn draw_candle_body(
-        &self,
-        ctx: &mut Context,
-        x: f64,
-        width: f64,
-        candle: &Candle,
-        color: Color,
-    ) {
-        let (body_top, body_bottom) = if candle.is_bullish() {
-            (candle.close, candle.open)
-        } else {
-            (candle.open, candle.close)
-        };
-
-        if (body_top - body_bottom).abs() < 0.001 {
-            self.draw_flat_candle(ctx, x, width, body_top, color);
-        } else {
-            self.draw_filled_candle(ctx, x, width, body_top, body_bottom, color);
+    fn draw_overlay(&self, ctx: &mut Context, overlay: &Overlay) {
+        for (i, pair) in overlay.closes.windows(2).enumerate() {
+            ctx.draw(&Line {
+                x1: i as f64 + 0.4,
+                y1: pair[0],
+                x2: (i + 1) as f64 + 0.4,
+                y2: pair[1],
+                color: overlay.color,
+            });
+        }
+        if let Some(last) = overlay.closes.last() {
+            ctx.print(
+                overlay.closes.len() as f64,
+                *last,
+                ratatui::text::Line::styled(
+                    overlay.name.clone(),
+                    Style::default().fg(overlay.color),
+                ),
+            );
         }
     }
 
-    fn draw_flat_candle(&self, ctx: &mut Context, x: f64, width: f64, price: f64, color: Color) {
-        ctx.draw(&Line {
-            x1: x,
-            y1: price,
-            x2: x + width,
-            y2: price,
-            color,
-        });
-    }
-
-    fn draw_filled_candle(
-        &self,
-        ctx: &mut Context,
-        x: f64,
-        width: f64,
-        top: f64,
-        bottom: f64,
-        color: Color,
-    ) {
-        let points = vec![(x, bottom), (x + width, bottom), (x + width, top), (x, top)];
-        ctx.draw(&Points {
-            coords: &points,
-            color,
-        });
-
-        for y in (((bottom * 100.0) as i32)..=((top * 100.0) as i32)).step_by(1) {
-            let y = y as f64 / 100.0;
-            ctx.draw(&Line {
-                x1: x,
-                y1: y,
-                x2: x + width,
-                y2: y,
-                color,
-            });
+    /// Draws each percentile band as a line fanning out from the last close
+    /// of the `len` visible candles.
+    fn draw_fan(&self, ctx: &mut Context, len: usize) {
+        for (band, percentile) in PERCENTILES.iter().enumerate() {
+            let color = match *percentile as u32 {
+                50 => Color::White,
+                25 | 75 => Color::Gray,
+                _ => Color::DarkGray,
+            };
+            let mut previous = (len as f64 - 0.6, self.last_price);
+            for (step, prices) in self.fan_bands.iter().enumerate() {
+                let point = ((len + step) as f64 + 0.4, prices[band]);
+                ctx.draw(&Line {
+                    x1: previous.0,
+                    y1: previous.1,
+                    x2: point.0,
+                    y2: point.1,
+                    color,
+                });
+                previous = point;
+            }
         }
     }
 
@@ -271,11 +490,267 @@ impl CandlestickChart {
             if let Some(last_ma) = self.ma50_values.back() {
                 ctx.print(0.0, y_max * 0.90, format!("MA50: {:.2}", last_ma));
             }
+            if let Some(regime) = self.source.regime().filter(|_| self.show_regime) {
+                let color = match regime {
+                    Regime::Trending => Color::Cyan,
+                    Regime::Choppy => Color::Magenta,
+                };
+                ctx.print(
+                    0.0,
+                    y_max * 0.85,
+                    ratatui::text::Line::styled(
+                        format!("Regime: {}", regime.name()),
+                        Style::default().fg(color),
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Another asset's closes drawn over the chart, rescaled to its price.
+struct Overlay {
+    name: String,
+    closes: Vec<f64>,
+    color: Color,
+}
+
+const OVERLAY_COLORS: [Color; 3] = [Color::Cyan, Color::Magenta, Color::LightBlue];
+
+/// The simulated assets, moved together by correlated shocks.
+struct Market {
+    charts: Vec<CandlestickChart>,
+    shocks: CorrelatedShocks,
+    rng: StdRng,
+    seed: u64,
+    selected: usize,
+    overlay: bool,
+    /// No new candles until unpaused, except single steps.
+    paused: bool,
+    /// Time between candles.
+    interval: Duration,
+}
+
+impl Market {
+    /// Builds the assets `cli` asks for, seeded with `cli.seed` or a random seed.
+    fn from_cli(cli: &Cli) -> io::Result<Self> {
+        let jumps = cli
+            .jumps()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let seed = cli.seed.unwrap_or_else(rand::random);
+        let mut replay = cli.replay.as_deref().map(Replay::load).transpose()?;
+        let shocks = correlation::parse_matrix(&cli.correlation, cli.assets as usize)
+            .and_then(|matrix| CorrelatedShocks::new(&matrix))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        let (mut live, history) = match cli.source {
+            SourceKind::Sim => (None, None),
+            SourceKind::Bybit if cli.assets > 1 => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "--source bybit charts a single asset",
+                ));
+            }
+            SourceKind::Bybit => {
+                let (source, history) = BybitSource::connect(
+                    &cli.symbol,
+                    &cli.kline_interval,
+                    cli.visible_range.max(1),
+                )
+                .map_err(io::Error::other)?;
+                (Some(source), Some(history))
+            }
+        };
+
+        let initial_price = cli.initial_price;
+        let initial_candles = match history {
+            Some(history) => history,
+            None => vec![match replay.as_mut().and_then(|r| r.next_candle(0.0)) {
+                Some(candle) => candle,
+                None => Candle::new(
+                    initial_price,
+                    initial_price * 1.05,
+                    initial_price * 0.98,
+                    initial_price * 1.03,
+                    BASE_VOLUME,
+                ),
+            }],
+        };
+        let last_close = initial_candles.last().map_or(initial_price, |c| c.close);
+        let mut charts = (0..cli.assets)
+            .map(|i| {
+                let source: Box<dyn ChartSource> = match (replay.take(), live.take()) {
+                    (Some(replay), _) => Box::new(replay),
+                    (None, Some(live)) => Box::new(live),
+                    (None, None) => Box::new(SimulatedSource::new(
+                        last_close,
+                        StdRng::seed_from_u64(seed.wrapping_add(i as u64 + 1)),
+                        cli.price_model(),
+                        jumps,
+                    )),
+                };
+                let name = match cli.source {
+                    SourceKind::Sim => format!("SIM{}", i + 1),
+                    SourceKind::Bybit => cli.symbol.to_uppercase(),
+                };
+                let mut chart = CandlestickChart::new(
+                    name,
+                    initial_candles[..1].to_vec(),
+                    cli.visible_range.max(1),
+                    source,
+                );
+                // The rest of the history goes through the chart so the MA covers it
+                for candle in &initial_candles[1..] {
+                    chart.push_candle(candle.clone());
+                }
+                chart.show_regime = cli.show_regime;
+                if let Some(paths) = cli.fan {
+                    chart.fan = Some(MonteCarlo {
+                        paths: paths as usize,
+                        horizon: cli.fan_horizon as usize,
+                        rng: StdRng::seed_from_u64(seed.wrapping_add(FAN_SEED_OFFSET + i as u64)),
+                    });
+                    chart.update_fan();
+                }
+                chart
+            })
+            .collect::<Vec<_>>();
+        if let Some(path) = &cli.export {
+            for chart in &mut charts {
+                let path = export::asset_path(path, &chart.name, cli.assets as usize);
+                let mut recorder = Recorder::create(path, cli.export_live)?;
+                for candle in &initial_candles {
+                    recorder.record(candle);
+                }
+                chart.recorder = Some(recorder);
+            }
+        }
+        Ok(Self {
+            charts,
+            shocks,
+            rng: StdRng::seed_from_u64(seed),
+            seed,
+            selected: 0,
+            overlay: false,
+            paused: false,
+            interval: Duration::from_millis(cli.interval),
+        })
+    }
+
+    fn next_candle(&mut self) {
+        let shocks = self.shocks.sample(&mut self.rng);
+        for (chart, shock) in self.charts.iter_mut().zip(shocks) {
+            chart.next_candle(shock);
+        }
+    }
+
+    /// Finishes the `--export` files, if any.
+    fn finish_exports(&mut self) -> io::Result<()> {
+        for chart in &mut self.charts {
+            if let Some(recorder) = chart.recorder.take() {
+                recorder.finish()?;
+            }
         }
+        Ok(())
+    }
+
+    fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % self.charts.len();
+    }
+
+    fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    /// Adds exactly one candle, pausing first so the next one waits.
+    fn step(&mut self) {
+        self.paused = true;
+        self.next_candle();
+    }
+
+    /// Steps the candle interval down to the next shorter preset.
+    fn faster(&mut self) {
+        let current = self.interval.as_millis() as u64;
+        let next = SPEED_STEPS_MS
+            .iter()
+            .rev()
+            .find(|&&ms| ms < current)
+            .unwrap_or(&SPEED_STEPS_MS[0]);
+        self.interval = Duration::from_millis(*next);
+    }
+
+    /// Steps the candle interval up to the next longer preset.
+    fn slower(&mut self) {
+        let current = self.interval.as_millis() as u64;
+        let next = SPEED_STEPS_MS
+            .iter()
+            .find(|&&ms| ms > current)
+            .unwrap_or(&SPEED_STEPS_MS[SPEED_STEPS_MS.len() - 1]);
+        self.interval = Duration::from_millis(*next);
+    }
+
+    fn toggle_overlay(&mut self) {
+        self.overlay = !self.overlay;
+    }
+
+    /// The other assets' visible closes, rebased so each starts at the
+    /// selected asset's first visible close.
+    fn overlays(&self) -> Vec<Overlay> {
+        let chart = &self.charts[self.selected];
+        let Some(base) = chart.visible_candles().first().map(|c| c.close) else {
+            return Vec::new();
+        };
+        self.charts
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != self.selected)
+            .zip(OVERLAY_COLORS.iter().cycle())
+            .filter_map(|((_, other), &color)| {
+                let candles = other.visible_candles();
+                let first = candles.first()?.close;
+                Some(Overlay {
+                    name: other.name.clone(),
+                    closes: candles.iter().map(|c| c.close / first * base).collect(),
+                    color,
+                })
+            })
+            .collect()
+    }
+
+    fn draw(&self, frame: &mut Frame, area: Rect) {
+        let chart = &self.charts[self.selected];
+        let mut source = chart
+            .source
+            .describe()
+            .unwrap_or_else(|| format!("seed {}", self.seed));
+        if self.charts.len() > 1 {
+            source = format!(
+                "{} {}/{}, {}, Tab asset, 'o' overlay",
+                chart.name,
+                self.selected + 1,
+                self.charts.len(),
+                source
+            );
+        }
+        source.push_str(&format!(", {}ms/candle '+'/'-'", self.interval.as_millis()));
+        source.push_str(if self.paused {
+            ", PAUSED: space resume, 'n' step"
+        } else {
+            ", space pause"
+        });
+        let overlays = if self.overlay {
+            self.overlays()
+        } else {
+            Vec::new()
+        };
+        chart.draw(frame, area, &source, &overlays);
     }
 }
 
 fn main() -> io::Result<()> {
+    let cli = Cli::parse();
+    let mut market = Market::from_cli(&cli)?;
+
     // Terminal setup
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -283,33 +758,33 @@ fn main() -> io::Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Initialize chart
-    let initial_candle = Candle::new(
-        INITIAL_PRICE,
-        INITIAL_PRICE * 1.05,
-        INITIAL_PRICE * 0.98,
-        INITIAL_PRICE * 1.03,
-    );
-    let mut chart = CandlestickChart::new(vec![initial_candle], VISIBLE_RANGE);
-
     // Main loop
     let mut last_update = std::time::Instant::now();
-    let update_interval = Duration::from_millis(UPDATE_INTERVAL_MS);
 
     loop {
-        if last_update.elapsed() >= update_interval {
-            chart.generate_new_candle();
+        if !market.paused && last_update.elapsed() >= market.interval {
+            market.next_candle();
             last_update = std::time::Instant::now();
         }
 
         terminal.draw(|f| {
-            chart.draw(f, f.size());
+            market.draw(f, f.size());
         })?;
 
         if event::poll(Duration::from_millis(100))? {
             if let Event::Key(key) = event::read()? {
-                if key.code == KeyCode::Char('q') {
-                    break;
+                match key.code {
+                    KeyCode::Char('q') => break,
+                    KeyCode::Tab => market.select_next(),
+                    KeyCode::Char('o') => market.toggle_overlay(),
+                    KeyCode::Char(' ') => {
+                        market.toggle_pause();
+                        last_update = std::time::Instant::now();
+                    }
+                    KeyCode::Char('n') => market.step(),
+                    KeyCode::Char('+') | KeyCode::Char('=') => market.faster(),
+                    KeyCode::Char('-') => market.slower(),
+                    _ => {}
                 }
             }
         }
@@ -324,5 +799,5 @@ fn main() -> io::Result<()> {
     )?;
     terminal.show_cursor()?;
 
-    Ok(())
+    market.finish_exports()
 }
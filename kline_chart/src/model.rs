@@ -0,0 +1,163 @@
+//! Price processes the simulator can draw the next close from.
+
+use clap::ValueEnum;
+use rand::{rngs::StdRng, Rng};
+use rand_distr::{Distribution, Normal, Poisson};
+
+/// Which process generates the closes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ModelKind {
+    /// Uniform random percent moves around a drift, an unbounded random walk
+    Random,
+    /// Ornstein–Uhlenbeck: pulled back towards a mean level, range-bound
+    Ou,
+    /// Markov switching between a trending and a choppy regime
+    Regime,
+}
+
+/// Mean-reverting process `dX = speed * (mean - X) dt + volatility dW`,
+/// stepped once per candle.
+#[derive(Debug, Clone, Copy)]
+pub struct OrnsteinUhlenbeck {
+    pub mean: f64,
+    /// Fraction of the distance to the mean closed per candle, 0..1.
+    pub speed: f64,
+    /// Standard deviation of the per-candle shock, in price units.
+    pub volatility: f64,
+}
+
+/// State of the regime-switching model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Regime {
+    Trending,
+    Choppy,
+}
+
+impl Regime {
+    pub fn name(self) -> &'static str {
+        match self {
+            Regime::Trending => "trending",
+            Regime::Choppy => "choppy",
+        }
+    }
+}
+
+/// Drift and volatility of one regime, in percent per candle.
+#[derive(Debug, Clone, Copy)]
+pub struct RegimeParams {
+    pub drift: f64,
+    pub volatility: f64,
+    /// Chance of staying in this regime for the next candle.
+    pub persistence: f64,
+}
+
+/// Two-state Markov regime-switching model: each candle first decides
+/// whether the regime changes, then moves price lognormally with that
+/// regime's drift and volatility.
+#[derive(Debug, Clone, Copy)]
+pub struct RegimeSwitching {
+    pub trending: RegimeParams,
+    pub choppy: RegimeParams,
+    pub regime: Regime,
+}
+
+impl RegimeSwitching {
+    fn params(&self) -> &RegimeParams {
+        match self.regime {
+            Regime::Trending => &self.trending,
+            Regime::Choppy => &self.choppy,
+        }
+    }
+}
+
+/// The configured price process.
+#[derive(Debug, Clone, Copy)]
+pub enum PriceModel {
+    /// Moves uniformly within `drift ± volatility` percent per candle.
+    Random {
+        drift: f64,
+        volatility: f64,
+    },
+    MeanReverting(OrnsteinUhlenbeck),
+    RegimeSwitching(RegimeSwitching),
+}
+
+impl PriceModel {
+    /// Close of the next candle, given the previous close and a standard
+    /// normal `shock`, which is what correlates several simulated assets.
+    pub fn next_close(&mut self, last: f64, shock: f64, rng: &mut StdRng) -> f64 {
+        match self {
+            PriceModel::Random { drift, volatility } => {
+                // The shock mapped onto a uniform draw in -1..1
+                let uniform = 2.0 * normal_cdf(shock) - 1.0;
+                let price_change_percent = *drift + *volatility * uniform;
+                last + last * (price_change_percent / 100.0)
+            }
+            PriceModel::MeanReverting(ou) => {
+                // Prices stay positive however far the shock throws them
+                (last + ou.speed * (ou.mean - last) + ou.volatility * shock).max(0.01)
+            }
+            PriceModel::RegimeSwitching(model) => {
+                if !rng.gen_bool(model.params().persistence.clamp(0.0, 1.0)) {
+                    model.regime = match model.regime {
+                        Regime::Trending => Regime::Choppy,
+                        Regime::Choppy => Regime::Trending,
+                    };
+                }
+                let params = model.params();
+                last * ((params.drift + params.volatility * shock) / 100.0).exp()
+            }
+        }
+    }
+
+    /// The current regime, for models that have one.
+    pub fn regime(&self) -> Option<Regime> {
+        match self {
+            PriceModel::RegimeSwitching(model) => Some(model.regime),
+            _ => None,
+        }
+    }
+}
+
+/// Merton-style jumps: a Poisson number of jumps per candle, each moving
+/// price by a lognormal factor.
+#[derive(Debug, Clone, Copy)]
+pub struct Jumps {
+    poisson: Poisson<f64>,
+    size: Normal<f64>,
+}
+
+impl Jumps {
+    /// `intensity` is the expected number of jumps per candle; the log of
+    /// each jump factor is normal with `mean` and `volatility`.
+    pub fn new(intensity: f64, mean: f64, volatility: f64) -> Result<Self, String> {
+        Ok(Self {
+            poisson: Poisson::new(intensity).map_err(|e| format!("jump intensity: {}", e))?,
+            size: Normal::new(mean, volatility).map_err(|e| format!("jump size: {}", e))?,
+        })
+    }
+
+    /// Combined price factor of this candle's jumps, 1.0 when there are none.
+    pub fn sample_factor(&self, rng: &mut StdRng) -> f64 {
+        let count = self.poisson.sample(rng) as u64;
+        let log_move: f64 = (0..count).map(|_| self.size.sample(rng)).sum();
+        log_move.exp()
+    }
+}
+
+/// Standard normal cumulative distribution, via the Abramowitz and Stegun
+/// 7.1.26 approximation of erf (error below 1.5e-7).
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
@@ -0,0 +1,52 @@
+//! Historical OHLCV candles fed to the chart bar by bar instead of the simulator.
+
+use crate::{source::ChartSource, Candle};
+use kline_core::DataSource;
+use std::{io, path::Path, vec};
+
+/// Candles loaded from a CSV file, handed out one at a time.
+pub struct Replay {
+    name: String,
+    candles: vec::IntoIter<Candle>,
+    total: usize,
+}
+
+impl Replay {
+    /// Loads a CSV with `open,high,low,close` columns; any other columns,
+    /// such as a timestamp or volume, are ignored.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut reader = csv::Reader::from_path(path).map_err(io::Error::other)?;
+        let candles = reader
+            .deserialize()
+            .collect::<Result<Vec<Candle>, _>>()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if candles.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("no candles in {}", path.display()),
+            ));
+        }
+        Ok(Self {
+            name: path.file_name().map_or_else(
+                || path.display().to_string(),
+                |n| n.to_string_lossy().into_owned(),
+            ),
+            total: candles.len(),
+            candles: candles.into_iter(),
+        })
+    }
+}
+
+impl DataSource for Replay {
+    fn next_candle(&mut self, _shock: f64) -> Option<Candle> {
+        self.candles.next()
+    }
+
+    /// The file name and the candles handed out so far and in total.
+    fn describe(&self) -> Option<String> {
+        let played = self.total - self.candles.len();
+        Some(format!("replay {} {}/{}", self.name, played, self.total))
+    }
+}
+
+impl ChartSource for Replay {}
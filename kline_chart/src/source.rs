@@ -0,0 +1,99 @@
+//! Where a chart's candles come from: the simulator, a replayed file or
+//! Bybit.
+
+use crate::{
+    fan::{MonteCarlo, PERCENTILES},
+    model::{Jumps, PriceModel, Regime},
+    Candle, BASE_VOLUME, MIN_PRICE, PRICE_VOLATILITY_FACTOR, VOLUME_NOISE, VOLUME_PER_PERCENT_MOVE,
+};
+use clap::ValueEnum;
+use kline_core::{bybit::BybitSource, DataSource};
+use rand::{rngs::StdRng, Rng};
+use rand_distr::StandardNormal;
+
+/// Where live candles come from when not replaying a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SourceKind {
+    /// Generated by the --model price process
+    Sim,
+    /// Closed candles of --symbol polled from Bybit's public API
+    Bybit,
+}
+
+/// A source as the chart uses it: its candles, plus what only a simulation
+/// can tell.
+pub trait ChartSource: DataSource {
+    /// The current regime, for sources that have one.
+    fn regime(&self) -> Option<Regime> {
+        None
+    }
+
+    /// Percentile bands of `fan`'s paths run forward from `last`, for
+    /// sources that can simulate ahead.
+    fn forecast(&self, _fan: &mut MonteCarlo, _last: f64) -> Vec<[f64; PERCENTILES.len()]> {
+        Vec::new()
+    }
+}
+
+/// Candles generated by a price model, with optional jumps.
+pub struct SimulatedSource {
+    rng: StdRng,
+    model: PriceModel,
+    jumps: Option<Jumps>,
+    last_price: f64,
+}
+
+impl SimulatedSource {
+    /// Starts simulating from `last_price`, the close of the chart's last candle.
+    pub fn new(last_price: f64, rng: StdRng, model: PriceModel, jumps: Option<Jumps>) -> Self {
+        Self {
+            rng,
+            model,
+            jumps,
+            last_price,
+        }
+    }
+}
+
+impl DataSource for SimulatedSource {
+    fn next_candle(&mut self, shock: f64) -> Option<Candle> {
+        // A jump gaps the open away from the previous close
+        let open = match &self.jumps {
+            Some(jumps) => (self.last_price * jumps.sample_factor(&mut self.rng)).max(MIN_PRICE),
+            None => self.last_price,
+        };
+        // A drift bigger than the price would otherwise take it through zero,
+        // and the wick ranges below would be empty
+        let new_price = self
+            .model
+            .next_close(open, shock, &mut self.rng)
+            .max(MIN_PRICE);
+        let rng = &mut self.rng;
+
+        let volatility = open * PRICE_VOLATILITY_FACTOR;
+        let high = open.max(new_price) + rng.gen_range(0.0..volatility);
+        let low = (open.min(new_price) - rng.gen_range(0.0..volatility)).max(MIN_PRICE);
+
+        // Volume grows with the size of the move, gap included
+        let move_percent = ((new_price - self.last_price) / self.last_price).abs() * 100.0;
+        let noise: f64 = rng.sample(StandardNormal);
+        let volume = BASE_VOLUME
+            * (1.0 + VOLUME_PER_PERCENT_MOVE * move_percent)
+            * (VOLUME_NOISE * noise).exp();
+
+        self.last_price = new_price;
+        Some(Candle::new(open, high, low, new_price, volume))
+    }
+}
+
+impl ChartSource for SimulatedSource {
+    fn regime(&self) -> Option<Regime> {
+        self.model.regime()
+    }
+
+    fn forecast(&self, fan: &mut MonteCarlo, last: f64) -> Vec<[f64; PERCENTILES.len()]> {
+        fan.bands(&self.model, self.jumps.as_ref(), last)
+    }
+}
+
+impl ChartSource for BybitSource {}
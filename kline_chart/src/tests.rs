@@ -0,0 +1,227 @@
+//! Golden-frame tests: the chart is rendered into a ratatui `TestBackend`
+//! and the resulting buffer is compared with a frame stored under
+//! `tests/golden`. Run with `UPDATE_GOLDEN=1` to rewrite the stored frames
+//! after an intended rendering change.
+
+use super::*;
+use ratatui::{backend::TestBackend, buffer::Buffer};
+use std::{fs, path::Path};
+
+const WIDTH: u16 = 100;
+const HEIGHT: u16 = 30;
+
+/// Turns a buffer into text: the symbols row by row, then the foreground
+/// colour of every cell as one letter, so a colour change shows up too.
+fn serialize(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut symbols = String::new();
+    let mut colors = String::new();
+    for y in 0..area.height {
+        for x in 0..area.width {
+            let cell = buffer.get(x, y);
+            symbols.push_str(cell.symbol());
+            colors.push(color_code(cell.fg));
+        }
+        symbols.push('\n');
+        colors.push('\n');
+    }
+    format!("{}\n{}", symbols, colors)
+}
+
+fn color_code(color: Color) -> char {
+    match color {
+        Color::Reset => '.',
+        Color::Green => 'g',
+        Color::Red => 'r',
+        Color::Yellow => 'y',
+        Color::White => 'w',
+        Color::Gray => 'a',
+        Color::DarkGray => 'd',
+        Color::Cyan => 'c',
+        Color::Magenta => 'm',
+        Color::LightBlue => 'b',
+        _ => '?',
+    }
+}
+
+/// Compares `buffer` with `tests/golden/<name>.txt`, or writes it there
+/// when `UPDATE_GOLDEN` is set.
+fn assert_golden(name: &str, buffer: &Buffer) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{}.txt", name));
+    let actual = serialize(buffer);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "cannot read {} ({}), run with UPDATE_GOLDEN=1 to create it",
+            path.display(),
+            e
+        )
+    });
+    assert!(
+        expected == actual,
+        "frame differs from {}, rerun with UPDATE_GOLDEN=1 if the change is intended\n\
+         expected:\n{}\nactual:\n{}",
+        path.display(),
+        expected,
+        actual
+    );
+}
+
+fn render(draw: impl FnOnce(&mut Frame)) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap();
+    terminal.draw(draw).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+fn fixed_chart() -> CandlestickChart {
+    let mut chart = CandlestickChart::new(
+        String::from("SIM1"),
+        vec![Candle::new(100.0, 101.0, 99.0, 100.5, 1000.0)],
+        20,
+        Box::new(SimulatedSource::new(
+            100.5,
+            StdRng::seed_from_u64(7),
+            PriceModel::Random {
+                drift: 0.0,
+                volatility: 2.0,
+            },
+            None,
+        )),
+    );
+    let closes = [
+        101.2, 102.0, 101.1, 99.8, 100.4, 102.3, 103.9, 103.1, 104.6, 103.0, 101.7, 102.2,
+    ];
+    let mut open = 100.5;
+    for (i, close) in closes.into_iter().enumerate() {
+        let high = f64::max(open, close) + 0.4;
+        let low = f64::min(open, close) - 0.3;
+        let volume = 800.0 + 150.0 * (i % 4) as f64;
+        chart.push_candle(Candle::new(open, high, low, close, volume));
+        open = close;
+    }
+    chart
+}
+
+#[test]
+fn fixed_candles() {
+    let chart = fixed_chart();
+    let buffer = render(|f| chart.draw(f, f.size(), "golden", &[]));
+    assert_golden("fixed_candles", &buffer);
+}
+
+#[test]
+fn flat_candle_and_overlay() {
+    let mut chart = fixed_chart();
+    chart.push_candle(Candle::new(102.2, 102.5, 101.9, 102.2, 500.0));
+    let overlay = Overlay {
+        name: String::from("SIM2"),
+        closes: chart
+            .visible_candles()
+            .iter()
+            .map(|c| c.close + 1.0)
+            .collect(),
+        color: OVERLAY_COLORS[0],
+    };
+    let buffer = render(|f| chart.draw(f, f.size(), "golden", &[overlay]));
+    assert_golden("flat_candle_and_overlay", &buffer);
+}
+
+#[test]
+fn seeded_market() {
+    let cli = Cli::parse_from(["kline_chart", "--seed", "42", "--visible-range", "30"]);
+    let mut market = Market::from_cli(&cli).unwrap();
+    for _ in 0..40 {
+        market.next_candle();
+    }
+    let buffer = render(|f| market.draw(f, f.size()));
+    assert_golden("seeded_market", &buffer);
+}
+
+#[test]
+fn seeded_regime_market_with_overlay() {
+    let cli = Cli::parse_from([
+        "kline_chart",
+        "--seed",
+        "42",
+        "--assets",
+        "2",
+        "--correlation",
+        "0.8",
+        "--model",
+        "regime",
+        "--show-regime",
+    ]);
+    let mut market = Market::from_cli(&cli).unwrap();
+    for _ in 0..25 {
+        market.next_candle();
+    }
+    market.toggle_overlay();
+    market.toggle_pause();
+    let buffer = render(|f| market.draw(f, f.size()));
+    assert_golden("seeded_regime_market_with_overlay", &buffer);
+}
+
+#[test]
+fn seeded_fan() {
+    let cli = Cli::parse_from([
+        "kline_chart",
+        "--seed",
+        "42",
+        "--visible-range",
+        "30",
+        "--fan",
+        "200",
+        "--fan-horizon",
+        "15",
+    ]);
+    let mut market = Market::from_cli(&cli).unwrap();
+    for _ in 0..30 {
+        market.next_candle();
+    }
+    let buffer = render(|f| market.draw(f, f.size()));
+    assert_golden("seeded_fan", &buffer);
+}
+
+#[test]
+fn cli_rejects_prices_and_volatility_out_of_range() {
+    for args in [
+        ["--initial-price", "0"],
+        ["--initial-price", "-5"],
+        ["--volatility", "0"],
+        ["--volatility", "100"],
+    ] {
+        let cli = Cli::try_parse_from(["kline_chart", args[0], args[1]]);
+        assert!(cli.is_err(), "{:?} should be rejected", args);
+    }
+    assert!(Cli::try_parse_from(["kline_chart", "--volatility", "99.5"]).is_ok());
+}
+
+#[test]
+fn simulated_price_stays_above_zero() {
+    let cli = Cli::parse_from(["kline_chart", "--seed", "42", "--drift", "-150"]);
+    let mut market = Market::from_cli(&cli).unwrap();
+    for _ in 0..5 {
+        market.next_candle();
+    }
+    for candle in &market.charts[0].candles {
+        assert!(candle.low >= MIN_PRICE, "{:?}", candle);
+    }
+}
+
+#[test]
+fn correlation_rejects_non_finite_and_out_of_range_values() {
+    for spec in ["nan", "inf", "1,nan;nan,1"] {
+        let err = correlation::parse_matrix(spec, 2).unwrap_err();
+        assert!(err.contains("not a finite number"), "{}: {}", spec, err);
+    }
+    let matrix = correlation::parse_matrix("1,1.5;1.5,1", 2).unwrap();
+    let err = correlation::CorrelatedShocks::new(&matrix).unwrap_err();
+    assert!(err.contains("outside -1 to 1"), "{}", err);
+    assert!(correlation::CorrelatedShocks::new(&[vec![f64::NAN]]).is_err());
+}
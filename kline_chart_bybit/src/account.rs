@@ -0,0 +1,51 @@
+use crate::{
+    constants::{ACCOUNT_TYPE, EQUITY_CAPACITY, EQUITY_RESOLUTION_MS, EXECUTIONS_CAPACITY},
+    models::WalletData,
+    rest::{RestClient, RestResult},
+    ui::{EquityCurve, ExecutionsPanel, OrdersPanel, PositionsPanel, WalletPanel},
+};
+use chrono::Utc;
+use serde::Deserialize;
+
+/// Private state of one API-key profile, kept apart so switching profiles
+/// never mixes positions or fills between accounts.
+pub struct Account {
+    pub positions: PositionsPanel,
+    pub wallet: WalletPanel,
+    pub executions: ExecutionsPanel,
+    pub orders: OrdersPanel,
+    pub equity: EquityCurve,
+}
+
+impl Account {
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            positions: PositionsPanel::new(),
+            wallet: WalletPanel::new(),
+            executions: ExecutionsPanel::new(symbol, EXECUTIONS_CAPACITY),
+            orders: OrdersPanel::new(symbol),
+            equity: EquityCurve::new(EQUITY_CAPACITY, EQUITY_RESOLUTION_MS),
+        }
+    }
+
+    /// Samples the wallet's current equity into the equity curve.
+    pub fn record_equity(&mut self) {
+        if let Some(balance) = self.wallet.balance() {
+            let equity = balance.total_equity;
+            self.equity.record(Utc::now().timestamp_millis(), equity);
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WalletBalanceResponse {
+    list: Vec<WalletData>,
+}
+
+/// Current balances, used to fill the wallet panel before the first push on
+/// the private `wallet` topic.
+pub async fn fetch_wallet_balance(client: &RestClient) -> RestResult<Vec<WalletData>> {
+    let query = format!("accountType={}", ACCOUNT_TYPE);
+    let response: WalletBalanceResponse = client.get("/v5/account/wallet-balance", &query).await?;
+    Ok(response.list)
+}
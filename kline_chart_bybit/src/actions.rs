@@ -0,0 +1,149 @@
+use crate::{
+    account::fetch_wallet_balance,
+    margin::{set_leverage, switch_margin_mode},
+    models::{MarginMode, OrderData, WalletData},
+    orders::{
+        amend_order, cancel_all_orders, cancel_order, fetch_open_orders, place_order, AmendRequest,
+        OrderRequest,
+    },
+    rest::RestClient,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Work requested by the UI that has to go over the network.
+#[derive(Debug)]
+pub enum Action {
+    PlaceOrder(OrderRequest),
+    FetchWallet,
+    /// Cancels every open order in the symbol.
+    CancelAll(String),
+    AmendOrder(AmendRequest),
+    CancelOrder {
+        symbol: String,
+        order_id: String,
+    },
+    FetchOpenOrders(String),
+    /// Sets leverage, switching the margin mode first when `switch_mode` is set.
+    SetLeverage {
+        symbol: String,
+        leverage: f64,
+        margin_mode: MarginMode,
+        switch_mode: bool,
+    },
+}
+
+/// Outcome of an [`Action`], reported back to the UI.
+#[derive(Debug)]
+pub enum ActionResult {
+    OrderPlaced {
+        description: String,
+        order_id: String,
+    },
+    Wallet(Vec<WalletData>),
+    OrdersCancelled {
+        symbol: String,
+        count: usize,
+    },
+    OrderAmended {
+        description: String,
+    },
+    OrderCancelled {
+        order_id: String,
+    },
+    OpenOrders(Vec<OrderData>),
+    LeverageSet {
+        symbol: String,
+        leverage: f64,
+        margin_mode: MarginMode,
+    },
+    Failed {
+        description: String,
+        error: String,
+    },
+}
+
+/// Runs `action` on the runtime and sends its result to `results`.
+pub fn spawn_action(client: RestClient, action: Action, results: UnboundedSender<ActionResult>) {
+    tokio::spawn(async move {
+        let result = match action {
+            Action::PlaceOrder(order) => {
+                let description = order.describe();
+                match place_order(&client, &order).await {
+                    Ok(order_id) => ActionResult::OrderPlaced {
+                        description,
+                        order_id,
+                    },
+                    Err(e) => ActionResult::Failed {
+                        description,
+                        error: e.to_string(),
+                    },
+                }
+            }
+            Action::FetchWallet => match fetch_wallet_balance(&client).await {
+                Ok(wallets) => ActionResult::Wallet(wallets),
+                Err(e) => ActionResult::Failed {
+                    description: String::from("wallet balance"),
+                    error: e.to_string(),
+                },
+            },
+            Action::CancelAll(symbol) => match cancel_all_orders(&client, &symbol).await {
+                Ok(count) => ActionResult::OrdersCancelled { symbol, count },
+                Err(e) => ActionResult::Failed {
+                    description: format!("cancel all {}", symbol),
+                    error: e.to_string(),
+                },
+            },
+            Action::AmendOrder(request) => {
+                let description = request.describe();
+                match amend_order(&client, &request).await {
+                    Ok(_) => ActionResult::OrderAmended { description },
+                    Err(e) => ActionResult::Failed {
+                        description,
+                        error: e.to_string(),
+                    },
+                }
+            }
+            Action::CancelOrder { symbol, order_id } => {
+                match cancel_order(&client, &symbol, &order_id).await {
+                    Ok(order_id) => ActionResult::OrderCancelled { order_id },
+                    Err(e) => ActionResult::Failed {
+                        description: format!("cancel {}", order_id),
+                        error: e.to_string(),
+                    },
+                }
+            }
+            Action::FetchOpenOrders(symbol) => match fetch_open_orders(&client, &symbol).await {
+                Ok(orders) => ActionResult::OpenOrders(orders),
+                Err(e) => ActionResult::Failed {
+                    description: format!("open orders {}", symbol),
+                    error: e.to_string(),
+                },
+            },
+            Action::SetLeverage {
+                symbol,
+                leverage,
+                margin_mode,
+                switch_mode,
+            } => {
+                let result = if switch_mode {
+                    switch_margin_mode(&client, &symbol, margin_mode, leverage).await
+                } else {
+                    set_leverage(&client, &symbol, leverage).await
+                };
+                match result {
+                    Ok(()) => ActionResult::LeverageSet {
+                        symbol,
+                        leverage,
+                        margin_mode,
+                    },
+                    Err(e) => ActionResult::Failed {
+                        description: format!("leverage {}x {}", leverage, symbol),
+                        error: e.to_string(),
+                    },
+                }
+            }
+        };
+        // The receiver only goes away when the app is shutting down
+        let _ = results.send(result);
+    });
+}
@@ -0,0 +1,129 @@
+use crate::orders::OrderRequest;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Notification service a fired alert can be delivered to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Channel {
+    Telegram,
+    Discord,
+    Email,
+    Webhook,
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Channel::Telegram => "telegram",
+            Channel::Discord => "discord",
+            Channel::Email => "email",
+            Channel::Webhook => "webhook",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertDirection {
+    /// Fires once price trades at or above the level.
+    Above,
+    /// Fires once price trades at or below the level.
+    Below,
+}
+
+#[derive(Debug, Clone)]
+pub struct PriceAlert {
+    pub id: u64,
+    pub price: f64,
+    pub direction: AlertDirection,
+    /// Order offered for confirmation when the alert fires; never sent on its own.
+    pub order: Option<OrderRequest>,
+    /// Where the alert is delivered besides the status line.
+    pub channels: Vec<Channel>,
+}
+
+impl PriceAlert {
+    /// The condition in words, e.g. `price above 3200.00`.
+    pub fn rule(&self) -> String {
+        let direction = match self.direction {
+            AlertDirection::Above => "above",
+            AlertDirection::Below => "below",
+        };
+        format!("price {} {:.2}", direction, self.price)
+    }
+
+    fn is_hit(&self, price: f64) -> bool {
+        match self.direction {
+            AlertDirection::Above => price >= self.price,
+            AlertDirection::Below => price <= self.price,
+        }
+    }
+}
+
+/// One-shot price alerts, checked against every price update.
+#[derive(Debug, Default)]
+pub struct AlertEngine {
+    alerts: Vec<PriceAlert>,
+    next_id: u64,
+    last_price: Option<f64>,
+    /// Channels given to new alerts.
+    channels: Vec<Channel>,
+}
+
+impl AlertEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an alert at `price`; the direction is inferred from the last seen price.
+    pub fn add_price_alert(&mut self, price: f64) -> &PriceAlert {
+        self.add_alert(price, None)
+    }
+
+    /// Like [`add_price_alert`](Self::add_price_alert), with an order to
+    /// propose once price gets there.
+    pub fn add_order_alert(&mut self, price: f64, order: OrderRequest) -> &PriceAlert {
+        self.add_alert(price, Some(order))
+    }
+
+    fn add_alert(&mut self, price: f64, order: Option<OrderRequest>) -> &PriceAlert {
+        let direction = match self.last_price {
+            Some(last) if price < last => AlertDirection::Below,
+            _ => AlertDirection::Above,
+        };
+        self.next_id += 1;
+        self.alerts.push(PriceAlert {
+            id: self.next_id,
+            price,
+            direction,
+            order,
+            channels: self.channels.clone(),
+        });
+        &self.alerts[self.alerts.len() - 1]
+    }
+
+    /// Feeds a new price and returns every alert it triggered.
+    pub fn on_price(&mut self, price: f64) -> Vec<PriceAlert> {
+        self.last_price = Some(price);
+        let (triggered, pending) = self.alerts.drain(..).partition(|alert| alert.is_hit(price));
+        self.alerts = pending;
+        triggered
+    }
+
+    pub fn alerts(&self) -> &[PriceAlert] {
+        &self.alerts
+    }
+
+    pub fn channels(&self) -> &[Channel] {
+        &self.channels
+    }
+
+    /// Routes alerts registered from now on to `channels`.
+    pub fn set_channels(&mut self, channels: Vec<Channel>) {
+        self.channels = channels;
+    }
+
+    pub fn clear(&mut self) {
+        self.alerts.clear();
+    }
+}
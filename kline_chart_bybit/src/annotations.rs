@@ -0,0 +1,46 @@
+use crate::paths::data_dir;
+use serde::Deserialize;
+use std::{fs, io, path::PathBuf};
+
+/// A note pinned to a point on the chart, e.g. a news event or a trade entry.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Annotation {
+    /// Milliseconds since the epoch, same as candle start times.
+    pub timestamp: i64,
+    pub price: f64,
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnnotationFile {
+    #[serde(default, alias = "annotation")]
+    annotations: Vec<Annotation>,
+}
+
+fn annotations_dir() -> PathBuf {
+    data_dir().join("annotations")
+}
+
+/// Loads `<symbol>.json` or `<symbol>.toml` from the annotations directory.
+///
+/// A missing file is not an error and yields no annotations.
+pub fn load_annotations(symbol: &str) -> io::Result<Vec<Annotation>> {
+    let dir = annotations_dir();
+
+    let json_path = dir.join(format!("{symbol}.json"));
+    if json_path.exists() {
+        let text = fs::read_to_string(json_path)?;
+        let file: AnnotationFile = serde_json::from_str(&text)?;
+        return Ok(file.annotations);
+    }
+
+    let toml_path = dir.join(format!("{symbol}.toml"));
+    if toml_path.exists() {
+        let text = fs::read_to_string(toml_path)?;
+        let file: AnnotationFile =
+            toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        return Ok(file.annotations);
+    }
+
+    Ok(Vec::new())
+}
@@ -0,0 +1,111 @@
+use crate::{indicators::sma, metrics::METRICS, models::Candle};
+use axum::{extract::State, routing::get, Json, Router};
+use serde::Serialize;
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{Arc, RwLock},
+};
+use tokio::{net::TcpListener, sync::mpsc::UnboundedSender};
+
+/// What the TUI currently shows, copied out of the app for `--serve`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ChartSnapshot {
+    pub symbol: String,
+    pub interval: String,
+    pub candles: Vec<Candle>,
+    pub ma_window: usize,
+    pub last_price: Option<f64>,
+    pub status: ConnectionStatus,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConnectionStatus {
+    /// Whether market data is flowing, live or replayed.
+    pub public: bool,
+    /// Whether the private stream is connected; false when paper trading.
+    pub private: bool,
+    pub paper: bool,
+    pub profile: Option<String>,
+    /// The status line of the TUI.
+    pub message: String,
+    /// When the snapshot was taken, in milliseconds since the epoch.
+    pub updated: i64,
+}
+
+/// Snapshot shared between the event loop, which writes it, and the server.
+pub type SharedSnapshot = Arc<RwLock<ChartSnapshot>>;
+
+#[derive(Debug, Serialize)]
+struct IndicatorPoint {
+    start: i64,
+    value: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+struct Indicators {
+    ma_window: usize,
+    ma: Vec<IndicatorPoint>,
+}
+
+async fn candles(State(snapshot): State<SharedSnapshot>) -> Json<Vec<Candle>> {
+    Json(read(&snapshot).candles)
+}
+
+async fn indicators(State(snapshot): State<SharedSnapshot>) -> Json<Indicators> {
+    let snapshot = read(&snapshot);
+    let closes: Vec<f64> = snapshot.candles.iter().map(|c| c.close).collect();
+    let ma = snapshot
+        .candles
+        .iter()
+        .zip(sma(&closes, snapshot.ma_window))
+        .map(|(candle, value)| IndicatorPoint {
+            start: candle.start,
+            value,
+        })
+        .collect();
+    Json(Indicators {
+        ma_window: snapshot.ma_window,
+        ma,
+    })
+}
+
+async fn metrics() -> String {
+    METRICS.render()
+}
+
+async fn status(State(snapshot): State<SharedSnapshot>) -> Json<ChartSnapshot> {
+    let mut snapshot = read(&snapshot);
+    snapshot.candles.clear();
+    Json(snapshot)
+}
+
+fn read(snapshot: &SharedSnapshot) -> ChartSnapshot {
+    snapshot
+        .read()
+        .map(|s| s.clone())
+        .unwrap_or_else(|poisoned| poisoned.into_inner().clone())
+}
+
+/// Binds `addr` and serves `GET /candles`, `/indicators` and `/status` as JSON,
+/// and `/metrics` for Prometheus, in the background. If the server stops, the
+/// reason goes to `errors` for the status line.
+pub async fn serve(
+    addr: SocketAddr,
+    snapshot: SharedSnapshot,
+    errors: UnboundedSender<String>,
+) -> io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    let router = Router::new()
+        .route("/candles", get(candles))
+        .route("/indicators", get(indicators))
+        .route("/status", get(status))
+        .route("/metrics", get(metrics))
+        .with_state(snapshot);
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, router).await {
+            let _ = errors.send(format!("HTTP API stopped: {e}"));
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,1887 @@
+use crate::{
+    account::Account,
+    actions::{Action, ActionResult},
+    alerts::{AlertDirection, AlertEngine, Channel},
+    annotations::{load_annotations, Annotation},
+    api::{ChartSnapshot, ConnectionStatus, SharedSnapshot},
+    config::{ChartConfig, ChartKey, HotkeysConfig, KeysConfig},
+    constants::{FIB_RATIOS, SIGNALS_CAPACITY, TICK_SIZE, TRADES_CAPACITY},
+    drawings::{Drawings, FibRetracement, TrendLine},
+    export::{write_candles, write_indicators, write_trades, DataFormat},
+    fees::FeeModel,
+    grpc::{to_proto, CandleSender},
+    indicators::ma_series,
+    instrument::Instrument,
+    journal::Journal,
+    metrics::{Metrics, METRICS},
+    models::{
+        Candle, Execution, ExecutionData, KlineResponse, OpResponse, OpenOrder, OrderData,
+        OrderbookResponse, PositionData, PrivateResponse, SessionLevels, Side, SpreadSeries,
+        TopicEnvelope, Trade, TradeResponse, WalletData,
+    },
+    orders::{parse_alert_order, parse_amend, parse_order_command, AmendRequest, OrderRequest},
+    paper::PaperBroker,
+    replay::Recorder,
+    session::{Indicators, Panels, SessionState},
+    sinks::{SinkEvent, Sinks},
+    sizing::{size_position, PositionSize},
+    store::CandleStore,
+    strategy::{live::orders_for, LiveMode, PositionCaps, Signal, SignalEvent, Strategy},
+    ui::{
+        draw_confirm, CandlestickChart, DomLadder, LeverageDialog, Marker, PriceLevel, TimeLine,
+        TradesTape,
+    },
+};
+use chrono::Utc;
+use crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::Paragraph,
+    Frame,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    path::PathBuf,
+    sync::atomic::Ordering,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mode {
+    Normal,
+    /// Clicks add/select horizontal levels instead of alerts.
+    Draw,
+    /// Typing a new name for the selected level.
+    Rename(String),
+    /// Typing an order command, see [`parse_order_command`].
+    OrderEntry(String),
+    /// Waiting for `y` before the order is sent.
+    ConfirmOrder(OrderRequest),
+    /// Typing risk % and stop for the position-size calculator, see [`size_position`].
+    Sizing(String),
+    /// Typing the new quantity and/or price of an open order, see [`parse_amend`].
+    AmendOrder {
+        order_id: String,
+        buffer: String,
+    },
+    /// The leverage and margin mode dialog is open.
+    Leverage(LeverageDialog),
+    /// Typing an alert with an attached order, see [`parse_alert_order`].
+    AlertOrderEntry(String),
+    /// An alert fired and its order waits for `y` in a popup.
+    ConfirmAlertOrder {
+        alert: String,
+        order: OrderRequest,
+    },
+}
+
+/// Holds every widget on screen and routes websocket frames to them.
+pub struct App {
+    /// Symbol and kline interval of the chart.
+    symbol: String,
+    interval: String,
+    pub chart: CandlestickChart,
+    pub trades: TradesTape,
+    pub ladder: DomLadder,
+    pub alerts: AlertEngine,
+    pub drawings: Drawings,
+    pub annotations: Vec<Annotation>,
+    pub session: SessionLevels,
+    /// Positions, balances and fills of the active profile.
+    pub account: Account,
+    /// When set, the chart shows this synthetic series instead of the main symbol.
+    spread: Option<SpreadSeries>,
+    mode: Mode,
+    selected_level: Option<usize>,
+    /// First anchor of a trendline that is being placed.
+    pending_trendline: Option<(i64, f64)>,
+    /// Swing price picked as the start of a Fibonacci retracement.
+    pending_fib: Option<f64>,
+    status: String,
+    /// Network work queued by the UI, drained by the event loop.
+    actions: Vec<Action>,
+    /// Orders of fired alerts still waiting to be confirmed, oldest first.
+    alert_orders: VecDeque<(String, OrderRequest)>,
+    /// Whether API credentials are configured and orders can be sent.
+    trading_enabled: bool,
+    hotkeys: HotkeysConfig,
+    hotkeys_armed: bool,
+    keys: KeysConfig,
+    /// Fed every confirmed candle of the chart symbol.
+    strategy: Option<Box<dyn Strategy>>,
+    strategy_mode: LiveMode,
+    strategy_caps: PositionCaps,
+    signals: VecDeque<SignalEvent>,
+    /// Contract specs of the chart symbol, for position sizing.
+    instrument: Instrument,
+    /// When set, confirmed orders fill against this simulator instead of the exchange.
+    paper: Option<PaperBroker>,
+    /// Round trips of our fills, paired up from this session's executions.
+    journal: Option<Journal>,
+    export_dir: PathBuf,
+    export_format: DataFormat,
+    /// Confirmed candles of the chart are saved here.
+    store: Option<CandleStore>,
+    recorder: Option<Recorder>,
+    /// Published to the HTTP API on every tick.
+    api: Option<SharedSnapshot>,
+    /// Confirmed candles go out to gRPC subscribers here.
+    candle_feed: Option<CandleSender>,
+    /// External services told about candles, trades and alerts.
+    sinks: Sinks,
+    profiles: Vec<String>,
+    /// Channel sets 'N' cycles new alerts through.
+    alert_routes: Vec<Vec<Channel>>,
+    profile: Option<String>,
+    /// Accounts of the inactive profiles, by name.
+    inactive_accounts: HashMap<String, Account>,
+    /// Profile the event loop should reconnect to.
+    pending_profile: Option<String>,
+    last_trade_price: Option<f64>,
+    show_trades: bool,
+    show_ladder: bool,
+    show_positions: bool,
+    show_wallet: bool,
+    show_executions: bool,
+    show_orders: bool,
+    show_equity: bool,
+    chart_area: Rect,
+    mouse_position: Option<(u16, u16)>,
+}
+
+impl App {
+    pub fn new(settings: &ChartConfig) -> Self {
+        let mut chart = CandlestickChart::new(settings.visible_range);
+        chart.set_ma_window(settings.ma_window);
+        let mut app = Self {
+            symbol: settings.symbol.clone(),
+            interval: settings.interval.clone(),
+            chart,
+            trades: TradesTape::new(TRADES_CAPACITY),
+            ladder: DomLadder::new(TICK_SIZE),
+            alerts: AlertEngine::new(),
+            drawings: Drawings::load(&settings.symbol),
+            annotations: Vec::new(),
+            session: SessionLevels::new(),
+            account: Account::new(&settings.symbol),
+            spread: None,
+            mode: Mode::Normal,
+            selected_level: None,
+            pending_trendline: None,
+            pending_fib: None,
+            status: String::from("Click the chart or press 'a' to place an alert"),
+            actions: Vec::new(),
+            alert_orders: VecDeque::new(),
+            trading_enabled: false,
+            hotkeys: HotkeysConfig::default(),
+            hotkeys_armed: false,
+            keys: KeysConfig::default(),
+            strategy: None,
+            strategy_mode: LiveMode::DryRun,
+            strategy_caps: PositionCaps {
+                qty: 0.0,
+                max_position: 0.0,
+            },
+            signals: VecDeque::new(),
+            instrument: Instrument::fallback(&settings.symbol),
+            paper: None,
+            journal: None,
+            export_dir: PathBuf::from("."),
+            export_format: DataFormat::Csv,
+            store: None,
+            recorder: None,
+            api: None,
+            candle_feed: None,
+            sinks: Sinks::new(),
+            profiles: Vec::new(),
+            alert_routes: Vec::new(),
+            profile: None,
+            inactive_accounts: HashMap::new(),
+            pending_profile: None,
+            last_trade_price: None,
+            show_trades: true,
+            show_ladder: false,
+            show_positions: false,
+            show_wallet: false,
+            show_executions: false,
+            show_orders: false,
+            show_equity: false,
+            chart_area: Rect::default(),
+            mouse_position: None,
+        };
+        app.reload_annotations();
+        app
+    }
+
+    pub fn set_keys(&mut self, keys: KeysConfig) {
+        self.keys = keys;
+    }
+
+    pub fn set_status(&mut self, status: String) {
+        self.status = status;
+    }
+
+    pub fn set_trading_enabled(&mut self, enabled: bool) {
+        self.trading_enabled = enabled;
+        self.show_positions = enabled;
+        self.show_wallet = enabled;
+        self.show_executions = enabled;
+        self.show_orders = enabled;
+        if enabled {
+            // The wallet and order topics only push on changes, so seed them over REST
+            self.actions.push(Action::FetchWallet);
+            self.actions
+                .push(Action::FetchOpenOrders(self.symbol.clone()));
+        }
+    }
+
+    pub fn set_instrument(&mut self, instrument: Instrument) {
+        self.instrument = instrument;
+    }
+
+    /// Switches to paper trading with a virtual balance of `balance`.
+    pub fn set_paper_trading(&mut self, balance: f64, fees: FeeModel) {
+        self.paper = Some(PaperBroker::new(balance, fees, self.instrument.tick_size));
+        self.trading_enabled = true;
+        self.show_positions = true;
+        self.show_wallet = true;
+        self.show_executions = true;
+        self.show_orders = true;
+        self.show_equity = true;
+        self.sync_paper_account();
+    }
+
+    /// Copies the simulator's state into the account panels.
+    fn sync_paper_account(&mut self) {
+        if let Some(paper) = &self.paper {
+            self.account.positions.set_positions(paper.positions());
+            self.account.wallet.set_balance(paper.wallet());
+            self.account.orders.set_orders(paper.orders(&self.symbol));
+            self.account.record_equity();
+        }
+    }
+
+    fn on_paper_fills(&mut self, fills: Vec<Execution>) {
+        for fill in fills {
+            let side = match fill.side {
+                Side::Buy => "BUY",
+                Side::Sell => "SELL",
+            };
+            self.status = format!("Paper fill: {} {} @ {:.2}", side, fill.qty, fill.price);
+            self.journal_fill(&fill);
+            self.account.executions.push(fill);
+        }
+        self.sync_paper_account();
+    }
+
+    /// The UI state worth restoring next time.
+    pub fn session_state(&self) -> SessionState {
+        SessionState {
+            symbol: self.symbol.clone(),
+            interval: self.interval.clone(),
+            zoom: Some(self.chart.zoom()),
+            indicators: Indicators {
+                ma50: self.chart.show_ma(),
+            },
+            panels: Panels {
+                trades: self.show_trades,
+                ladder: self.show_ladder,
+                positions: self.show_positions,
+                wallet: self.show_wallet,
+                executions: self.show_executions,
+                orders: self.show_orders,
+                equity: self.show_equity,
+            },
+        }
+    }
+
+    /// Applies a saved session, unless it was for another chart.
+    pub fn restore_session(&mut self, state: &SessionState) {
+        if state.symbol != self.symbol || state.interval != self.interval {
+            return;
+        }
+        if let Some(zoom) = state.zoom {
+            self.chart.set_zoom(zoom);
+        }
+        self.chart.set_show_ma(state.indicators.ma50);
+        self.show_trades = state.panels.trades;
+        self.show_ladder = state.panels.ladder;
+        self.show_positions = state.panels.positions;
+        self.show_wallet = state.panels.wallet;
+        self.show_executions = state.panels.executions;
+        self.show_orders = state.panels.orders;
+        self.show_equity = state.panels.equity;
+    }
+
+    pub fn set_candle_feed(&mut self, feed: CandleSender) {
+        self.candle_feed = Some(feed);
+    }
+
+    pub fn sinks(&mut self) -> &mut Sinks {
+        &mut self.sinks
+    }
+
+    pub fn set_api(&mut self, api: SharedSnapshot) {
+        self.api = Some(api);
+    }
+
+    /// Copies the chart state to the HTTP API, if it is being served.
+    pub fn publish_snapshot(&self, public_connected: bool, private_connected: bool) {
+        let Some(api) = &self.api else {
+            return;
+        };
+        let snapshot = ChartSnapshot {
+            symbol: self.symbol.clone(),
+            interval: self.interval.clone(),
+            candles: self.chart.candles().to_vec(),
+            ma_window: self.chart.ma_window(),
+            last_price: self.last_trade_price.or_else(|| self.chart.last_price()),
+            status: ConnectionStatus {
+                public: public_connected,
+                private: private_connected,
+                paper: self.paper.is_some(),
+                profile: self.profile.clone(),
+                message: self.status.clone(),
+                updated: Utc::now().timestamp_millis(),
+            },
+        };
+        if let Ok(mut shared) = api.write() {
+            *shared = snapshot;
+        }
+    }
+
+    /// Appends every inbound frame to `recorder` from now on.
+    pub fn set_recorder(&mut self, recorder: Recorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Writes a raw frame to the recording, stopping it on the first error.
+    fn record(&mut self, stream: &str, text: &str) {
+        let Some(recorder) = self.recorder.as_mut() else {
+            return;
+        };
+        if let Err(e) = recorder.record(stream, text) {
+            self.recorder = None;
+            self.status = format!("Recording stopped: {}", e);
+        }
+    }
+
+    /// Persists confirmed candles to `store` from now on, after seeding the
+    /// chart with what it already holds.
+    pub fn set_store(&mut self, store: CandleStore) {
+        match store.latest(&self.symbol, &self.interval, self.chart.capacity()) {
+            Ok(candles) => {
+                for candle in candles {
+                    self.chart.upsert_candle(candle);
+                }
+            }
+            Err(e) => self.status = format!("Failed to load stored candles: {}", e),
+        }
+        self.store = Some(store);
+    }
+
+    /// Saves a confirmed candle and hands it to the gRPC subscribers.
+    fn store_candle(&mut self, candle: &Candle) {
+        if let Some(store) = &self.store {
+            if let Err(e) = store.insert(&self.symbol, &self.interval, candle) {
+                self.status = format!("Failed to store candle: {}", e);
+            }
+        }
+        if let Some(feed) = &self.candle_feed {
+            // No receivers just means nobody is subscribed right now
+            let _ = feed.send(to_proto(&self.symbol, &self.interval, candle));
+        }
+        self.sinks.publish(SinkEvent::Candle {
+            symbol: self.symbol.clone(),
+            interval: self.interval.clone(),
+            candle: candle.clone(),
+        });
+    }
+
+    pub fn set_export_dir(&mut self, dir: PathBuf, format: DataFormat) {
+        self.export_dir = dir;
+        self.export_format = format;
+    }
+
+    /// Writes the chart's candles, its enabled indicators and the trades tape
+    /// to timestamped files in the export directory.
+    fn export_market_data(&mut self) {
+        let candles = self.chart.candles();
+        let (Some(first), Some(last)) = (candles.first(), candles.last()) else {
+            self.status = String::from("No candles to export yet");
+            return;
+        };
+        let extension = self.export_format.extension();
+        let candles_path = self.export_dir.join(format!(
+            "{}-{}-{}-{}.{}",
+            self.symbol, self.interval, first.start, last.start, extension
+        ));
+        let mut trades: Vec<Trade> = self.trades.trades().cloned().collect();
+        trades.reverse();
+        let trades_path = self.export_dir.join(format!(
+            "{}-trades-{}.{}",
+            self.symbol, last.start, extension
+        ));
+        let mut series = Vec::new();
+        if self.chart.show_ma() {
+            series.push(ma_series(candles, self.chart.ma_window()));
+        }
+        let indicators_path = self.export_dir.join(format!(
+            "{}-{}-{}-{}-indicators.{}",
+            self.symbol, self.interval, first.start, last.start, extension
+        ));
+        let result = write_candles(&candles_path, candles, self.export_format)
+            .and_then(|()| write_trades(&trades_path, &trades, self.export_format))
+            .and_then(|()| {
+                if series.is_empty() {
+                    Ok(())
+                } else {
+                    write_indicators(&indicators_path, candles, &series, self.export_format)
+                }
+            });
+        self.status = match result {
+            Ok(()) => format!(
+                "{} candles and {} trades exported to {}",
+                candles.len(),
+                trades.len(),
+                self.export_dir.display()
+            ),
+            Err(e) => format!("Export failed: {}", e),
+        };
+    }
+
+    pub fn set_journal(&mut self, journal: Journal) {
+        self.journal = Some(journal);
+    }
+
+    fn journal_fill(&mut self, fill: &Execution) {
+        let Some(journal) = &mut self.journal else {
+            return;
+        };
+        let strategy = self.strategy.as_ref().map_or("manual", |s| s.name());
+        match journal.record_fill(fill, strategy) {
+            Ok(Some(entry)) => {
+                self.status = format!("Trade closed: {:+.2} USDT, journaled", entry.pnl);
+            }
+            Ok(None) => {}
+            Err(e) => self.status = format!("Journal write failed: {}", e),
+        }
+    }
+
+    /// Channel sets 'N' cycles through; status-line-only is always offered
+    /// first, and new alerts start on the route after it.
+    pub fn set_alert_routes(&mut self, routes: Vec<Vec<Channel>>) {
+        self.alert_routes = vec![Vec::new()];
+        self.alert_routes
+            .extend(routes.into_iter().filter(|route| !route.is_empty()));
+        let start = self.alert_routes.get(1).cloned().unwrap_or_default();
+        self.alerts.set_channels(start);
+    }
+
+    fn cycle_alert_route(&mut self) {
+        if self.alert_routes.len() < 2 {
+            self.status = String::from("No alert channels configured");
+            return;
+        }
+        let current = self
+            .alert_routes
+            .iter()
+            .position(|route| route.as_slice() == self.alerts.channels())
+            .unwrap_or(0);
+        let next = self.alert_routes[(current + 1) % self.alert_routes.len()].clone();
+        self.status = format!("New alerts notify: {}", describe_channels(&next));
+        self.alerts.set_channels(next);
+    }
+
+    /// Profiles available to the runtime switcher and the one connected at startup.
+    pub fn set_profiles(&mut self, profiles: Vec<String>, active: Option<String>) {
+        self.profiles = profiles;
+        self.profile = active;
+    }
+
+    /// The active API-key profile, if any.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Takes the profile the user switched to, for the event loop to reconnect.
+    pub fn take_profile_switch(&mut self) -> Option<String> {
+        self.pending_profile.take()
+    }
+
+    fn switch_to_next_profile(&mut self) {
+        let Some(current) = self.profile.clone() else {
+            self.status = String::from("No API profiles configured");
+            return;
+        };
+        if self.profiles.len() < 2 {
+            self.status = format!("Only profile '{}' is configured", current);
+            return;
+        }
+        let index = self
+            .profiles
+            .iter()
+            .position(|p| *p == current)
+            .unwrap_or(0);
+        let next = self.profiles[(index + 1) % self.profiles.len()].clone();
+
+        let account = self
+            .inactive_accounts
+            .remove(&next)
+            .unwrap_or_else(|| Account::new(&self.symbol));
+        let previous = std::mem::replace(&mut self.account, account);
+        self.inactive_accounts.insert(current, previous);
+        self.status = format!("Switching to profile '{}'...", next);
+        self.profile = Some(next.clone());
+        self.pending_profile = Some(next);
+        self.actions.push(Action::FetchWallet);
+        self.actions
+            .push(Action::FetchOpenOrders(self.symbol.clone()));
+    }
+
+    /// Takes the queued network actions for the event loop to run.
+    pub fn take_actions(&mut self) -> Vec<Action> {
+        std::mem::take(&mut self.actions)
+    }
+
+    pub fn handle_action_result(&mut self, result: ActionResult) {
+        match result {
+            ActionResult::OrderPlaced {
+                description,
+                order_id,
+            } => self.status = format!("Order placed: {} (id {})", description, order_id),
+            ActionResult::OrdersCancelled { symbol, count } => {
+                self.status = format!("Cancelled {} orders in {}", count, symbol)
+            }
+            ActionResult::OrderAmended { description } => {
+                self.status = format!("Order amended: {}", description)
+            }
+            ActionResult::OrderCancelled { order_id } => {
+                self.status = format!("Order {} cancelled", order_id)
+            }
+            ActionResult::LeverageSet {
+                symbol,
+                leverage,
+                margin_mode,
+            } => {
+                self.status = format!(
+                    "{} leverage {}x, {} margin",
+                    symbol,
+                    leverage,
+                    margin_mode.label()
+                )
+            }
+            ActionResult::OpenOrders(orders) => self.account.orders.set_orders(
+                orders
+                    .iter()
+                    .filter_map(OpenOrder::from_order_data)
+                    .collect(),
+            ),
+            ActionResult::Wallet(wallets) => {
+                for wallet in &wallets {
+                    self.account.wallet.update_from_wallet(wallet);
+                }
+                self.account.record_equity();
+            }
+            ActionResult::Failed { description, error } => {
+                self.status = format!("FAILED: {}: {}", description, error)
+            }
+        }
+    }
+
+    /// Latest traded price, falling back to the last candle close.
+    fn reference_price(&self) -> Option<f64> {
+        self.last_trade_price.or_else(|| self.chart.last_price())
+    }
+
+    fn start_order_entry(&mut self, prefill: String) {
+        if !self.trading_enabled {
+            self.status = String::from("Trading needs API credentials in the config file");
+            return;
+        }
+        self.status = format!("Order: {}_", prefill);
+        self.mode = Mode::OrderEntry(prefill);
+    }
+
+    fn handle_order_entry_key(&mut self, code: KeyCode, mut buffer: String) {
+        match code {
+            KeyCode::Enter => {
+                match parse_order_command(&buffer, &self.symbol, self.reference_price()) {
+                    Ok(order) => {
+                        self.status = format!("Send {}? (y/n)", order.describe());
+                        self.mode = Mode::ConfirmOrder(order);
+                    }
+                    Err(e) => {
+                        self.status = format!("Order: {}_  ({})", buffer, e);
+                        self.mode = Mode::OrderEntry(buffer);
+                    }
+                }
+            }
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.status = String::from("Order cancelled");
+            }
+            _ => {
+                edit_text(&mut buffer, code);
+                self.status = format!("Order: {}_", buffer);
+                self.mode = Mode::OrderEntry(buffer);
+            }
+        }
+    }
+
+    fn handle_confirm_order_key(&mut self, code: KeyCode, order: OrderRequest) {
+        self.mode = Mode::Normal;
+        if code == KeyCode::Char('y') {
+            self.submit_order(order);
+        } else {
+            self.status = String::from("Order cancelled");
+        }
+    }
+
+    /// Sends a confirmed order to the exchange, or the simulator when paper trading.
+    fn submit_order(&mut self, order: OrderRequest) {
+        // Price may have moved since the order was typed, e.g. until its alert
+        // fired, so a stop is aimed at where price is now
+        let last_price = self.reference_price();
+        let order = match last_price {
+            Some(price) => order.with_trigger_direction_at(price),
+            None => order,
+        };
+        if let Some(paper) = self.paper.as_mut() {
+            let Some(last_price) = last_price else {
+                self.status = String::from("No price yet to fill paper orders against");
+                return;
+            };
+            let (order_id, fills) = paper.submit(&order, last_price);
+            self.status = format!("Paper order {} #{}", order.describe(), order_id);
+            self.on_paper_fills(fills);
+        } else {
+            self.status = format!("Sending {}...", order.describe());
+            self.actions.push(Action::PlaceOrder(order));
+        }
+    }
+
+    pub fn set_strategy(
+        &mut self,
+        strategy: Box<dyn Strategy>,
+        mode: LiveMode,
+        caps: PositionCaps,
+    ) {
+        self.status = match mode {
+            LiveMode::DryRun => format!("Strategy: {} (dry run)", strategy.name()),
+            LiveMode::Trade => format!(
+                "Strategy: {} TRADING {} per entry, max {}",
+                strategy.name(),
+                caps.qty,
+                caps.max_position
+            ),
+        };
+        self.strategy = Some(strategy);
+        self.strategy_mode = mode;
+        self.strategy_caps = caps;
+    }
+
+    /// Feeds a closed candle to the strategy and logs what it signals.
+    fn run_strategy(&mut self, candle: &Candle) {
+        let Some(strategy) = self.strategy.as_mut() else {
+            return;
+        };
+        let Some(signal) = strategy.on_candle(candle) else {
+            return;
+        };
+        self.status = format!(
+            "{}: {} @ {:.2}",
+            strategy.name(),
+            signal.arrow(),
+            candle.close
+        );
+        self.signals.push_back(SignalEvent {
+            time: candle.start,
+            price: candle.close,
+            signal,
+        });
+        if self.signals.len() > SIGNALS_CAPACITY {
+            self.signals.pop_front();
+        }
+
+        if self.strategy_mode == LiveMode::Trade {
+            self.trade_signal(signal);
+        }
+    }
+
+    /// Routes a live strategy signal through the order path, within the caps.
+    fn trade_signal(&mut self, signal: Signal) {
+        if !self.trading_enabled {
+            self.status = String::from("Strategy signal not traded: no API credentials");
+            return;
+        }
+        let current = self
+            .account
+            .positions
+            .position(&self.symbol)
+            .map_or(0.0, |p| match p.side {
+                Side::Buy => p.size,
+                Side::Sell => -p.size,
+            });
+        for order in orders_for(&self.symbol, signal, current, self.strategy_caps) {
+            self.submit_order(order);
+        }
+    }
+
+    pub fn set_hotkeys(&mut self, hotkeys: HotkeysConfig) {
+        self.hotkeys = hotkeys;
+    }
+
+    /// Runs the trading hotkey bound to `c`, if any. Returns whether `c` was one.
+    fn handle_hotkey(&mut self, c: char) -> bool {
+        let hotkeys = self.hotkeys.clone();
+        if c == hotkeys.arm {
+            self.hotkeys_armed = !self.hotkeys_armed && self.trading_enabled;
+            self.status = if self.hotkeys_armed {
+                format!(
+                    "Hotkeys ARMED: {} buy / {} sell {}, {} close, {} cancel all",
+                    hotkeys.buy, hotkeys.sell, hotkeys.size, hotkeys.close, hotkeys.cancel_all
+                )
+            } else if self.trading_enabled {
+                String::from("Hotkeys disarmed")
+            } else {
+                String::from("Trading needs API credentials in the config file")
+            };
+            return true;
+        }
+        if ![hotkeys.buy, hotkeys.sell, hotkeys.close, hotkeys.cancel_all].contains(&c) {
+            return false;
+        }
+        if !self.hotkeys_armed {
+            self.status = format!("Hotkeys disarmed, press {} to arm", hotkeys.arm);
+            return true;
+        }
+
+        if c == hotkeys.buy {
+            self.submit_order(OrderRequest::market(&self.symbol, Side::Buy, hotkeys.size));
+        } else if c == hotkeys.sell {
+            self.submit_order(OrderRequest::market(&self.symbol, Side::Sell, hotkeys.size));
+        } else if c == hotkeys.close {
+            self.close_position();
+        } else {
+            self.cancel_all_orders();
+        }
+        true
+    }
+
+    fn cancel_all_orders(&mut self) {
+        if let Some(paper) = self.paper.as_mut() {
+            let count = paper.cancel_all(&self.symbol);
+            self.status = format!("Cancelled {} paper orders in {}", count, self.symbol);
+            self.sync_paper_account();
+        } else {
+            self.status = format!("Cancelling all {} orders...", self.symbol);
+            self.actions.push(Action::CancelAll(self.symbol.clone()));
+        }
+    }
+
+    fn cancel_selected_order(&mut self) {
+        let Some(order_id) = self.account.orders.selected().map(|o| o.order_id.clone()) else {
+            self.status = String::from("No order selected");
+            return;
+        };
+        if let Some(paper) = self.paper.as_mut() {
+            paper.cancel(&order_id);
+            self.status = format!("Paper order #{} cancelled", order_id);
+            self.sync_paper_account();
+        } else {
+            self.status = format!("Cancelling order {}...", order_id);
+            self.actions.push(Action::CancelOrder {
+                symbol: self.symbol.clone(),
+                order_id,
+            });
+        }
+    }
+
+    fn start_amend(&mut self) {
+        let Some(order) = self.account.orders.selected() else {
+            self.status = String::from("No order selected");
+            return;
+        };
+        self.status = format!(
+            "Amend {} {}: new qty and/or @price_",
+            order.order_id, order.qty
+        );
+        self.mode = Mode::AmendOrder {
+            order_id: order.order_id.clone(),
+            buffer: String::new(),
+        };
+    }
+
+    fn handle_amend_key(&mut self, code: KeyCode, order_id: String, mut buffer: String) {
+        match code {
+            KeyCode::Enter => match parse_amend(&buffer) {
+                Ok((qty, price)) => {
+                    self.mode = Mode::Normal;
+                    self.amend_order(order_id, qty, price);
+                }
+                Err(e) => {
+                    self.status = format!("Amend: {}_  ({})", buffer, e);
+                    self.mode = Mode::AmendOrder { order_id, buffer };
+                }
+            },
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.status = String::from("Amend cancelled");
+            }
+            _ => {
+                edit_text(&mut buffer, code);
+                self.status = format!("Amend: {}_", buffer);
+                self.mode = Mode::AmendOrder { order_id, buffer };
+            }
+        }
+    }
+
+    fn amend_order(&mut self, order_id: String, qty: Option<f64>, price: Option<f64>) {
+        if let Some(paper) = self.paper.as_mut() {
+            self.status = if paper.amend(&order_id, qty, price) {
+                format!("Paper order #{} amended", order_id)
+            } else {
+                format!("Paper order #{} is gone", order_id)
+            };
+            self.sync_paper_account();
+        } else {
+            let request = AmendRequest::new(&self.symbol, &order_id, qty, price);
+            self.status = format!("Sending {}...", request.describe());
+            self.actions.push(Action::AmendOrder(request));
+        }
+    }
+
+    fn open_leverage_dialog(&mut self) {
+        if self.paper.is_some() {
+            self.status = String::from("Paper trading has no leverage settings");
+            return;
+        }
+        if !self.trading_enabled {
+            self.status = String::from("Trading needs API credentials in the config file");
+            return;
+        }
+        let position = self.account.positions.position(&self.symbol);
+        self.mode = Mode::Leverage(LeverageDialog::new(&self.symbol, position));
+    }
+
+    fn handle_leverage_key(&mut self, code: KeyCode, mut dialog: LeverageDialog) {
+        match code {
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                return;
+            }
+            KeyCode::Tab => dialog.margin_mode = dialog.margin_mode.toggled(),
+            KeyCode::Enter => match dialog.leverage() {
+                Ok(leverage) => {
+                    self.mode = Mode::Normal;
+                    self.status = format!(
+                        "Setting {} leverage {}x {}...",
+                        dialog.symbol,
+                        leverage,
+                        dialog.margin_mode.label()
+                    );
+                    self.actions.push(Action::SetLeverage {
+                        symbol: dialog.symbol,
+                        leverage,
+                        margin_mode: dialog.margin_mode,
+                        switch_mode: dialog.margin_mode != dialog.current_mode,
+                    });
+                    return;
+                }
+                Err(e) => dialog.error = Some(e),
+            },
+            KeyCode::Char(c) if c.is_ascii_digit() || c == '.' => {
+                edit_text(&mut dialog.input, code);
+                dialog.error = None;
+            }
+            KeyCode::Backspace => {
+                edit_text(&mut dialog.input, code);
+                dialog.error = None;
+            }
+            _ => {}
+        }
+        self.mode = Mode::Leverage(dialog);
+    }
+
+    /// Flattens the position in the chart symbol with a reduce-only market order.
+    fn close_position(&mut self) {
+        let Some(position) = self.account.positions.position(&self.symbol) else {
+            self.status = format!("No {} position to close", self.symbol);
+            return;
+        };
+        let side = match position.side {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        };
+        let order = OrderRequest::market(&self.symbol, side, position.size).reduce_only();
+        self.submit_order(order);
+    }
+
+    fn start_sizing(&mut self) {
+        self.mode = Mode::Sizing(String::new());
+        self.status = String::from(
+            "Size: risk % and stop distance or @price (click the chart to pick the stop)_",
+        );
+    }
+
+    fn calculate_size(&self, input: &str) -> Result<PositionSize, String> {
+        let balance = self
+            .account
+            .wallet
+            .balance()
+            .map(|b| b.available_balance)
+            .ok_or("no wallet balance yet")?;
+        let entry = self.reference_price().ok_or("no price yet")?;
+        size_position(input, balance, entry, &self.instrument)
+    }
+
+    fn show_sizing(&mut self, buffer: String) {
+        self.status = match self.calculate_size(&buffer) {
+            Ok(size) => format!(
+                "Size: {}_  => {} {} SL {} risking {:.2} (Enter to fill order)",
+                buffer,
+                match size.side {
+                    Side::Buy => "buy",
+                    Side::Sell => "sell",
+                },
+                self.instrument.format_qty(size.qty),
+                self.instrument.format_price(size.stop_price),
+                size.risk
+            ),
+            Err(e) => format!("Size: {}_  ({})", buffer, e),
+        };
+        self.mode = Mode::Sizing(buffer);
+    }
+
+    fn handle_sizing_key(&mut self, code: KeyCode, mut buffer: String) {
+        match code {
+            KeyCode::Enter => match self.calculate_size(&buffer) {
+                Ok(size) => {
+                    let side = match size.side {
+                        Side::Buy => "buy",
+                        Side::Sell => "sell",
+                    };
+                    self.mode = Mode::Normal;
+                    let qty = self.instrument.format_qty(size.qty);
+                    let stop = self.instrument.format_price(size.stop_price);
+                    self.start_order_entry(format!("{} {} sl {}", side, qty, stop));
+                }
+                Err(_) => self.show_sizing(buffer),
+            },
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.status = String::from("Sizing cancelled");
+            }
+            _ => {
+                edit_text(&mut buffer, code);
+                self.show_sizing(buffer);
+            }
+        }
+    }
+
+    /// Uses the clicked price as the calculator's stop.
+    fn pick_sizing_stop(&mut self, row: u16, buffer: String) {
+        let Some(price) = self.chart.price_at(self.chart_area, row) else {
+            return;
+        };
+        let risk = buffer.split_whitespace().next().unwrap_or("1");
+        let price = self
+            .instrument
+            .format_price(self.instrument.round_price(price));
+        self.show_sizing(format!("{} @{}", risk, price));
+    }
+
+    /// Handles a frame from the authenticated private websocket.
+    pub fn handle_private_message(&mut self, text: &str) {
+        Metrics::increment(&METRICS.private_messages);
+        self.record("private", text);
+        let topic = serde_json::from_str::<TopicEnvelope>(text)
+            .ok()
+            .and_then(|envelope| envelope.topic);
+        match topic.as_deref() {
+            Some("position") => {
+                if let Ok(response) = serde_json::from_str::<PrivateResponse<PositionData>>(text) {
+                    for position in &response.data {
+                        self.account.positions.update_from_position(position);
+                    }
+                }
+                return;
+            }
+            Some("wallet") => {
+                if let Ok(response) = serde_json::from_str::<PrivateResponse<WalletData>>(text) {
+                    for wallet in &response.data {
+                        self.account.wallet.update_from_wallet(wallet);
+                    }
+                    self.account.record_equity();
+                }
+                return;
+            }
+            Some("order") => {
+                if let Ok(response) = serde_json::from_str::<PrivateResponse<OrderData>>(text) {
+                    for order in &response.data {
+                        self.account.orders.update_from_order(order);
+                    }
+                }
+                return;
+            }
+            Some("execution") => {
+                if let Ok(response) = serde_json::from_str::<PrivateResponse<ExecutionData>>(text) {
+                    for execution in response
+                        .data
+                        .iter()
+                        .filter_map(Execution::from_execution_data)
+                    {
+                        self.journal_fill(&execution);
+                        self.account.executions.push(execution);
+                    }
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        if let Ok(response) = serde_json::from_str::<OpResponse>(text) {
+            if response.op == "auth" {
+                self.status = if response.success {
+                    match &self.profile {
+                        Some(profile) => format!("Private API authenticated ({})", profile),
+                        None => String::from("Private API authenticated"),
+                    }
+                } else {
+                    format!("Private API auth failed: {}", response.ret_msg)
+                };
+            }
+        }
+    }
+
+    /// Area the chart was last drawn into.
+    pub fn chart_area(&self) -> Rect {
+        self.chart_area
+    }
+
+    pub fn set_spread(&mut self, spread: SpreadSeries) {
+        self.status = format!("Charting spread {}", spread.name());
+        self.spread = Some(spread);
+    }
+
+    /// Routes a leg's candles into the spread series. Returns `false` if `symbol` is not a leg.
+    fn update_spread(&mut self, symbol: &str, response: &KlineResponse) -> bool {
+        let Some(spread) = self.spread.as_mut() else {
+            return false;
+        };
+        if !spread.is_leg(symbol) {
+            return false;
+        }
+        for kline_data in &response.data {
+            if let Some(candle) =
+                Candle::from_kline_data(kline_data).and_then(|candle| spread.update(symbol, candle))
+            {
+                self.chart.upsert_candle(candle);
+            }
+        }
+        // Alerts placed on the chart refer to spread values
+        if let Some(price) = self.chart.last_price() {
+            self.on_price(price);
+        }
+        true
+    }
+
+    fn reload_annotations(&mut self) {
+        match load_annotations(&self.symbol) {
+            Ok(annotations) => {
+                if !annotations.is_empty() {
+                    self.status = format!("Loaded {} annotations", annotations.len());
+                }
+                self.annotations = annotations;
+            }
+            Err(e) => self.status = format!("Failed to load annotations: {}", e),
+        }
+    }
+
+    pub fn handle_message(&mut self, text: &str) {
+        Metrics::increment(&METRICS.public_messages);
+        if let Some(error) = self.sinks.take_error() {
+            self.status = error;
+        }
+        self.record("public", text);
+        let Ok(envelope) = serde_json::from_str::<TopicEnvelope>(text) else {
+            return;
+        };
+        let Some(topic) = envelope.topic else {
+            return;
+        };
+
+        let topic_symbol = topic.rsplit('.').next().unwrap_or_default();
+        if topic == format!("kline.D.{}", self.symbol) {
+            if let Ok(response) = serde_json::from_str::<KlineResponse>(text) {
+                for kline_data in &response.data {
+                    self.session.update_from_kline(kline_data);
+                }
+            }
+        } else if topic.starts_with("kline.") {
+            let Ok(response) = serde_json::from_str::<KlineResponse>(text) else {
+                return;
+            };
+            if self.update_spread(topic_symbol, &response) {
+                return;
+            }
+            if topic_symbol != self.symbol {
+                for kline_data in &response.data {
+                    self.chart.update_comparison_from_kline(kline_data);
+                }
+            } else if self.spread.is_none() {
+                for kline_data in response.data {
+                    let lag = Utc::now().timestamp_millis() - kline_data.timestamp;
+                    METRICS.candle_lag_ms.store(lag, Ordering::Relaxed);
+                    self.chart.update_from_kline(&kline_data);
+                    if let (Some(paper), Ok(volume)) =
+                        (self.paper.as_mut(), kline_data.volume.parse())
+                    {
+                        paper.set_volume(volume);
+                    }
+                    if kline_data.confirm {
+                        if let Some(candle) = Candle::from_kline_data(&kline_data) {
+                            self.store_candle(&candle);
+                            self.run_strategy(&candle);
+                        }
+                    }
+                }
+                if let Some(price) = self.chart.last_price() {
+                    self.on_price(price);
+                }
+            }
+        } else if topic.starts_with("publicTrade.") {
+            if let Ok(response) = serde_json::from_str::<TradeResponse>(text) {
+                for trade_data in &response.data {
+                    self.trades.update_from_trade(trade_data);
+                    if let Some(trade) = Trade::from_trade_data(trade_data) {
+                        self.sinks.publish(SinkEvent::Trade {
+                            symbol: self.symbol.clone(),
+                            trade,
+                        });
+                    }
+                }
+                if let Some(paper) = self.paper.as_mut() {
+                    let fills = response
+                        .data
+                        .iter()
+                        .filter_map(|t| t.price.parse().ok())
+                        .flat_map(|price| paper.on_price(&self.symbol, price))
+                        .collect();
+                    self.on_paper_fills(fills);
+                }
+                if let Some(price) = response.data.last().and_then(|t| t.price.parse().ok()) {
+                    self.last_trade_price = Some(price);
+                    self.ladder.set_last_price(price);
+                    self.account.positions.mark(&self.symbol, price);
+                    if self.spread.is_none() {
+                        self.on_price(price);
+                    }
+                }
+            }
+        } else if topic.starts_with("orderbook.") {
+            if let Ok(response) = serde_json::from_str::<OrderbookResponse>(text) {
+                self.ladder.update_from_orderbook(&response);
+            }
+        }
+    }
+
+    fn on_price(&mut self, price: f64) {
+        for alert in self.alerts.on_price(price) {
+            Metrics::increment(&METRICS.alert_triggers);
+            self.sinks.publish(SinkEvent::Alert {
+                symbol: self.symbol.clone(),
+                price: alert.price,
+                last_price: price,
+                rule: alert.rule(),
+                channels: alert.channels.clone(),
+                time: Utc::now().timestamp_millis(),
+            });
+            self.status = format!(
+                "ALERT: price crossed {:.2} (last {:.2})",
+                alert.price, price
+            );
+            if let Some(order) = alert.order {
+                let description = format!("Price crossed {:.2} (last {:.2})", alert.price, price);
+                self.alert_orders.push_back((description, order));
+            }
+        }
+        self.offer_alert_order();
+    }
+
+    /// Pops up the next fired alert's order, unless the user is busy typing;
+    /// then it waits for the next price update.
+    fn offer_alert_order(&mut self) {
+        if self.mode != Mode::Normal {
+            return;
+        }
+        if let Some((alert, order)) = self.alert_orders.pop_front() {
+            self.status = format!("ALERT order: send {}? (y/n)", order.describe());
+            self.mode = Mode::ConfirmAlertOrder { alert, order };
+        }
+    }
+
+    fn start_alert_order_entry(&mut self) {
+        if !self.trading_enabled {
+            self.status = String::from("Trading needs API credentials in the config file");
+            return;
+        }
+        self.status = String::from("Alert order: <price> <order>, e.g. 3200 sell 0.5_");
+        self.mode = Mode::AlertOrderEntry(String::new());
+    }
+
+    fn handle_alert_order_entry_key(&mut self, code: KeyCode, mut buffer: String) {
+        match code {
+            KeyCode::Enter => {
+                match parse_alert_order(&buffer, &self.symbol, self.reference_price()) {
+                    Ok((price, order)) => {
+                        self.mode = Mode::Normal;
+                        let alert = self.alerts.add_order_alert(price, order);
+                        self.status = format!(
+                            "Alert #{} at {:.2} will offer: {}",
+                            alert.id,
+                            alert.price,
+                            alert
+                                .order
+                                .as_ref()
+                                .map(|o| o.describe())
+                                .unwrap_or_default()
+                        );
+                    }
+                    Err(e) => {
+                        self.status = format!("Alert order: {}_  ({})", buffer, e);
+                        self.mode = Mode::AlertOrderEntry(buffer);
+                    }
+                }
+            }
+            KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.status = String::from("Alert order cancelled");
+            }
+            _ => {
+                edit_text(&mut buffer, code);
+                self.status = format!("Alert order: {}_", buffer);
+                self.mode = Mode::AlertOrderEntry(buffer);
+            }
+        }
+    }
+
+    fn handle_confirm_alert_order_key(&mut self, code: KeyCode, order: OrderRequest) {
+        self.mode = Mode::Normal;
+        if code == KeyCode::Char('y') {
+            self.submit_order(order);
+        } else {
+            self.status = format!("Alert order discarded: {}", order.describe());
+        }
+        self.offer_alert_order();
+    }
+
+    /// Places an alert at the price under the given screen position.
+    fn place_alert_at(&mut self, row: u16) {
+        let Some(price) = self.chart.price_at(self.chart_area, row) else {
+            return;
+        };
+        let alert = self.alerts.add_price_alert(price);
+        self.status = format!(
+            "Alert #{} set at {:.2}, notifies {}",
+            alert.id,
+            alert.price,
+            describe_channels(&alert.channels)
+        );
+    }
+
+    fn save_drawings(&mut self) {
+        if let Err(e) = self.drawings.save(&self.symbol) {
+            self.status = format!("Failed to save drawings: {}", e);
+        }
+    }
+
+    /// Price distance covered by one terminal row around `row`, used as click tolerance.
+    fn row_tolerance(&self, row: u16) -> f64 {
+        match (
+            self.chart.price_at(self.chart_area, row),
+            self.chart.price_at(self.chart_area, row + 1),
+        ) {
+            (Some(a), Some(b)) => (a - b).abs(),
+            _ => 0.0,
+        }
+    }
+
+    /// Selects the level under `row`, or adds a new one there.
+    fn select_or_add_level(&mut self, row: u16) {
+        let Some(price) = self.chart.price_at(self.chart_area, row) else {
+            return;
+        };
+        let index = match self.drawings.nearest_level(price, self.row_tolerance(row)) {
+            Some(index) => index,
+            None => {
+                let index = self.drawings.add_level(price);
+                self.save_drawings();
+                index
+            }
+        };
+        self.selected_level = Some(index);
+        let level = &self.drawings.levels[index];
+        self.status = format!(
+            "Selected {} at {:.2} (drag to move, 'x' delete, 'n' rename)",
+            level.name, level.price
+        );
+    }
+
+    fn move_selected_level(&mut self, row: u16) {
+        let Some(index) = self.selected_level else {
+            return;
+        };
+        if let Some(price) = self.chart.price_at(self.chart_area, row) {
+            self.drawings.levels[index].price = price;
+            self.save_drawings();
+        }
+    }
+
+    fn delete_selected_level(&mut self) {
+        if let Some(index) = self.selected_level.take() {
+            let level = self.drawings.levels.remove(index);
+            self.save_drawings();
+            self.status = format!("Deleted {}", level.name);
+        }
+    }
+
+    /// Candle start time and price under the crosshair (mouse position).
+    fn crosshair_anchor(&self) -> Option<(i64, f64)> {
+        let (column, row) = self.mouse_position?;
+        let candle = self.chart.candle_at(self.chart_area, column)?;
+        let price = self.chart.price_at(self.chart_area, row)?;
+        Some((candle.start, price))
+    }
+
+    /// First press sets the trendline start, the second press completes it.
+    fn place_trendline_anchor(&mut self) {
+        let Some(anchor) = self.crosshair_anchor() else {
+            return;
+        };
+        match self.pending_trendline.take() {
+            None => {
+                self.pending_trendline = Some(anchor);
+                self.status = format!("Trendline start at {:.2}, press 'l' at the end", anchor.1);
+            }
+            Some(start) if start.0 != anchor.0 => {
+                let (start, end) = if start.0 < anchor.0 {
+                    (start, anchor)
+                } else {
+                    (anchor, start)
+                };
+                self.drawings.trendlines.push(TrendLine {
+                    start_time: start.0,
+                    start_price: start.1,
+                    end_time: end.0,
+                    end_price: end.1,
+                });
+                self.save_drawings();
+                self.status = String::from("Trendline added ('u' to undo)");
+            }
+            Some(start) => {
+                self.pending_trendline = Some(start);
+                self.status = String::from("Trendline end must be on a different candle");
+            }
+        }
+    }
+
+    /// First press picks the swing the move starts from, the second its end.
+    fn place_fib_anchor(&mut self) {
+        let Some((_, price)) = self.crosshair_anchor() else {
+            return;
+        };
+        match self.pending_fib.take() {
+            None => {
+                self.pending_fib = Some(price);
+                self.status = format!("Fib start at {:.2}, press 'f' at the other swing", price);
+            }
+            Some(start) => {
+                self.drawings.fib = Some(FibRetracement {
+                    start_price: start,
+                    end_price: price,
+                });
+                self.drawings.show_fib = true;
+                self.save_drawings();
+                self.status = format!("Fib retracement {:.2} -> {:.2}", start, price);
+            }
+        }
+    }
+
+    fn toggle_fib(&mut self) {
+        self.drawings.show_fib = !self.drawings.show_fib;
+        self.save_drawings();
+    }
+
+    fn undo_trendline(&mut self) {
+        if self.drawings.trendlines.pop().is_some() {
+            self.save_drawings();
+            self.status = String::from("Removed last trendline");
+        }
+    }
+
+    /// Handles keys while typing a level name.
+    fn handle_rename_key(&mut self, code: KeyCode, mut buffer: String) {
+        match code {
+            KeyCode::Char(_) | KeyCode::Backspace => {
+                edit_text(&mut buffer, code);
+                self.status = format!("Name: {}_", buffer);
+                self.mode = Mode::Rename(buffer);
+            }
+            KeyCode::Enter => {
+                if let (Some(index), false) = (self.selected_level, buffer.is_empty()) {
+                    self.drawings.levels[index].name = buffer;
+                    self.save_drawings();
+                }
+                self.mode = Mode::Draw;
+                self.status = String::from("Drawing mode");
+            }
+            KeyCode::Esc => {
+                self.mode = Mode::Draw;
+                self.status = String::from("Drawing mode");
+            }
+            _ => self.mode = Mode::Rename(buffer),
+        }
+    }
+
+    fn handle_draw_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('x') | KeyCode::Delete => self.delete_selected_level(),
+            KeyCode::Char('n') if self.selected_level.is_some() => {
+                self.mode = Mode::Rename(String::new());
+                self.status = String::from("Name: _");
+            }
+            KeyCode::Char('l') => self.place_trendline_anchor(),
+            KeyCode::Char('f') => self.place_fib_anchor(),
+            KeyCode::Char('F') => self.toggle_fib(),
+            KeyCode::Char('u') => self.undo_trendline(),
+            KeyCode::Tab if !self.drawings.levels.is_empty() => {
+                let next = self
+                    .selected_level
+                    .map_or(0, |i| (i + 1) % self.drawings.levels.len());
+                self.selected_level = Some(next);
+            }
+            KeyCode::Char('w') | KeyCode::Esc => {
+                self.mode = Mode::Normal;
+                self.selected_level = None;
+                self.pending_trendline = None;
+                self.pending_fib = None;
+                self.status = String::from("Drawing mode off");
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns `true` when the app should quit.
+    pub fn handle_key(&mut self, code: KeyCode) -> bool {
+        match self.mode.clone() {
+            Mode::Rename(buffer) => {
+                self.handle_rename_key(code, buffer);
+                return false;
+            }
+            Mode::OrderEntry(buffer) => {
+                self.handle_order_entry_key(code, buffer);
+                return false;
+            }
+            Mode::ConfirmOrder(order) => {
+                self.handle_confirm_order_key(code, order);
+                return false;
+            }
+            Mode::Sizing(buffer) => {
+                self.handle_sizing_key(code, buffer);
+                return false;
+            }
+            Mode::AmendOrder { order_id, buffer } => {
+                self.handle_amend_key(code, order_id, buffer);
+                return false;
+            }
+            Mode::Leverage(dialog) => {
+                self.handle_leverage_key(code, dialog);
+                return false;
+            }
+            Mode::AlertOrderEntry(buffer) => {
+                self.handle_alert_order_entry_key(code, buffer);
+                return false;
+            }
+            Mode::ConfirmAlertOrder { order, .. } => {
+                self.handle_confirm_alert_order_key(code, order);
+                return false;
+            }
+            Mode::Draw if code != KeyCode::Char(self.keys.quit) => {
+                self.handle_draw_key(code);
+                return false;
+            }
+            _ => {}
+        }
+
+        if let KeyCode::Char(c) = code {
+            if self.handle_hotkey(c) {
+                return false;
+            }
+        }
+
+        if let Some(key) = match code {
+            KeyCode::Char(c) => self.keys.lookup(c),
+            _ => None,
+        } {
+            return self.handle_chart_key(key);
+        }
+
+        match code {
+            KeyCode::Char('P') => self.switch_to_next_profile(),
+            KeyCode::Char('L') => self.open_leverage_dialog(),
+            KeyCode::Char('T') => self.start_alert_order_entry(),
+            KeyCode::Up if self.show_orders => self.account.orders.select_previous(),
+            KeyCode::Down if self.show_orders => self.account.orders.select_next(),
+            KeyCode::Char('m') if self.show_orders => self.start_amend(),
+            KeyCode::Delete if self.show_orders => self.cancel_selected_order(),
+            KeyCode::Char('K') if self.show_orders => self.cancel_all_orders(),
+            KeyCode::PageUp => self.account.executions.scroll_up(5),
+            KeyCode::PageDown => self.account.executions.scroll_down(5),
+            KeyCode::Char('a') => {
+                if let Some((_, row)) = self.mouse_position {
+                    self.place_alert_at(row);
+                }
+            }
+            KeyCode::Char('N') => self.cycle_alert_route(),
+            KeyCode::Char('c') => {
+                self.alerts.clear();
+                self.status = String::from("Alerts cleared");
+            }
+            KeyCode::Char('F') => self.toggle_fib(),
+            KeyCode::Char('r') => self.reload_annotations(),
+            KeyCode::Char('o') => self.start_order_entry(String::new()),
+            KeyCode::Char('z') => self.start_sizing(),
+            KeyCode::Char('w') => {
+                self.mode = Mode::Draw;
+                self.status = String::from(
+                    "Drawing mode: click adds/selects a level, 'l' trendline, 'f' fib, Esc leaves",
+                );
+            }
+            _ => {}
+        }
+        false
+    }
+
+    /// Runs a rebindable chart command. Returns true to quit.
+    fn handle_chart_key(&mut self, key: ChartKey) -> bool {
+        match key {
+            ChartKey::Quit => return true,
+            ChartKey::Trades => self.show_trades = !self.show_trades,
+            ChartKey::Ladder => self.show_ladder = !self.show_ladder,
+            ChartKey::Positions => self.show_positions = !self.show_positions,
+            ChartKey::Wallet => self.show_wallet = !self.show_wallet,
+            ChartKey::Executions => self.show_executions = !self.show_executions,
+            ChartKey::Orders => self.show_orders = !self.show_orders,
+            ChartKey::Equity => self.show_equity = !self.show_equity,
+            ChartKey::Export => self.export_market_data(),
+            ChartKey::ZoomIn => self.chart.zoom_in(),
+            ChartKey::ZoomOut => self.chart.zoom_out(),
+            ChartKey::ToggleMa => self.chart.set_show_ma(!self.chart.show_ma()),
+        }
+        false
+    }
+
+    pub fn handle_mouse(&mut self, event: MouseEvent) {
+        match event.kind {
+            MouseEventKind::Moved => {
+                self.mouse_position = Some((event.column, event.row));
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                self.mouse_position = Some((event.column, event.row));
+                if self.mode == Mode::Draw {
+                    self.move_selected_level(event.row);
+                }
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.mouse_position = Some((event.column, event.row));
+                if self
+                    .chart
+                    .candle_at(self.chart_area, event.column)
+                    .is_none()
+                {
+                    return;
+                }
+                match self.mode.clone() {
+                    Mode::Normal => self.place_alert_at(event.row),
+                    Mode::Draw => self.select_or_add_level(event.row),
+                    Mode::Sizing(buffer) => self.pick_sizing_stop(event.row, buffer),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn chart_levels(&self) -> Vec<PriceLevel> {
+        let drawn = self.drawings.levels.iter().enumerate().map(|(i, level)| {
+            let color = if self.selected_level == Some(i) {
+                Color::Yellow
+            } else {
+                Color::LightBlue
+            };
+            PriceLevel {
+                price: level.price,
+                label: format!("{} {:.2}", level.name, level.price),
+                color,
+                dashed: true,
+            }
+        });
+
+        let alerts = self.alerts.alerts().iter().map(|alert| {
+            let arrow = match alert.direction {
+                AlertDirection::Above => "^",
+                AlertDirection::Below => "v",
+            };
+            let order = alert
+                .order
+                .as_ref()
+                .map(|order| format!(" -> {}", order.describe()))
+                .unwrap_or_default();
+            PriceLevel {
+                price: alert.price,
+                label: format!("{} alert {:.2}{}", arrow, alert.price, order),
+                color: Color::Magenta,
+                dashed: false,
+            }
+        });
+
+        let fib = self
+            .drawings
+            .fib
+            .iter()
+            .filter(|_| self.drawings.show_fib)
+            .flat_map(|fib| {
+                FIB_RATIOS.iter().map(|ratio| {
+                    let price = fib.level(*ratio);
+                    PriceLevel {
+                        price,
+                        label: format!("{:.1}% {:.2}", ratio * 100.0, price),
+                        color: Color::LightGreen,
+                        dashed: false,
+                    }
+                })
+            });
+
+        let session = [
+            ("Session H", self.session.high(), Color::Green),
+            ("Session L", self.session.low(), Color::Red),
+            ("Prev close", self.session.previous_close(), Color::Gray),
+        ]
+        .into_iter()
+        .filter(|_| self.spread.is_none())
+        .filter_map(|(name, price, color)| {
+            price.map(|price| PriceLevel {
+                price,
+                label: format!("{} {:.2}", name, price),
+                color,
+                dashed: false,
+            })
+        });
+
+        drawn.chain(fib).chain(session).chain(alerts).collect()
+    }
+
+    /// Bottom row with the private-account panels side by side.
+    fn draw_account_panels(&self, frame: &mut Frame, area: Rect) {
+        let mut constraints = Vec::new();
+        if self.show_positions {
+            constraints.push(Constraint::Min(40));
+        }
+        if self.show_executions {
+            constraints.push(Constraint::Length(50));
+        }
+        if self.show_orders {
+            constraints.push(Constraint::Length(58));
+        }
+        if self.show_wallet {
+            constraints.push(Constraint::Length(60));
+        }
+        if self.show_equity {
+            constraints.push(Constraint::Length(50));
+        }
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(constraints)
+            .split(area);
+
+        let mut next = 0;
+        if self.show_positions {
+            self.account.positions.draw(frame, columns[next]);
+            next += 1;
+        }
+        if self.show_executions {
+            self.account.executions.draw(frame, columns[next]);
+            next += 1;
+        }
+        if self.show_orders {
+            self.account.orders.draw(frame, columns[next]);
+            next += 1;
+        }
+        if self.show_wallet {
+            self.account.wallet.draw(frame, columns[next]);
+            next += 1;
+        }
+        if self.show_equity {
+            self.account.equity.draw(frame, columns[next]);
+        }
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+        self.chart.set_levels(self.chart_levels());
+        self.chart.set_time_lines(
+            self.drawings
+                .trendlines
+                .iter()
+                .map(|line| TimeLine {
+                    start: (line.start_time, line.start_price),
+                    end: (line.end_time, line.end_price),
+                    color: Color::LightCyan,
+                    extend_right: true,
+                })
+                .collect(),
+        );
+        let signals = self.signals.iter().map(|event| Marker {
+            time: event.time,
+            price: event.price,
+            text: event.signal.arrow().to_string(),
+            color: match event.signal {
+                Signal::EnterLong => Color::Green,
+                Signal::EnterShort => Color::Red,
+                Signal::Exit => Color::Yellow,
+            },
+        });
+        self.chart.set_markers(
+            self.annotations
+                .iter()
+                .map(|annotation| Marker {
+                    time: annotation.timestamp,
+                    price: annotation.price,
+                    text: annotation.text.clone(),
+                    color: Color::White,
+                })
+                .chain(signals)
+                .collect(),
+        );
+
+        let show_account = self.show_positions
+            || self.show_wallet
+            || self.show_executions
+            || self.show_orders
+            || self.show_equity;
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Length(if show_account { 8 } else { 0 }),
+                Constraint::Length(1),
+            ])
+            .split(frame.size());
+        frame.render_widget(
+            Paragraph::new(self.status.as_str()).style(Style::default().fg(Color::Cyan)),
+            rows[2],
+        );
+        if show_account {
+            self.draw_account_panels(frame, rows[1]);
+        }
+
+        let mut constraints = vec![Constraint::Min(40)];
+        if self.show_trades {
+            constraints.push(Constraint::Length(42));
+        }
+        if self.show_ladder {
+            constraints.push(Constraint::Length(34));
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(constraints)
+            .split(rows[0]);
+        let mut panels = chunks.iter().copied();
+
+        if let Some(area) = panels.next() {
+            self.chart.draw(frame, area);
+            self.chart_area = area;
+        }
+        if self.show_trades {
+            if let Some(area) = panels.next() {
+                self.trades.draw(frame, area);
+            }
+        }
+        if self.show_ladder {
+            if let Some(area) = panels.next() {
+                self.ladder.draw(frame, area);
+            }
+        }
+        if let Some((column, row)) = self.mouse_position {
+            self.chart.draw_tooltip(frame, self.chart_area, column, row);
+        }
+        match &self.mode {
+            Mode::Leverage(dialog) => dialog.draw(frame),
+            Mode::ConfirmAlertOrder { alert, order } => draw_confirm(
+                frame,
+                "Alert order",
+                vec![
+                    Line::from(alert.as_str()),
+                    Line::from(format!("Send {}?", order.describe())),
+                ],
+            ),
+            _ => {}
+        }
+    }
+}
+
+/// Channels of an alert for the status line.
+fn describe_channels(channels: &[Channel]) -> String {
+    if channels.is_empty() {
+        return String::from("the status line only");
+    }
+    channels
+        .iter()
+        .map(Channel::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Applies a typing key to a one-line text buffer.
+fn edit_text(buffer: &mut String, code: KeyCode) {
+    match code {
+        KeyCode::Char(c) => buffer.push(c),
+        KeyCode::Backspace => {
+            buffer.pop();
+        }
+        _ => {}
+    }
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new(&ChartConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alert_stop_is_aimed_at_the_price_when_it_fires() {
+        // Typed at 3000: the stop at 3100 is above price, so rising to it
+        let (_, order) =
+            parse_alert_order("3200 sell 1 stop 3100", "BTCUSDT", Some(3000.0)).unwrap();
+        assert_eq!(order.trigger_direction, Some(1));
+
+        let mut app = App::default();
+        app.set_paper_trading(10_000.0, FeeModel::zero());
+        // The alert fires once price has run through both levels
+        app.last_trade_price = Some(3250.0);
+        app.handle_confirm_alert_order_key(KeyCode::Char('y'), order);
+
+        // Falling to 3100 from 3250, so it rests instead of filling at once
+        let paper = app.paper.as_ref().unwrap();
+        assert_eq!(paper.open_orders(), 1);
+        assert!(paper.positions().is_empty());
+    }
+}
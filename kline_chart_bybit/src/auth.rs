@@ -0,0 +1,115 @@
+use crate::{
+    config::{endpoints, ApiConfig},
+    constants::{AUTH_EXPIRY_MS, PRIVATE_TOPICS, RECV_WINDOW, USER_AGENT},
+    models::SubscribeMessage,
+};
+use chrono::Utc;
+use futures_util::SinkExt;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    connect_async,
+    tungstenite::{client::IntoClientRequest, protocol::Message},
+    MaybeTlsStream, WebSocketStream,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// `{"op":"auth","args":[api_key, expires, signature]}`
+#[derive(Debug, Serialize)]
+struct AuthMessage {
+    op: &'static str,
+    args: (String, i64, String),
+}
+
+/// Signs requests for Bybit's private REST and websocket APIs.
+#[derive(Clone)]
+pub struct Signer {
+    api_key: String,
+    api_secret: String,
+    pub testnet: bool,
+}
+
+impl Signer {
+    pub fn new(api: &ApiConfig) -> Self {
+        Self {
+            api_key: api.key.clone(),
+            api_secret: api.secret.clone(),
+            testnet: api.testnet,
+        }
+    }
+
+    pub fn api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    /// Hex-encoded HMAC-SHA256 of `payload` with the API secret.
+    pub fn sign(&self, payload: &str) -> String {
+        let mut mac = HmacSha256::new_from_slice(self.api_secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(payload.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    /// Headers for a v5 REST call. `payload` is the query string for GET
+    /// requests or the JSON body for POST requests.
+    pub fn rest_headers(&self, timestamp: i64, payload: &str) -> Vec<(&'static str, String)> {
+        let signature = self.sign(&format!(
+            "{}{}{}{}",
+            timestamp, self.api_key, RECV_WINDOW, payload
+        ));
+        vec![
+            ("X-BAPI-API-KEY", self.api_key.clone()),
+            ("X-BAPI-TIMESTAMP", timestamp.to_string()),
+            ("X-BAPI-RECV-WINDOW", RECV_WINDOW.to_string()),
+            ("X-BAPI-SIGN", signature),
+        ]
+    }
+
+    fn websocket_auth_message(&self, expires: i64) -> AuthMessage {
+        AuthMessage {
+            op: "auth",
+            args: (
+                self.api_key.clone(),
+                expires,
+                self.sign(&format!("GET/realtime{}", expires)),
+            ),
+        }
+    }
+}
+
+/// Opens the private websocket, authenticates and subscribes to the private
+/// topics. Replies arrive on the stream like any other message.
+pub async fn connect_private(signer: &Signer) -> Result<WsStream, Box<dyn std::error::Error>> {
+    let url = if signer.testnet {
+        &endpoints().testnet_private_ws
+    } else {
+        &endpoints().private_ws
+    };
+    let mut request = url.as_str().into_client_request()?;
+    request
+        .headers_mut()
+        .insert("User-Agent", USER_AGENT.parse()?);
+
+    let (mut ws_stream, _) = connect_async(request).await?;
+
+    let expires = Utc::now().timestamp_millis() + AUTH_EXPIRY_MS;
+    let auth = signer.websocket_auth_message(expires);
+    ws_stream
+        .send(Message::Text(serde_json::to_string(&auth)?))
+        .await?;
+
+    let subscribe = SubscribeMessage {
+        op: "subscribe".to_string(),
+        args: PRIVATE_TOPICS.iter().map(|t| t.to_string()).collect(),
+    };
+    ws_stream
+        .send(Message::Text(serde_json::to_string(&subscribe)?))
+        .await?;
+
+    Ok(ws_stream)
+}
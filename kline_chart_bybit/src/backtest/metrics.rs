@@ -0,0 +1,114 @@
+use super::ClosedTrade;
+
+/// Summary statistics of a backtest.
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    pub total_return: f64,
+    pub trades: usize,
+    /// Share of trades with positive PnL, in percent.
+    pub win_rate: f64,
+    /// Gross profit over gross loss; infinite without losing trades.
+    pub profit_factor: f64,
+    /// Largest peak-to-trough equity drop, in percent.
+    pub max_drawdown: f64,
+    /// Annualized Sharpe ratio of per-candle returns, risk-free rate zero.
+    pub sharpe: f64,
+}
+
+impl Metrics {
+    pub fn compute(trades: &[ClosedTrade], equity: &[(i64, f64)], periods_per_year: f64) -> Self {
+        let wins = trades.iter().filter(|t| t.pnl > 0.0).count();
+        let gross_profit: f64 = trades.iter().map(|t| t.pnl.max(0.0)).sum();
+        let gross_loss: f64 = trades.iter().map(|t| (-t.pnl).max(0.0)).sum();
+
+        let mut peak = f64::MIN;
+        let mut max_drawdown: f64 = 0.0;
+        for (_, value) in equity {
+            peak = peak.max(*value);
+            max_drawdown = max_drawdown.max((peak - value) / peak * 100.0);
+        }
+
+        let returns: Vec<f64> = equity
+            .windows(2)
+            .map(|pair| pair[1].1 / pair[0].1 - 1.0)
+            .collect();
+        let sharpe = if returns.len() < 2 {
+            0.0
+        } else {
+            let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+            let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>()
+                / (returns.len() - 1) as f64;
+            if variance > 0.0 {
+                mean / variance.sqrt() * periods_per_year.sqrt()
+            } else {
+                0.0
+            }
+        };
+
+        let total_return = match (equity.first(), equity.last()) {
+            (Some((_, first)), Some((_, last))) => (last / first - 1.0) * 100.0,
+            _ => 0.0,
+        };
+
+        Self {
+            total_return,
+            trades: trades.len(),
+            win_rate: if trades.is_empty() {
+                0.0
+            } else {
+                wins as f64 / trades.len() as f64 * 100.0
+            },
+            profit_factor: if gross_loss > 0.0 {
+                gross_profit / gross_loss
+            } else if gross_profit > 0.0 {
+                f64::INFINITY
+            } else {
+                0.0
+            },
+            max_drawdown,
+            sharpe,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Side;
+
+    fn trade(pnl: f64) -> ClosedTrade {
+        ClosedTrade {
+            side: Side::Buy,
+            entry_time: 0,
+            entry_price: 100.0,
+            exit_time: 0,
+            exit_price: 100.0,
+            qty: 1.0,
+            fees: 0.0,
+            pnl,
+        }
+    }
+
+    #[test]
+    fn profit_factor_and_drawdown() {
+        let trades = [trade(30.0), trade(-10.0), trade(-5.0), trade(20.0)];
+        // Peaks at 120 and 150; the drops to 90 (25%) and 105 (30%)
+        let equity = [(0, 100.0), (1, 120.0), (2, 90.0), (3, 150.0), (4, 105.0)];
+
+        let metrics = Metrics::compute(&trades, &equity, 0.0);
+
+        assert_eq!(metrics.trades, 4);
+        assert_eq!(metrics.win_rate, 50.0);
+        assert!((metrics.profit_factor - 50.0 / 15.0).abs() < 1e-12);
+        assert!((metrics.max_drawdown - 30.0).abs() < 1e-12);
+        assert!((metrics.total_return - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn profit_factor_without_losses() {
+        let metrics = Metrics::compute(&[trade(10.0)], &[(0, 100.0), (1, 110.0)], 0.0);
+
+        assert_eq!(metrics.profit_factor, f64::INFINITY);
+        assert_eq!(metrics.max_drawdown, 0.0);
+    }
+}
@@ -0,0 +1,279 @@
+pub mod metrics;
+pub mod optimize;
+pub mod report;
+pub mod walk_forward;
+
+pub use metrics::Metrics;
+pub use optimize::{grid_search, Objective};
+pub use report::write_report;
+pub use walk_forward::{walk_forward, WalkForward};
+
+use crate::{
+    fees::FeeModel,
+    models::{Candle, Side},
+    strategy::{Signal, Strategy},
+};
+
+/// Milliseconds in a (calendar) year, for annualizing per-candle returns.
+const YEAR_MS: f64 = 365.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+
+#[derive(Debug, Clone)]
+pub struct BacktestConfig {
+    pub capital: f64,
+    pub fees: FeeModel,
+    /// Price increment, for tick-based slippage.
+    pub tick_size: f64,
+}
+
+/// A round trip from entry to exit.
+#[derive(Debug, Clone)]
+pub struct ClosedTrade {
+    pub side: Side,
+    pub entry_time: i64,
+    pub entry_price: f64,
+    pub exit_time: i64,
+    pub exit_price: f64,
+    pub qty: f64,
+    /// Entry and exit fees together.
+    pub fees: f64,
+    /// Net of fees.
+    pub pnl: f64,
+}
+
+#[derive(Debug, Clone)]
+struct OpenPosition {
+    side: Side,
+    entry_time: i64,
+    entry_price: f64,
+    qty: f64,
+    entry_fee: f64,
+}
+
+impl OpenPosition {
+    fn pnl_at(&self, price: f64) -> f64 {
+        let direction = match self.side {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        };
+        (price - self.entry_price) * self.qty * direction
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BacktestResult {
+    pub strategy: String,
+    pub trades: Vec<ClosedTrade>,
+    /// Mark-to-market equity at every candle close.
+    pub equity: Vec<(i64, f64)>,
+    pub metrics: Metrics,
+}
+
+/// Replays `candles` (oldest first) through `strategy`.
+///
+/// A signal from a candle's close is filled at the next candle's open, so the
+/// strategy never trades on a price it could not have had. Each entry commits
+/// the whole account at 1x; a position still open at the end is closed at the
+/// last close. Every fill is a market order paying taker fees and slippage.
+pub fn run(
+    candles: &[Candle],
+    strategy: &mut dyn Strategy,
+    config: &BacktestConfig,
+) -> BacktestResult {
+    run_from(candles, 0, strategy, config)
+}
+
+/// Like [`run`], but the first `warmup` candles only feed the strategy's
+/// indicators: trading and the equity curve start after them.
+pub fn run_from(
+    candles: &[Candle],
+    warmup: usize,
+    strategy: &mut dyn Strategy,
+    config: &BacktestConfig,
+) -> BacktestResult {
+    let (history, candles) = candles.split_at(warmup.min(candles.len()));
+    let mut pending: Option<Signal> = None;
+    for candle in history {
+        pending = strategy.on_candle(candle);
+    }
+
+    let mut cash = config.capital;
+    let mut position: Option<OpenPosition> = None;
+    let mut trades = Vec::new();
+    let mut equity = Vec::with_capacity(candles.len());
+
+    for candle in candles {
+        if let Some(signal) = pending.take() {
+            let wanted = match signal {
+                Signal::EnterLong => Some(Side::Buy),
+                Signal::EnterShort => Some(Side::Sell),
+                Signal::Exit => None,
+            };
+            if position.as_ref().map(|p| p.side) != wanted {
+                if let Some(open) = position.take() {
+                    // The entry fee already left the account when entering
+                    let entry_fee = open.entry_fee;
+                    let trade = close_trade(open, candle, candle.open, config);
+                    cash += trade.pnl + entry_fee;
+                    trades.push(trade);
+                }
+                if let Some(side) = wanted {
+                    // Size so the entry fee still fits in the account
+                    let estimate = cash / (candle.open * (1.0 + config.fees.taker));
+                    let price = config.fees.taker_price(
+                        side,
+                        candle.open,
+                        estimate,
+                        config.tick_size,
+                        candle.volume,
+                    );
+                    let qty = cash / (price * (1.0 + config.fees.taker));
+                    let entry_fee = config.fees.fee(price * qty, false);
+                    cash -= entry_fee;
+                    position = Some(OpenPosition {
+                        side,
+                        entry_time: candle.start,
+                        entry_price: price,
+                        qty,
+                        entry_fee,
+                    });
+                }
+            }
+        }
+
+        let marked = cash + position.as_ref().map_or(0.0, |p| p.pnl_at(candle.close));
+        equity.push((candle.start, marked));
+        pending = strategy.on_candle(candle);
+    }
+
+    if let (Some(open), Some(last)) = (position.take(), candles.last()) {
+        let entry_fee = open.entry_fee;
+        let trade = close_trade(open, last, last.close, config);
+        if let Some(point) = equity.last_mut() {
+            // The final exit's costs belong in the final equity too
+            point.1 = cash + trade.pnl + entry_fee;
+        }
+        trades.push(trade);
+    }
+
+    let metrics = Metrics::compute(&trades, &equity, periods_per_year(candles));
+    BacktestResult {
+        strategy: strategy.name().to_string(),
+        trades,
+        equity,
+        metrics,
+    }
+}
+
+/// Exits `open` at `quote` within `candle`, after slippage and fees.
+fn close_trade(
+    open: OpenPosition,
+    candle: &Candle,
+    quote: f64,
+    config: &BacktestConfig,
+) -> ClosedTrade {
+    let exit_side = match open.side {
+        Side::Buy => Side::Sell,
+        Side::Sell => Side::Buy,
+    };
+    let price =
+        config
+            .fees
+            .taker_price(exit_side, quote, open.qty, config.tick_size, candle.volume);
+    let exit_fee = config.fees.fee(price * open.qty, false);
+    let fees = open.entry_fee + exit_fee;
+    ClosedTrade {
+        pnl: open.pnl_at(price) - fees,
+        side: open.side,
+        entry_time: open.entry_time,
+        entry_price: open.entry_price,
+        exit_time: candle.start,
+        exit_price: price,
+        qty: open.qty,
+        fees,
+    }
+}
+
+/// Candles per year, from the spacing of the first two.
+pub(crate) fn periods_per_year(candles: &[Candle]) -> f64 {
+    match candles {
+        [first, second, ..] if second.start > first.start => {
+            YEAR_MS / (second.start - first.start) as f64
+        }
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hands out a fixed signal per candle.
+    struct Script(std::vec::IntoIter<Option<Signal>>);
+
+    impl Strategy for Script {
+        fn name(&self) -> &str {
+            "script"
+        }
+
+        fn on_candle(&mut self, _candle: &Candle) -> Option<Signal> {
+            self.0.next().flatten()
+        }
+    }
+
+    fn candle(start: i64, open: f64, close: f64) -> Candle {
+        Candle {
+            start,
+            open,
+            high: open.max(close),
+            low: open.min(close),
+            close,
+            volume: 0.0,
+            turnover: 0.0,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn entry_fee_is_paid_once() {
+        let candles = [
+            candle(0, 90.0, 100.0),
+            candle(60_000, 100.0, 100.0),
+            candle(120_000, 100.0, 110.0),
+            candle(180_000, 120.0, 120.0),
+        ];
+        // Enter on the first traded candle's close, exit on the next
+        let mut strategy =
+            Script(vec![None, Some(Signal::EnterLong), Some(Signal::Exit), None].into_iter());
+        let config = BacktestConfig {
+            capital: 1000.0,
+            fees: FeeModel {
+                maker: 0.0,
+                taker: 0.001,
+                slippage: crate::fees::Slippage::Ticks(0.0),
+            },
+            tick_size: 0.1,
+        };
+
+        let result = run_from(&candles, 1, &mut strategy, &config);
+
+        // Filled at the open after each signal: in at 100, out at 120
+        let qty = 1000.0 / (100.0 * 1.001);
+        let entry_fee = 100.0 * qty * 0.001;
+        let exit_fee = 120.0 * qty * 0.001;
+        let [trade] = result.trades.as_slice() else {
+            panic!("expected one trade, got {:?}", result.trades);
+        };
+        assert_close(trade.qty, qty);
+        assert_close(trade.fees, entry_fee + exit_fee);
+        assert_close(trade.pnl, 20.0 * qty - entry_fee - exit_fee);
+
+        let equity: Vec<f64> = result.equity.iter().map(|(_, value)| *value).collect();
+        assert_eq!(equity.len(), 3);
+        assert_close(equity[0], 1000.0);
+        assert_close(equity[1], 1000.0 - entry_fee + 10.0 * qty);
+        assert_close(equity[2], 1000.0 + trade.pnl);
+    }
+}
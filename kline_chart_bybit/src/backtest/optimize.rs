@@ -0,0 +1,89 @@
+use super::{run, BacktestConfig, Metrics};
+use crate::{
+    models::Candle,
+    strategy::{StrategyKind, StrategyParams},
+};
+use clap::ValueEnum;
+use rayon::prelude::*;
+
+/// Metric the optimizer ranks parameter sets by, higher being better.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Objective {
+    Sharpe,
+    Return,
+    ProfitFactor,
+    /// Smallest max drawdown
+    Drawdown,
+    WinRate,
+}
+
+impl Objective {
+    pub fn score(self, metrics: &Metrics) -> f64 {
+        match self {
+            Objective::Sharpe => metrics.sharpe,
+            Objective::Return => metrics.total_return,
+            Objective::ProfitFactor => metrics.profit_factor,
+            Objective::Drawdown => -metrics.max_drawdown,
+            Objective::WinRate => metrics.win_rate,
+        }
+    }
+}
+
+/// Parses an inclusive `start:end:step` range, or a single value.
+pub fn parse_range(spec: &str) -> Result<Vec<usize>, String> {
+    let parts: Vec<&str> = spec.split(':').collect();
+    let number = |s: &str| {
+        s.parse::<usize>()
+            .map_err(|_| format!("invalid number '{}' in range '{}'", s, spec))
+    };
+    let (start, end, step) = match parts.as_slice() {
+        [value] => (number(value)?, number(value)?, 1),
+        [start, end] => (number(start)?, number(end)?, 1),
+        [start, end, step] => (number(start)?, number(end)?, number(step)?),
+        _ => return Err(format!("range '{}' must be start:end[:step]", spec)),
+    };
+    if step == 0 || start > end {
+        return Err(format!("range '{}' is empty", spec));
+    }
+    Ok((start..=end).step_by(step).collect())
+}
+
+#[derive(Debug, Clone)]
+pub struct GridResult {
+    pub params: StrategyParams,
+    pub metrics: Metrics,
+    pub score: f64,
+}
+
+/// Backtests `kind` with every fast/slow combination (fast below slow) on all
+/// cores and returns the results best first.
+pub fn grid_search(
+    candles: &[Candle],
+    kind: StrategyKind,
+    fast: &[usize],
+    slow: &[usize],
+    config: &BacktestConfig,
+    objective: Objective,
+) -> Vec<GridResult> {
+    let grid: Vec<StrategyParams> = fast
+        .iter()
+        .flat_map(|&f| slow.iter().map(move |&s| (f, s)))
+        .filter(|(f, s)| f < s)
+        .map(|(fast, slow)| StrategyParams { fast, slow })
+        .collect();
+
+    let mut results: Vec<GridResult> = grid
+        .into_par_iter()
+        .map(|params| {
+            let mut strategy = kind.build(&params);
+            let metrics = run(candles, strategy.as_mut(), config).metrics;
+            GridResult {
+                score: objective.score(&metrics),
+                params,
+                metrics,
+            }
+        })
+        .collect();
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results
+}
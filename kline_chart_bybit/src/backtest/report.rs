@@ -0,0 +1,219 @@
+use super::BacktestResult;
+use crate::models::Side;
+use chrono::{TimeZone, Utc};
+use std::{fmt::Write as _, fs, io, path::Path};
+
+const SVG_WIDTH: f64 = 800.0;
+const SVG_HEIGHT: f64 = 240.0;
+
+/// Return of each calendar month (UTC) as `("YYYY-MM", percent)`, measured
+/// from the previous month's closing equity.
+pub fn monthly_returns(equity: &[(i64, f64)]) -> Vec<(String, f64)> {
+    let mut months: Vec<(String, f64, f64)> = Vec::new();
+    let mut previous_close = equity.first().map(|(_, e)| *e).unwrap_or_default();
+    for (time, value) in equity {
+        let month = format_time(*time, "%Y-%m");
+        match months.last_mut() {
+            Some(last) if last.0 == month => last.2 = *value,
+            _ => {
+                if let Some(last) = months.last() {
+                    previous_close = last.2;
+                }
+                months.push((month, previous_close, *value));
+            }
+        }
+    }
+    months
+        .into_iter()
+        .map(|(month, open, close)| (month, (close / open - 1.0) * 100.0))
+        .collect()
+}
+
+fn format_time(timestamp_ms: i64, format: &str) -> String {
+    Utc.timestamp_millis_opt(timestamp_ms)
+        .single()
+        .map(|t| t.format(format).to_string())
+        .unwrap_or_default()
+}
+
+/// The equity curve as a standalone SVG line chart.
+pub fn equity_svg(equity: &[(i64, f64)]) -> String {
+    let (Some(first), Some(last)) = (equity.first(), equity.last()) else {
+        return String::new();
+    };
+    let (min, max) = equity
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(lo, hi), (_, e)| {
+            (lo.min(*e), hi.max(*e))
+        });
+    let span_x = ((last.0 - first.0) as f64).max(1.0);
+    let span_y = (max - min).max(f64::EPSILON);
+    let points: Vec<String> = equity
+        .iter()
+        .map(|(t, e)| {
+            let x = (t - first.0) as f64 / span_x * SVG_WIDTH;
+            let y = SVG_HEIGHT - (e - min) / span_y * SVG_HEIGHT;
+            format!("{:.1},{:.1}", x, y)
+        })
+        .collect();
+    let color = if last.1 >= first.1 { "#2a2" } else { "#c22" };
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {w} {h}\" width=\"{w}\" height=\"{h}\">\
+         <rect width=\"100%\" height=\"100%\" fill=\"#fff\"/>\
+         <polyline fill=\"none\" stroke=\"{color}\" stroke-width=\"1.5\" points=\"{points}\"/>\
+         <text x=\"4\" y=\"14\" font-size=\"12\">{max:.2}</text>\
+         <text x=\"4\" y=\"{bottom}\" font-size=\"12\">{min:.2}</text></svg>\n",
+        w = SVG_WIDTH,
+        h = SVG_HEIGHT,
+        points = points.join(" "),
+        bottom = SVG_HEIGHT - 4.0,
+    )
+}
+
+fn side_name(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "long",
+        Side::Sell => "short",
+    }
+}
+
+/// Writes a backtest report to `path`: HTML for `.html`/`.htm`, markdown
+/// otherwise. The markdown report puts its equity chart in a sibling `.svg`.
+pub fn write_report(
+    path: &Path,
+    result: &BacktestResult,
+    parameters: &[(String, String)],
+) -> io::Result<()> {
+    let html = matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("html") | Some("htm")
+    );
+    let text = if html {
+        html_report(result, parameters)
+    } else {
+        let svg_path = path.with_extension("svg");
+        fs::write(&svg_path, equity_svg(&result.equity))?;
+        let svg_name = svg_path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        markdown_report(result, parameters, &svg_name)
+    };
+    fs::write(path, text)
+}
+
+fn summary_rows(result: &BacktestResult) -> Vec<(&'static str, String)> {
+    let m = &result.metrics;
+    let fees: f64 = result.trades.iter().map(|t| t.fees).sum();
+    vec![
+        ("Total return", format!("{:+.2}%", m.total_return)),
+        ("Trades", m.trades.to_string()),
+        ("Win rate", format!("{:.1}%", m.win_rate)),
+        ("Profit factor", format!("{:.2}", m.profit_factor)),
+        ("Max drawdown", format!("{:.2}%", m.max_drawdown)),
+        ("Sharpe", format!("{:.2}", m.sharpe)),
+        ("Fees paid", format!("{:.2}", fees)),
+    ]
+}
+
+fn markdown_report(
+    result: &BacktestResult,
+    parameters: &[(String, String)],
+    svg_name: &str,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Backtest: {}\n", result.strategy);
+
+    let _ = writeln!(out, "## Parameters\n\n| Parameter | Value |\n|---|---|");
+    for (name, value) in parameters {
+        let _ = writeln!(out, "| {} | {} |", name, value);
+    }
+
+    let _ = writeln!(out, "\n## Summary\n\n| Metric | Value |\n|---|---|");
+    for (name, value) in summary_rows(result) {
+        let _ = writeln!(out, "| {} | {} |", name, value);
+    }
+
+    let _ = writeln!(out, "\n## Equity curve\n\n![equity]({})", svg_name);
+
+    let _ = writeln!(out, "\n## Monthly returns\n\n| Month | Return |\n|---|---|");
+    for (month, ret) in monthly_returns(&result.equity) {
+        let _ = writeln!(out, "| {} | {:+.2}% |", month, ret);
+    }
+
+    let _ = writeln!(
+        out,
+        "\n## Trades\n\n| # | Side | Entry time | Entry | Exit time | Exit | Qty | Fees | PnL |\n|---|---|---|---|---|---|---|---|---|"
+    );
+    for (i, t) in result.trades.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {:.2} | {} | {:.2} | {:.4} | {:.2} | {:+.2} |",
+            i + 1,
+            side_name(t.side),
+            format_time(t.entry_time, "%Y-%m-%d %H:%M"),
+            t.entry_price,
+            format_time(t.exit_time, "%Y-%m-%d %H:%M"),
+            t.exit_price,
+            t.qty,
+            t.fees,
+            t.pnl
+        );
+    }
+    out
+}
+
+fn html_report(result: &BacktestResult, parameters: &[(String, String)]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Backtest: {name}</title>\
+         <style>body{{font-family:sans-serif}}table{{border-collapse:collapse}}\
+         td,th{{border:1px solid #ccc;padding:2px 8px;text-align:right}}</style></head><body>\n\
+         <h1>Backtest: {name}</h1>",
+        name = result.strategy
+    );
+
+    let _ = writeln!(out, "<h2>Parameters</h2><table>");
+    for (name, value) in parameters {
+        let _ = writeln!(out, "<tr><th>{}</th><td>{}</td></tr>", name, value);
+    }
+    let _ = writeln!(out, "</table><h2>Summary</h2><table>");
+    for (name, value) in summary_rows(result) {
+        let _ = writeln!(out, "<tr><th>{}</th><td>{}</td></tr>", name, value);
+    }
+    let _ = writeln!(out, "</table><h2>Equity curve</h2>");
+    out.push_str(&equity_svg(&result.equity));
+
+    let _ = writeln!(
+        out,
+        "<h2>Monthly returns</h2><table><tr><th>Month</th><th>Return</th></tr>"
+    );
+    for (month, ret) in monthly_returns(&result.equity) {
+        let _ = writeln!(out, "<tr><td>{}</td><td>{:+.2}%</td></tr>", month, ret);
+    }
+
+    let _ = writeln!(
+        out,
+        "</table><h2>Trades</h2><table><tr><th>#</th><th>Side</th><th>Entry time</th><th>Entry</th>\
+         <th>Exit time</th><th>Exit</th><th>Qty</th><th>Fees</th><th>PnL</th></tr>"
+    );
+    for (i, t) in result.trades.iter().enumerate() {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.2}</td><td>{}</td><td>{:.2}</td>\
+             <td>{:.4}</td><td>{:.2}</td><td>{:+.2}</td></tr>",
+            i + 1,
+            side_name(t.side),
+            format_time(t.entry_time, "%Y-%m-%d %H:%M"),
+            t.entry_price,
+            format_time(t.exit_time, "%Y-%m-%d %H:%M"),
+            t.exit_price,
+            t.qty,
+            t.fees,
+            t.pnl
+        );
+    }
+    let _ = writeln!(out, "</table></body></html>");
+    out
+}
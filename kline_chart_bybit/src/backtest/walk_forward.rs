@@ -0,0 +1,109 @@
+use super::{
+    grid_search, periods_per_year, run_from, BacktestConfig, ClosedTrade, Metrics, Objective,
+};
+use crate::{
+    models::Candle,
+    strategy::{StrategyKind, StrategyParams},
+};
+
+/// One in-sample optimization followed by its out-of-sample run.
+#[derive(Debug, Clone)]
+pub struct Fold {
+    /// Start of the out-of-sample window.
+    pub start: i64,
+    pub end: i64,
+    pub params: StrategyParams,
+    pub in_sample: Metrics,
+    pub out_of_sample: Metrics,
+}
+
+#[derive(Debug, Clone)]
+pub struct WalkForward {
+    pub folds: Vec<Fold>,
+    /// Out-of-sample trades of every fold, in order.
+    pub trades: Vec<ClosedTrade>,
+    /// Out-of-sample equity, compounded from fold to fold.
+    pub equity: Vec<(i64, f64)>,
+    /// Statistics of the stitched out-of-sample trades and equity.
+    pub metrics: Metrics,
+}
+
+/// Parses the `IN:OUT` window lengths, in candles.
+pub fn parse_windows(spec: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("walk-forward windows '{}' must be IN:OUT candles", spec);
+    let (in_sample, out_of_sample) = spec.split_once(':').ok_or_else(invalid)?;
+    let in_sample: usize = in_sample.parse().map_err(|_| invalid())?;
+    let out_of_sample: usize = out_of_sample.parse().map_err(|_| invalid())?;
+    if in_sample == 0 || out_of_sample == 0 {
+        return Err(invalid());
+    }
+    Ok((in_sample, out_of_sample))
+}
+
+/// Rolls an `in_sample` window over `candles` in steps of `out_of_sample`:
+/// each step grid-searches the in-sample candles and trades the best
+/// parameters on the following, unseen, out-of-sample candles.
+///
+/// Only the out-of-sample results are aggregated, so the statistics reflect
+/// parameters that were chosen without looking at the data they ran on.
+#[allow(clippy::too_many_arguments)]
+pub fn walk_forward(
+    candles: &[Candle],
+    kind: StrategyKind,
+    fast: &[usize],
+    slow: &[usize],
+    config: &BacktestConfig,
+    objective: Objective,
+    in_sample: usize,
+    out_of_sample: usize,
+) -> WalkForward {
+    let mut folds = Vec::new();
+    let mut trades = Vec::new();
+    let mut equity: Vec<(i64, f64)> = Vec::new();
+    let mut capital = config.capital;
+
+    let mut start = 0;
+    while start + in_sample < candles.len() {
+        let end = (start + in_sample + out_of_sample).min(candles.len());
+        let training = &candles[start..start + in_sample];
+        let Some(best) = grid_search(training, kind, fast, slow, config, objective)
+            .into_iter()
+            .next()
+        else {
+            break;
+        };
+
+        // The in-sample candles warm up the indicators without trading
+        let mut strategy = kind.build(&best.params);
+        let fold_config = BacktestConfig {
+            capital,
+            ..config.clone()
+        };
+        let result = run_from(
+            &candles[start..end],
+            in_sample,
+            strategy.as_mut(),
+            &fold_config,
+        );
+        capital = result.equity.last().map_or(capital, |(_, value)| *value);
+
+        folds.push(Fold {
+            start: candles[start + in_sample].start,
+            end: candles[end - 1].start,
+            params: best.params,
+            in_sample: best.metrics,
+            out_of_sample: result.metrics,
+        });
+        trades.extend(result.trades);
+        equity.extend(result.equity);
+        start += out_of_sample;
+    }
+
+    let metrics = Metrics::compute(&trades, &equity, periods_per_year(candles));
+    WalkForward {
+        folds,
+        trades,
+        equity,
+        metrics,
+    }
+}
@@ -0,0 +1,346 @@
+use crate::{
+    backtest::Objective,
+    export::DataFormat,
+    import::{ImportFormat, TimeFormat},
+    journal::ExportFormat,
+    models::SpreadOp,
+    replay,
+    sinks::arrow::ArrowTarget,
+    strategy::{LiveMode, StrategyKind, StrategyParams},
+    ui::GraphicsMode,
+};
+use chrono::NaiveDate;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::{net::SocketAddr, path::PathBuf};
+
+/// Live Bybit candlestick chart in the terminal.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Symbol to chart; defaults to [chart] symbol in the config file
+    #[arg(long)]
+    pub symbol: Option<String>,
+
+    /// Kline interval to chart, e.g. 1, 15 or 60; defaults to [chart] interval in the config file
+    #[arg(long)]
+    pub interval: Option<String>,
+
+    /// Overlay a second symbol's closes, normalized to percent change
+    #[arg(long, value_name = "SYMBOL")]
+    pub compare: Option<String>,
+
+    /// Chart the spread between two symbols instead, e.g. ETHUSDT/BTCUSDT
+    #[arg(long, value_name = "BASE/QUOTE")]
+    pub spread: Option<String>,
+
+    /// How the spread legs are combined
+    #[arg(long, value_enum, default_value = "ratio", requires = "spread")]
+    pub spread_op: SpreadOp,
+
+    /// Render the chart as an image on terminals with kitty or sixel graphics
+    #[arg(long, value_enum, default_value = "auto")]
+    pub graphics: GraphicsMode,
+
+    /// Simulate fills against the live stream with a virtual balance instead of trading
+    #[arg(
+        long,
+        value_name = "BALANCE",
+        num_args = 0..=1,
+        default_missing_value = "10000"
+    )]
+    pub paper: Option<f64>,
+
+    /// Run a strategy on the live candles and draw its signals on the chart
+    #[arg(long, value_enum)]
+    pub strategy: Option<StrategyKind>,
+
+    #[command(flatten)]
+    pub strategy_params: StrategyParams,
+
+    /// Only draw the strategy's signals, or also trade them
+    #[arg(long, value_enum, default_value = "dry-run", requires = "strategy")]
+    pub live_strategy: LiveMode,
+
+    /// Quantity per strategy entry (defaults to the hotkey size)
+    #[arg(long, value_name = "QTY", requires = "strategy")]
+    pub strategy_qty: Option<f64>,
+
+    /// Largest position the strategy may hold (defaults to its entry quantity)
+    #[arg(long, value_name = "QTY", requires = "strategy")]
+    pub max_position: Option<f64>,
+
+    /// Feed a recorded session (frames from --record, or candles as JSON lines) instead of the live stream
+    #[arg(long, value_name = "PATH")]
+    pub replay: Option<PathBuf>,
+
+    /// Replay speed relative to the recording, e.g. 10x, 0.5x or max
+    #[arg(long, default_value = "1x", value_parser = replay::parse_speed, requires = "replay")]
+    pub speed: f64,
+
+    /// Append every raw websocket frame with its receive time to this file, for --replay
+    #[arg(long, value_name = "PATH", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Serve the chart's candles, indicators and status as JSON over HTTP on this address
+    #[arg(long, value_name = "ADDR", conflicts_with = "headless")]
+    pub serve: Option<SocketAddr>,
+
+    /// Serve SubscribeCandles over gRPC on this address, from the candle store and live
+    #[arg(long, value_name = "ADDR", conflicts_with = "headless")]
+    pub grpc: Option<SocketAddr>,
+
+    /// Also write confirmed candles as an Arrow IPC stream to this file, or unix:<path> for a socket
+    #[arg(long, value_name = "PATH", value_parser = ArrowTarget::parse)]
+    pub arrow_stream: Option<ArrowTarget>,
+
+    /// Skip the TUI and print confirmed candles with their indicators to stdout as NDJSON
+    #[arg(long)]
+    pub headless: bool,
+
+    /// API-key profile from the config file to trade with
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Prompt for an API key and secret, save them in the OS keyring and exit
+    #[arg(long)]
+    pub store_keys: bool,
+
+    /// Like --store-keys, but into a passphrase-encrypted file for machines without a keyring
+    #[arg(long, conflicts_with = "store_keys")]
+    pub encrypt_keys: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Replay historical candles through a strategy and print its metrics
+    Backtest(BacktestArgs),
+    /// Backtest a strategy over a grid of parameters and rank the results
+    Optimize(OptimizeArgs),
+    /// Download candles or recent trades and write them to CSV or Parquet
+    Export(ExportArgs),
+    /// Download a long range of candles into the candle store or a CSV for backtests
+    Download(DownloadArgs),
+    /// Load candles from a CSV or JSON file of any layout into the candle store
+    Import(ImportArgs),
+    /// Export or annotate the trade journal
+    #[command(subcommand)]
+    Journal(JournalCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum JournalCommand {
+    /// Write the journal to a file or stdout
+    Export {
+        #[arg(long, value_enum, default_value = "csv")]
+        format: ExportFormat,
+
+        /// Output file instead of stdout
+        #[arg(long, value_name = "PATH")]
+        out: Option<PathBuf>,
+
+        /// Only trades closed on or after this date (YYYY-MM-DD)
+        #[arg(long, value_name = "DATE")]
+        since: Option<NaiveDate>,
+
+        /// Only trades from live, paper or backtest
+        #[arg(long)]
+        source: Option<String>,
+    },
+    /// Attach a note to the most recent trade
+    Note { text: String },
+}
+
+/// Where backtests get their candles from.
+#[derive(Debug, Args)]
+pub struct CandleSourceArgs {
+    /// Symbol to download; defaults to [chart] symbol in the config file
+    #[arg(long)]
+    pub symbol: Option<String>,
+
+    /// Candles CSV to replay instead of downloading history
+    #[arg(long, value_name = "PATH")]
+    pub file: Option<PathBuf>,
+
+    /// Use every candle of the series in the candle store, e.g. from `download` or `import`
+    #[arg(long, conflicts_with = "file")]
+    pub from_store: bool,
+
+    /// Kline interval to download, e.g. 1, 15, 60 or D
+    #[arg(long, default_value = "60")]
+    pub interval: String,
+
+    /// Days of history to download
+    #[arg(long, default_value_t = 30)]
+    pub days: i64,
+}
+
+#[derive(Debug, Args)]
+pub struct BacktestArgs {
+    #[command(flatten)]
+    pub source: CandleSourceArgs,
+
+    #[arg(long, value_enum, default_value = "hold")]
+    pub strategy: StrategyKind,
+
+    #[command(flatten)]
+    pub strategy_params: StrategyParams,
+
+    /// Starting balance in USDT
+    #[arg(long, default_value_t = 10_000.0)]
+    pub capital: f64,
+
+    /// Ignore the configured fees and slippage
+    #[arg(long)]
+    pub no_fees: bool,
+
+    /// Also write a report (HTML for .html, otherwise markdown) to this file
+    #[arg(long, value_name = "PATH")]
+    pub report: Option<PathBuf>,
+
+    /// Append the trades to the trade journal
+    #[arg(long)]
+    pub journal: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct OptimizeArgs {
+    #[command(flatten)]
+    pub source: CandleSourceArgs,
+
+    #[arg(long, value_enum, default_value = "ma-cross")]
+    pub strategy: StrategyKind,
+
+    /// Fast periods to try, as start:end:step
+    #[arg(long, default_value = "5:30:5")]
+    pub fast: String,
+
+    /// Slow periods to try, as start:end:step
+    #[arg(long, default_value = "20:120:10")]
+    pub slow: String,
+
+    /// Metric to rank by
+    #[arg(long, value_enum, default_value = "sharpe")]
+    pub metric: Objective,
+
+    /// How many of the best parameter sets to print
+    #[arg(long, default_value_t = 10)]
+    pub top: usize,
+
+    /// Walk forward instead: optimize on IN candles, trade the best on the next OUT, roll
+    #[arg(long, value_name = "IN:OUT")]
+    pub walk_forward: Option<String>,
+
+    #[arg(long, default_value_t = 10_000.0)]
+    pub capital: f64,
+
+    /// Ignore the configured fees and slippage
+    #[arg(long)]
+    pub no_fees: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Defaults to [chart] symbol in the config file
+    #[arg(long)]
+    pub symbol: Option<String>,
+
+    /// Kline interval, e.g. 1, 15, 60 or D
+    #[arg(long, default_value = "1")]
+    pub interval: String,
+
+    /// First day to export (YYYY-MM-DD); defaults to --days before --to
+    #[arg(long, value_name = "DATE")]
+    pub from: Option<NaiveDate>,
+
+    /// Day to stop before (YYYY-MM-DD); defaults to now
+    #[arg(long, value_name = "DATE")]
+    pub to: Option<NaiveDate>,
+
+    /// Days of history when --from is not given
+    #[arg(long, default_value_t = 1)]
+    pub days: i64,
+
+    /// Output file; defaults to <symbol>-<interval>-<from>-<to>.<format> in the export directory
+    #[arg(long, value_name = "PATH")]
+    pub out: Option<PathBuf>,
+
+    /// File format; defaults to export_format from the config file
+    #[arg(long, value_enum)]
+    pub format: Option<DataFormat>,
+
+    /// Also export the moving average over this many candles; repeat for several
+    #[arg(long, value_name = "WINDOW", conflicts_with = "trades")]
+    pub ma: Vec<usize>,
+
+    /// Export the latest public trades instead of candles
+    #[arg(long)]
+    pub trades: bool,
+}
+
+/// Where `download` writes its candles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DownloadFormat {
+    /// A CSV that `backtest --file` reads
+    Csv,
+    /// The SQLite candle store the chart starts from
+    Sqlite,
+}
+
+#[derive(Debug, Args)]
+pub struct DownloadArgs {
+    /// Defaults to [chart] symbol in the config file
+    #[arg(long)]
+    pub symbol: Option<String>,
+
+    /// Kline interval, e.g. 15m, 1h, 1d, or Bybit's own 15, 60, D
+    #[arg(long, default_value = "1h")]
+    pub interval: String,
+
+    /// First day to download (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    pub from: NaiveDate,
+
+    /// Day to stop before (YYYY-MM-DD); defaults to now
+    #[arg(long, value_name = "DATE")]
+    pub to: Option<NaiveDate>,
+
+    #[arg(long, value_enum, default_value = "sqlite")]
+    pub format: DownloadFormat,
+
+    /// Output file; defaults to the candle store, or <symbol>-<interval>-<from>-<to>.csv in the export directory
+    #[arg(long, value_name = "PATH")]
+    pub out: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportArgs {
+    /// CSV or JSON file to read
+    pub path: PathBuf,
+
+    /// Symbol the candles belong to; defaults to [chart] symbol in the config file
+    #[arg(long)]
+    pub symbol: Option<String>,
+
+    /// Kline interval of the file, e.g. 15m, 1h or 60
+    #[arg(long)]
+    pub interval: String,
+
+    /// File layout; defaults to the file extension
+    #[arg(long, value_enum)]
+    pub format: Option<ImportFormat>,
+
+    /// Source column of a candle field, e.g. start=timestamp or close=Close; repeat for several
+    #[arg(long = "column", value_name = "FIELD=COLUMN")]
+    pub columns: Vec<String>,
+
+    /// Start column format: auto, ms, s, rfc3339 or a pattern like "%Y-%m-%d %H:%M"
+    #[arg(long, default_value = "auto")]
+    pub time_format: TimeFormat,
+
+    /// Candle store to import into; defaults to the one the chart uses
+    #[arg(long, value_name = "PATH")]
+    pub store: Option<PathBuf>,
+}
@@ -0,0 +1,417 @@
+use crate::{
+    alerts::Channel,
+    constants::{
+        MA_WINDOW_SIZE, PRIVATE_WEBSOCKET_URL, REST_URL, STREAM_INTERVAL, SYMBOL,
+        TESTNET_PRIVATE_WEBSOCKET_URL, TESTNET_REST_URL, VISIBLE_RANGE, WEBSOCKET_URL,
+    },
+    credentials::load_keys,
+    export::DataFormat,
+    fees::FeeModel,
+    paths::{config_dir, data_dir},
+    secrets::{SecretsFile, StoredKeys},
+    sinks::{
+        discord::DiscordConfig, email::EmailConfig, kafka::KafkaConfig, mqtt::MqttConfig,
+        redis::RedisConfig, telegram::TelegramConfig, webhook::WebhookConfig,
+    },
+    ui::ChartColors,
+};
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::{collections::BTreeMap, env, fs, io, path::PathBuf, str::FromStr, sync::OnceLock};
+
+/// Name the top-level `[api]` table goes by among the profiles.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Credentials of one profile. Leave `key` and `secret` out of the file to
+/// read them from the OS keyring instead (see `--store-keys`).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ApiConfig {
+    #[serde(default)]
+    pub key: String,
+    #[serde(default)]
+    pub secret: String,
+    #[serde(default)]
+    pub testnet: bool,
+}
+
+/// One-key trading shortcuts, active only while armed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HotkeysConfig {
+    /// Toggles the hotkeys between armed and disarmed.
+    pub arm: char,
+    pub buy: char,
+    pub sell: char,
+    /// Closes the whole position in the chart symbol at market.
+    pub close: char,
+    pub cancel_all: char,
+    /// Quantity of the buy/sell hotkeys.
+    pub size: f64,
+}
+
+impl Default for HotkeysConfig {
+    fn default() -> Self {
+        Self {
+            arm: 'A',
+            buy: 'B',
+            sell: 'S',
+            close: 'X',
+            cancel_all: 'C',
+            size: 0.01,
+        }
+    }
+}
+
+/// What the live chart shows; `--symbol` and `--interval` override it.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ChartConfig {
+    pub symbol: String,
+    /// Kline interval in Bybit notation, e.g. `1`, `15` or `D`.
+    pub interval: String,
+    /// Candles kept on the chart, and the widest zoom.
+    pub visible_range: usize,
+    /// Period of the moving average.
+    pub ma_window: usize,
+}
+
+impl Default for ChartConfig {
+    fn default() -> Self {
+        Self {
+            symbol: SYMBOL.to_string(),
+            interval: STREAM_INTERVAL.to_string(),
+            visible_range: VISIBLE_RANGE,
+            ma_window: MA_WINDOW_SIZE,
+        }
+    }
+}
+
+/// Chart colors as names (`green`, `lightred`) or `#rrggbb`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ColorsConfig {
+    pub up: String,
+    pub down: String,
+    pub ma: String,
+}
+
+impl Default for ColorsConfig {
+    fn default() -> Self {
+        Self {
+            up: String::from("green"),
+            down: String::from("red"),
+            ma: String::from("yellow"),
+        }
+    }
+}
+
+impl ColorsConfig {
+    pub fn chart_colors(&self) -> io::Result<ChartColors> {
+        let parse = |name: &str| {
+            Color::from_str(name).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unknown color '{}' in {}", name, Config::path().display()),
+                )
+            })
+        };
+        Ok(ChartColors {
+            up: parse(&self.up)?,
+            down: parse(&self.down)?,
+            ma: parse(&self.ma)?,
+        })
+    }
+}
+
+/// Exchange URLs, e.g. to point the app at a proxy.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Endpoints {
+    pub public_ws: String,
+    pub private_ws: String,
+    pub testnet_private_ws: String,
+    pub rest: String,
+    pub testnet_rest: String,
+}
+
+impl Default for Endpoints {
+    fn default() -> Self {
+        Self {
+            public_ws: WEBSOCKET_URL.to_string(),
+            private_ws: PRIVATE_WEBSOCKET_URL.to_string(),
+            testnet_private_ws: TESTNET_PRIVATE_WEBSOCKET_URL.to_string(),
+            rest: REST_URL.to_string(),
+            testnet_rest: TESTNET_REST_URL.to_string(),
+        }
+    }
+}
+
+static ENDPOINTS: OnceLock<Endpoints> = OnceLock::new();
+
+impl Endpoints {
+    /// Makes these the URLs every connection uses. Only the first call counts.
+    pub fn install(self) {
+        let _ = ENDPOINTS.set(self);
+    }
+}
+
+/// The installed endpoints, or Bybit's own if none were.
+pub fn endpoints() -> &'static Endpoints {
+    ENDPOINTS.get_or_init(Endpoints::default)
+}
+
+/// Chart commands that can be rebound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartKey {
+    Quit,
+    Trades,
+    Ladder,
+    Positions,
+    Wallet,
+    Executions,
+    Orders,
+    Equity,
+    Export,
+    ZoomIn,
+    ZoomOut,
+    ToggleMa,
+}
+
+/// `[alerts]` in the config file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AlertsConfig {
+    /// Channel sets new alerts can be routed to, e.g.
+    /// `[["telegram"], ["telegram", "email"]]`; the first is the default.
+    /// Empty offers every configured channel together, then each alone.
+    pub routes: Vec<Vec<Channel>>,
+}
+
+impl AlertsConfig {
+    /// The routes usable with the `available` channels.
+    pub fn routes(&self, available: &[Channel]) -> Vec<Vec<Channel>> {
+        if self.routes.is_empty() {
+            let mut routes = vec![available.to_vec()];
+            if available.len() > 1 {
+                routes.extend(available.iter().map(|&channel| vec![channel]));
+            }
+            return routes;
+        }
+        self.routes
+            .iter()
+            .map(|route| {
+                route
+                    .iter()
+                    .copied()
+                    .filter(|channel| available.contains(channel))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Keys of the chart commands. Trading shortcuts live in `[hotkeys]`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    pub quit: char,
+    pub trades: char,
+    pub ladder: char,
+    pub positions: char,
+    pub wallet: char,
+    pub executions: char,
+    pub orders: char,
+    pub equity: char,
+    pub export: char,
+    pub zoom_in: char,
+    pub zoom_out: char,
+    pub toggle_ma: char,
+}
+
+impl Default for KeysConfig {
+    fn default() -> Self {
+        Self {
+            quit: 'q',
+            trades: 't',
+            ladder: 'd',
+            positions: 'p',
+            wallet: 'b',
+            executions: 'e',
+            orders: 'O',
+            equity: 'E',
+            export: 'x',
+            zoom_in: '+',
+            zoom_out: '-',
+            toggle_ma: 'M',
+        }
+    }
+}
+
+impl KeysConfig {
+    /// The command bound to `key`, if any.
+    pub fn lookup(&self, key: char) -> Option<ChartKey> {
+        [
+            (self.quit, ChartKey::Quit),
+            (self.trades, ChartKey::Trades),
+            (self.ladder, ChartKey::Ladder),
+            (self.positions, ChartKey::Positions),
+            (self.wallet, ChartKey::Wallet),
+            (self.executions, ChartKey::Executions),
+            (self.orders, ChartKey::Orders),
+            (self.equity, ChartKey::Equity),
+            (self.export, ChartKey::Export),
+            (self.zoom_in, ChartKey::ZoomIn),
+            (self.zoom_out, ChartKey::ZoomOut),
+            (self.toggle_ma, ChartKey::ToggleMa),
+        ]
+        .into_iter()
+        .find(|(bound, _)| *bound == key)
+        .map(|(_, command)| command)
+    }
+}
+
+/// Contents of `config.toml` in the user config directory.
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub api: Option<ApiConfig>,
+    /// Extra named accounts, e.g. `[profiles.sub1]`.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ApiConfig>,
+    /// Profile to start with when `--profile` isn't given.
+    #[serde(default)]
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub hotkeys: HotkeysConfig,
+    /// Fees and slippage of paper trading and backtests.
+    #[serde(default)]
+    pub fees: FeeModel,
+    /// Where exports go; defaults to `exports` in the data directory.
+    #[serde(default)]
+    pub export_dir: Option<PathBuf>,
+    /// Format of the chart's export hotkey and the `export` subcommand.
+    #[serde(default)]
+    pub export_format: DataFormat,
+    #[serde(default)]
+    pub chart: ChartConfig,
+    #[serde(default)]
+    pub colors: ColorsConfig,
+    #[serde(default)]
+    pub endpoints: Endpoints,
+    #[serde(default)]
+    pub keys: KeysConfig,
+    /// Publish candles and alerts to Redis when present.
+    #[serde(default)]
+    pub redis: Option<RedisConfig>,
+    /// Produce candles and trades to Kafka when present.
+    #[serde(default)]
+    pub kafka: Option<KafkaConfig>,
+    /// Publish prices, candles and alerts to an MQTT broker when present.
+    #[serde(default)]
+    pub mqtt: Option<MqttConfig>,
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    /// Send fired alerts to a Telegram chat when present.
+    #[serde(default)]
+    pub telegram: Option<TelegramConfig>,
+    /// Post fired alerts to a Discord webhook when present.
+    #[serde(default)]
+    pub discord: Option<DiscordConfig>,
+    /// Email alerts routed to email over SMTP when present.
+    #[serde(default)]
+    pub email: Option<EmailConfig>,
+    /// POST alerts routed to the webhook as templated JSON when present.
+    #[serde(default)]
+    pub webhook: Option<WebhookConfig>,
+    /// Credentials from the unlocked encrypted file, by profile.
+    #[serde(skip)]
+    unlocked: BTreeMap<String, StoredKeys>,
+}
+
+impl Config {
+    pub fn path() -> PathBuf {
+        config_dir().join("config.toml")
+    }
+
+    pub fn export_dir(&self) -> PathBuf {
+        self.export_dir
+            .clone()
+            .unwrap_or_else(|| data_dir().join("exports"))
+    }
+
+    /// Loads the config file; a missing file yields the defaults.
+    pub fn load() -> io::Result<Self> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path)?;
+        toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Makes the decrypted credentials file available to the profiles.
+    pub fn set_secrets(&mut self, secrets: SecretsFile) {
+        self.unlocked = secrets.profiles;
+    }
+
+    /// API credentials, preferring `BYBIT_API_KEY`/`BYBIT_API_SECRET` from the
+    /// environment, then the `[api]` table, then stored keys.
+    pub fn api(&self) -> Option<ApiConfig> {
+        match (env::var("BYBIT_API_KEY"), env::var("BYBIT_API_SECRET")) {
+            (Ok(key), Ok(secret)) => Some(ApiConfig {
+                key,
+                secret,
+                testnet: self.api.as_ref().is_some_and(|api| api.testnet),
+            }),
+            _ => self.with_stored_keys(DEFAULT_PROFILE, self.api.clone().unwrap_or_default()),
+        }
+    }
+
+    /// All configured profile names, the `[api]` table first as [`DEFAULT_PROFILE`].
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        if self.api().is_some() {
+            names.push(DEFAULT_PROFILE.to_string());
+        }
+        names.extend(self.profiles.keys().cloned());
+        names
+    }
+
+    pub fn profile(&self, name: &str) -> Option<ApiConfig> {
+        if name == DEFAULT_PROFILE {
+            self.api()
+        } else {
+            self.with_stored_keys(name, self.profiles.get(name)?.clone())
+        }
+    }
+
+    /// The profile to start with: `requested`, then `default_profile`, then the first one.
+    pub fn initial_profile(&self, requested: Option<&str>) -> io::Result<Option<String>> {
+        let names = self.profile_names();
+        match requested.or(self.default_profile.as_deref()) {
+            Some(name) if names.iter().any(|n| n == name) => Ok(Some(name.to_string())),
+            Some(name) => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "no API profile named '{}' in {}",
+                    name,
+                    Self::path().display()
+                ),
+            )),
+            None => Ok(names.into_iter().next()),
+        }
+    }
+
+    /// Fills in credentials missing from the file, from the encrypted
+    /// credentials file first and then the keyring.
+    fn with_stored_keys(&self, profile: &str, api: ApiConfig) -> Option<ApiConfig> {
+        if !api.key.is_empty() && !api.secret.is_empty() {
+            return Some(api);
+        }
+        let (key, secret) = match self.unlocked.get(profile) {
+            Some(stored) => (stored.key.clone(), stored.secret.clone()),
+            None => load_keys(profile)?,
+        };
+        Some(ApiConfig { key, secret, ..api })
+    }
+}
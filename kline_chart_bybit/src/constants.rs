@@ -1,4 +1,37 @@
 pub const VISIBLE_RANGE: usize = 50;
+/// Fewest candles the chart can be zoomed in to.
+pub const MIN_ZOOM: usize = 10;
 pub const MA_WINDOW_SIZE: usize = 50;
 pub const WEBSOCKET_URL: &str = "wss://stream.bybit.com/v5/public/linear";
 pub const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36";
+pub const SYMBOL: &str = "ETHUSDT";
+/// Kline interval the chart streams, in Bybit notation (minutes).
+pub const STREAM_INTERVAL: &str = "1";
+pub const TRADES_CAPACITY: usize = 200;
+/// Own fills kept for the execution history panel.
+pub const EXECUTIONS_CAPACITY: usize = 500;
+/// Strategy signals kept for drawing on the live chart.
+pub const SIGNALS_CAPACITY: usize = 200;
+/// Equity samples kept for the equity curve, one per `EQUITY_RESOLUTION_MS`.
+pub const EQUITY_CAPACITY: usize = 2000;
+pub const EQUITY_RESOLUTION_MS: i64 = 5_000;
+/// Trades at or above this size (in base currency) are highlighted on the tape.
+pub const LARGE_TRADE_SIZE: f64 = 10.0;
+/// Trades at or above this size are treated as block trades.
+pub const BLOCK_TRADE_SIZE: f64 = 50.0;
+pub const TICK_SIZE: f64 = 0.01;
+pub const ORDERBOOK_DEPTH: usize = 50;
+pub const FIB_RATIOS: [f64; 7] = [0.0, 0.236, 0.382, 0.5, 0.618, 0.786, 1.0];
+pub const PRIVATE_WEBSOCKET_URL: &str = "wss://stream.bybit.com/v5/private";
+pub const TESTNET_PRIVATE_WEBSOCKET_URL: &str = "wss://stream-testnet.bybit.com/v5/private";
+pub const REST_URL: &str = "https://api.bybit.com";
+pub const TESTNET_REST_URL: &str = "https://api-testnet.bybit.com";
+/// How long a signed request stays valid on the exchange side, in milliseconds.
+pub const RECV_WINDOW: u64 = 5000;
+/// Lifetime of the websocket auth signature, in milliseconds.
+pub const AUTH_EXPIRY_MS: i64 = 10_000;
+/// Private topics subscribed right after authenticating.
+pub const PRIVATE_TOPICS: &[&str] = &["position", "wallet", "execution", "order"];
+/// Account type queried for balances; unified trading accounts hold all
+/// derivatives margin.
+pub const ACCOUNT_TYPE: &str = "UNIFIED";
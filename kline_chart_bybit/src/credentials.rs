@@ -0,0 +1,59 @@
+use crate::config::DEFAULT_PROFILE;
+use keyring::Entry;
+use std::io::{self, BufRead, Write};
+
+/// Service name the API keys are filed under in the OS credential store.
+const KEYRING_SERVICE: &str = "kline_chart_bybit";
+
+fn entries(profile: &str) -> keyring::Result<(Entry, Entry)> {
+    Ok((
+        Entry::new(KEYRING_SERVICE, &format!("{profile}:key"))?,
+        Entry::new(KEYRING_SERVICE, &format!("{profile}:secret"))?,
+    ))
+}
+
+/// API key and secret stored for `profile`, if any. A missing or unreachable
+/// credential store reads as "nothing stored".
+pub fn load_keys(profile: &str) -> Option<(String, String)> {
+    let (key, secret) = entries(profile).ok()?;
+    Some((key.get_password().ok()?, secret.get_password().ok()?))
+}
+
+pub fn store_keys(profile: &str, key: &str, secret: &str) -> keyring::Result<()> {
+    let (key_entry, secret_entry) = entries(profile)?;
+    key_entry.set_password(key)?;
+    secret_entry.set_password(secret)
+}
+
+/// Reads an API key from stdin and the secret without echo.
+pub fn prompt_keys(profile: &str) -> io::Result<(String, String)> {
+    print!("API key for profile '{profile}': ");
+    io::stdout().flush()?;
+    let mut key = String::new();
+    io::stdin().lock().read_line(&mut key)?;
+    let key = key.trim().to_string();
+    let secret = rpassword::prompt_password("API secret: ")?
+        .trim()
+        .to_string();
+    if key.is_empty() || secret.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "API key and secret must not be empty",
+        ));
+    }
+    Ok((key, secret))
+}
+
+/// One-time `--store-keys` setup: prompts for the key and secret (the secret
+/// without echo) and saves them in the OS credential store.
+pub fn store_keys_interactive(profile: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (key, secret) = prompt_keys(profile)?;
+    store_keys(profile, &key, &secret)?;
+    println!("Stored credentials for '{profile}' in the OS keyring.");
+    if profile == DEFAULT_PROFILE {
+        println!("Remove any plaintext key/secret under [api] in the config file.");
+    } else {
+        println!("Declare it in the config file as [profiles.{profile}] without key/secret.");
+    }
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use crate::paths::data_dir;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorizontalLevel {
+    pub name: String,
+    pub price: f64,
+}
+
+/// A line through two (candle start time, price) anchors, extended to the right edge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendLine {
+    pub start_time: i64,
+    pub start_price: f64,
+    pub end_time: i64,
+    pub end_price: f64,
+}
+
+/// Retracement of the move from `start_price` to `end_price`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FibRetracement {
+    pub start_price: f64,
+    pub end_price: f64,
+}
+
+impl FibRetracement {
+    /// Price at `ratio` of the move, measured back from its end.
+    pub fn level(&self, ratio: f64) -> f64 {
+        self.end_price - (self.end_price - self.start_price) * ratio
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// User drawings for a single symbol, stored as JSON in the data directory.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Drawings {
+    #[serde(default)]
+    pub levels: Vec<HorizontalLevel>,
+    #[serde(default)]
+    pub trendlines: Vec<TrendLine>,
+    #[serde(default)]
+    pub fib: Option<FibRetracement>,
+    #[serde(default = "default_true")]
+    pub show_fib: bool,
+}
+
+impl Default for Drawings {
+    fn default() -> Self {
+        Self {
+            levels: Vec::new(),
+            trendlines: Vec::new(),
+            fib: None,
+            show_fib: true,
+        }
+    }
+}
+
+impl Drawings {
+    fn path(symbol: &str) -> PathBuf {
+        data_dir().join("drawings").join(format!("{symbol}.json"))
+    }
+
+    /// Loads saved drawings, falling back to an empty set if none exist yet.
+    pub fn load(symbol: &str) -> Self {
+        fs::read_to_string(Self::path(symbol))
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, symbol: &str) -> io::Result<()> {
+        let path = Self::path(symbol);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Adds a level with a generated name and returns its index.
+    pub fn add_level(&mut self, price: f64) -> usize {
+        let name = format!("L{}", self.levels.len() + 1);
+        self.levels.push(HorizontalLevel { name, price });
+        self.levels.len() - 1
+    }
+
+    /// Index of the level closest to `price`, if it is within `tolerance`.
+    pub fn nearest_level(&self, price: f64, tolerance: f64) -> Option<usize> {
+        self.levels
+            .iter()
+            .enumerate()
+            .map(|(i, level)| (i, (level.price - price).abs()))
+            .filter(|(_, distance)| *distance <= tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{
+    history::write_csv,
+    indicators::Series,
+    models::{Candle, Side, Trade},
+};
+use arrow_array::{
+    ArrayRef, BooleanArray, Float64Array, RecordBatch, StringArray, TimestampMillisecondArray,
+};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use clap::ValueEnum;
+use parquet::{arrow::ArrowWriter, basic::Compression, file::properties::WriterProperties};
+use serde::Deserialize;
+use std::{fs, path::Path, sync::Arc};
+
+pub type ExportResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// File format of market data exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataFormat {
+    #[default]
+    Csv,
+    /// Snappy-compressed Parquet, readable by pandas and polars
+    Parquet,
+}
+
+impl DataFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            DataFormat::Csv => "csv",
+            DataFormat::Parquet => "parquet",
+        }
+    }
+}
+
+fn timestamp_field(name: &str) -> Field {
+    Field::new(
+        name,
+        DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+        false,
+    )
+}
+
+fn float_column(values: impl Iterator<Item = f64>) -> ArrayRef {
+    Arc::new(values.collect::<Float64Array>())
+}
+
+/// Candles as an Arrow batch: `start` (UTC timestamp) and OHLCV plus turnover.
+pub fn candles_batch(candles: &[Candle]) -> ExportResult<RecordBatch> {
+    let schema = Schema::new(vec![
+        timestamp_field("start"),
+        Field::new("open", DataType::Float64, false),
+        Field::new("high", DataType::Float64, false),
+        Field::new("low", DataType::Float64, false),
+        Field::new("close", DataType::Float64, false),
+        Field::new("volume", DataType::Float64, false),
+        Field::new("turnover", DataType::Float64, false),
+    ]);
+    let start = TimestampMillisecondArray::from_iter_values(candles.iter().map(|c| c.start))
+        .with_timezone("UTC");
+    let columns = vec![
+        Arc::new(start) as ArrayRef,
+        float_column(candles.iter().map(|c| c.open)),
+        float_column(candles.iter().map(|c| c.high)),
+        float_column(candles.iter().map(|c| c.low)),
+        float_column(candles.iter().map(|c| c.close)),
+        float_column(candles.iter().map(|c| c.volume)),
+        float_column(candles.iter().map(|c| c.turnover)),
+    ];
+    Ok(RecordBatch::try_new(Arc::new(schema), columns)?)
+}
+
+/// Trades as an Arrow batch: `timestamp`, `price`, `size`, `side` and `block_trade`.
+pub fn trades_batch(trades: &[Trade]) -> ExportResult<RecordBatch> {
+    let schema = Schema::new(vec![
+        timestamp_field("timestamp"),
+        Field::new("price", DataType::Float64, false),
+        Field::new("size", DataType::Float64, false),
+        Field::new("side", DataType::Utf8, false),
+        Field::new("block_trade", DataType::Boolean, false),
+    ]);
+    let timestamp = TimestampMillisecondArray::from_iter_values(trades.iter().map(|t| t.timestamp))
+        .with_timezone("UTC");
+    let side: StringArray = trades
+        .iter()
+        .map(|t| {
+            Some(match t.side {
+                Side::Buy => "Buy",
+                Side::Sell => "Sell",
+            })
+        })
+        .collect();
+    let block_trade: BooleanArray = trades.iter().map(|t| Some(t.block_trade)).collect();
+    let columns = vec![
+        Arc::new(timestamp) as ArrayRef,
+        float_column(trades.iter().map(|t| t.price)),
+        float_column(trades.iter().map(|t| t.size)),
+        Arc::new(side),
+        Arc::new(block_trade),
+    ];
+    Ok(RecordBatch::try_new(Arc::new(schema), columns)?)
+}
+
+/// Indicator series as an Arrow batch: the candle `start` and one nullable
+/// column per series.
+pub fn indicators_batch(candles: &[Candle], series: &[Series]) -> ExportResult<RecordBatch> {
+    let mut fields = vec![timestamp_field("start")];
+    fields.extend(
+        series
+            .iter()
+            .map(|s| Field::new(s.name.as_str(), DataType::Float64, true)),
+    );
+    let start = TimestampMillisecondArray::from_iter_values(candles.iter().map(|c| c.start))
+        .with_timezone("UTC");
+    let mut columns = vec![Arc::new(start) as ArrayRef];
+    columns.extend(
+        series
+            .iter()
+            .map(|s| Arc::new(s.values.iter().copied().collect::<Float64Array>()) as ArrayRef),
+    );
+    Ok(RecordBatch::try_new(
+        Arc::new(Schema::new(fields)),
+        columns,
+    )?)
+}
+
+/// Writes one batch to a Snappy-compressed Parquet file.
+pub fn write_parquet(path: &Path, batch: &RecordBatch) -> ExportResult<()> {
+    create_parent(path)?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer =
+        ArrowWriter::try_new(fs::File::create(path)?, batch.schema(), Some(properties))?;
+    writer.write(batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+fn create_parent(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+pub fn write_candles(path: &Path, candles: &[Candle], format: DataFormat) -> ExportResult<()> {
+    match format {
+        DataFormat::Csv => Ok(write_csv(path, candles)?),
+        DataFormat::Parquet => write_parquet(path, &candles_batch(candles)?),
+    }
+}
+
+/// Writes `series` aligned by candle start; undefined values are empty in
+/// CSV and null in Parquet.
+pub fn write_indicators(
+    path: &Path,
+    candles: &[Candle],
+    series: &[Series],
+    format: DataFormat,
+) -> ExportResult<()> {
+    match format {
+        DataFormat::Csv => {
+            create_parent(path)?;
+            let mut writer = csv::Writer::from_path(path)?;
+            let mut header = vec![String::from("start")];
+            header.extend(series.iter().map(|s| s.name.clone()));
+            writer.write_record(&header)?;
+            for (i, candle) in candles.iter().enumerate() {
+                let mut row = vec![candle.start.to_string()];
+                row.extend(series.iter().map(|s| {
+                    s.values
+                        .get(i)
+                        .copied()
+                        .flatten()
+                        .map_or_else(String::new, |v| v.to_string())
+                }));
+                writer.write_record(&row)?;
+            }
+            writer.flush()?;
+            Ok(())
+        }
+        DataFormat::Parquet => write_parquet(path, &indicators_batch(candles, series)?),
+    }
+}
+
+pub fn write_trades(path: &Path, trades: &[Trade], format: DataFormat) -> ExportResult<()> {
+    match format {
+        DataFormat::Csv => {
+            create_parent(path)?;
+            let mut writer = csv::Writer::from_path(path)?;
+            for trade in trades {
+                writer.serialize(trade)?;
+            }
+            writer.flush()?;
+            Ok(())
+        }
+        DataFormat::Parquet => write_parquet(path, &trades_batch(trades)?),
+    }
+}
@@ -0,0 +1,72 @@
+use crate::models::Side;
+use serde::Deserialize;
+
+/// How far a simulated market fill lands from the quoted price.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Slippage {
+    /// A fixed number of ticks against the taker.
+    Ticks(f64),
+    /// `impact × qty / volume` of the price, where volume is that of the
+    /// candle the fill happens in: taking a bigger share moves price more.
+    Volume(f64),
+}
+
+/// Fees and slippage charged on simulated fills, shared by the backtester and
+/// the paper-trading simulator. Set under `[fees]` in the config file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FeeModel {
+    /// Fee rate on resting (limit) fills, e.g. `0.0002` for 0.02%.
+    pub maker: f64,
+    /// Fee rate on market and triggered fills.
+    pub taker: f64,
+    pub slippage: Slippage,
+}
+
+impl Default for FeeModel {
+    /// Bybit's base-tier linear rates and one tick of slippage.
+    fn default() -> Self {
+        Self {
+            maker: 0.0002,
+            taker: 0.00055,
+            slippage: Slippage::Ticks(1.0),
+        }
+    }
+}
+
+impl FeeModel {
+    /// No fees or slippage, for comparing against frictionless results.
+    pub fn zero() -> Self {
+        Self {
+            maker: 0.0,
+            taker: 0.0,
+            slippage: Slippage::Ticks(0.0),
+        }
+    }
+
+    /// Price a market order actually fills at, given the quoted `price`.
+    /// A `volume` of zero (unknown) means no volume-based slippage.
+    pub fn taker_price(
+        &self,
+        side: Side,
+        price: f64,
+        qty: f64,
+        tick_size: f64,
+        volume: f64,
+    ) -> f64 {
+        let slip = match self.slippage {
+            Slippage::Ticks(ticks) => ticks * tick_size,
+            Slippage::Volume(impact) if volume > 0.0 => price * impact * qty / volume,
+            Slippage::Volume(_) => 0.0,
+        };
+        match side {
+            Side::Buy => price + slip,
+            Side::Sell => price - slip,
+        }
+    }
+
+    pub fn fee(&self, notional: f64, maker: bool) -> f64 {
+        notional.abs() * if maker { self.maker } else { self.taker }
+    }
+}
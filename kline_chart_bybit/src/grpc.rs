@@ -0,0 +1,120 @@
+use crate::{models::Candle, store::CandleStore};
+use std::{io, net::SocketAddr, path::PathBuf, pin::Pin};
+use tokio::{
+    net::TcpListener,
+    sync::{broadcast, mpsc::UnboundedSender},
+};
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, TcpListenerStream},
+    Stream, StreamExt,
+};
+use tonic::{transport::Server, Request, Response, Status};
+
+pub mod proto {
+    tonic::include_proto!("candles");
+}
+
+use proto::{
+    candle_feed_server::{CandleFeed, CandleFeedServer},
+    SubscribeRequest,
+};
+
+/// Confirmed candles fanned out to every subscriber; slow subscribers skip ahead.
+pub type CandleSender = broadcast::Sender<proto::Candle>;
+
+/// Backlog of live candles per subscriber before it starts missing some.
+const FEED_CAPACITY: usize = 256;
+
+pub fn candle_channel() -> CandleSender {
+    broadcast::channel(FEED_CAPACITY).0
+}
+
+pub fn to_proto(symbol: &str, interval: &str, candle: &Candle) -> proto::Candle {
+    proto::Candle {
+        symbol: symbol.to_string(),
+        interval: interval.to_string(),
+        start: candle.start,
+        open: candle.open,
+        high: candle.high,
+        low: candle.low,
+        close: candle.close,
+        volume: candle.volume,
+        turnover: candle.turnover,
+    }
+}
+
+/// `SubscribeCandles` over the candle store and the live feed.
+pub struct CandleService {
+    store_path: PathBuf,
+    live: CandleSender,
+}
+
+type CandleStream = Pin<Box<dyn Stream<Item = Result<proto::Candle, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl CandleFeed for CandleService {
+    type SubscribeCandlesStream = CandleStream;
+
+    async fn subscribe_candles(
+        &self,
+        request: Request<SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeCandlesStream>, Status> {
+        let SubscribeRequest {
+            symbol,
+            interval,
+            history,
+        } = request.into_inner();
+        // Subscribe before reading the store so no candle falls in between
+        let live = BroadcastStream::new(self.live.subscribe());
+
+        let path = self.store_path.clone();
+        let (store_symbol, store_interval) = (symbol.clone(), interval.clone());
+        let stored = tokio::task::spawn_blocking(move || {
+            CandleStore::open(&path)?.latest(&store_symbol, &store_interval, history as usize)
+        })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .map_err(|e| Status::unavailable(format!("candle store: {}", e)))?;
+
+        let last_stored = stored.last().map(|c| c.start);
+        let history: Vec<proto::Candle> = stored
+            .iter()
+            .map(|candle| to_proto(&symbol, &interval, candle))
+            .collect();
+        let live = live.filter_map(move |candle| match candle {
+            Ok(candle)
+                if candle.symbol == symbol
+                    && candle.interval == interval
+                    && last_stored.is_none_or(|last| candle.start > last) =>
+            {
+                Some(Ok(candle))
+            }
+            Ok(_) | Err(BroadcastStreamRecvError::Lagged(_)) => None,
+        });
+        Ok(Response::new(Box::pin(
+            tokio_stream::iter(history).map(Ok).chain(live),
+        )))
+    }
+}
+
+/// Binds `addr` and serves the candle feed in the background. If the server
+/// stops, the reason goes to `errors` for the status line.
+pub async fn serve(
+    addr: SocketAddr,
+    store_path: PathBuf,
+    live: CandleSender,
+    errors: UnboundedSender<String>,
+) -> io::Result<()> {
+    let listener = TcpListenerStream::new(TcpListener::bind(addr).await?);
+    let service = CandleService { store_path, live };
+    tokio::spawn(async move {
+        if let Err(e) = Server::builder()
+            .add_service(CandleFeedServer::new(service))
+            .serve_with_incoming(listener)
+            .await
+        {
+            let _ = errors.send(format!("gRPC server stopped: {e}"));
+        }
+    });
+    Ok(())
+}
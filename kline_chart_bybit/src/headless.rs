@@ -0,0 +1,81 @@
+use crate::{
+    config::ChartConfig,
+    models::{Candle, KlineResponse},
+    strategy::{Signal, Strategy},
+};
+use serde::Serialize;
+use std::collections::VecDeque;
+
+/// One NDJSON line: a confirmed candle with the indicators computed on it.
+#[derive(Debug, Serialize)]
+pub struct CandleRecord<'a> {
+    pub symbol: &'a str,
+    pub interval: &'a str,
+    #[serde(flatten)]
+    pub candle: &'a Candle,
+    /// Simple moving average of the closes, once the window is full.
+    pub ma: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signal: Option<Signal>,
+}
+
+/// Turns the public stream into NDJSON for `--headless`, computing what the
+/// chart would show for each confirmed candle.
+pub struct HeadlessFeed {
+    symbol: String,
+    interval: String,
+    ma_window: usize,
+    closes: VecDeque<f64>,
+    strategy: Option<Box<dyn Strategy>>,
+}
+
+impl HeadlessFeed {
+    pub fn new(chart: &ChartConfig, strategy: Option<Box<dyn Strategy>>) -> Self {
+        Self {
+            symbol: chart.symbol.clone(),
+            interval: chart.interval.clone(),
+            ma_window: chart.ma_window.max(1),
+            closes: VecDeque::new(),
+            strategy,
+        }
+    }
+
+    /// The lines for a websocket frame; only confirmed candles of the chart
+    /// symbol produce any.
+    pub fn handle_message(&mut self, text: &str) -> Vec<String> {
+        let Ok(response) = serde_json::from_str::<KlineResponse>(text) else {
+            return Vec::new();
+        };
+        if response.topic != format!("kline.{}.{}", self.interval, self.symbol) {
+            return Vec::new();
+        }
+        response
+            .data
+            .iter()
+            .filter(|data| data.confirm)
+            .filter_map(Candle::from_kline_data)
+            .filter_map(|candle| self.on_candle(&candle))
+            .collect()
+    }
+
+    fn on_candle(&mut self, candle: &Candle) -> Option<String> {
+        self.closes.push_back(candle.close);
+        if self.closes.len() > self.ma_window {
+            self.closes.pop_front();
+        }
+        let ma = (self.closes.len() == self.ma_window)
+            .then(|| self.closes.iter().sum::<f64>() / self.ma_window as f64);
+        let signal = self
+            .strategy
+            .as_mut()
+            .and_then(|strategy| strategy.on_candle(candle));
+        let record = CandleRecord {
+            symbol: &self.symbol,
+            interval: &self.interval,
+            candle,
+            ma,
+            signal,
+        };
+        serde_json::to_string(&record).ok()
+    }
+}
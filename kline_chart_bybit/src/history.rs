@@ -0,0 +1,265 @@
+use crate::{
+    config::endpoints,
+    constants::USER_AGENT,
+    models::{Candle, Side, Trade},
+    rest::RestResult,
+};
+use reqwest::{header::HeaderMap, StatusCode};
+use serde::Deserialize;
+use std::{fs, path::Path, time::Duration};
+use tokio::time::sleep;
+
+/// Bybit caps a kline page at this many candles.
+const PAGE_LIMIT: usize = 1000;
+
+/// `retCode` Bybit answers with when a client sends too many requests.
+const RATE_LIMITED: i64 = 10006;
+
+/// How many times a rate-limited page is retried before giving up.
+const MAX_RETRIES: u32 = 6;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KlineHistoryResponse {
+    ret_code: i64,
+    ret_msg: String,
+    result: KlineHistoryResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct KlineHistoryResult {
+    /// `[start, open, high, low, close, volume, turnover]`, newest first.
+    list: Vec<[String; 7]>,
+}
+
+/// Bybit's name for an interval given either natively (`60`, `D`) or with a
+/// unit suffix (`15m`, `1h`, `1d`, `1w`).
+pub fn normalize_interval(interval: &str) -> Option<String> {
+    if interval_ms(interval).is_some() {
+        return Some(interval.to_string());
+    }
+    let (count, unit) = interval.split_at(interval.len().checked_sub(1)?);
+    let count: i64 = count.parse().ok()?;
+    let normalized = match (unit, count) {
+        ("m", _) => count.to_string(),
+        ("h", _) => (count * 60).to_string(),
+        ("d", 1) => String::from("D"),
+        ("w", 1) => String::from("W"),
+        _ => return None,
+    };
+    Some(normalized)
+}
+
+/// Length of a kline interval (`1`, `60`, `D`, ...) in milliseconds.
+pub fn interval_ms(interval: &str) -> Option<i64> {
+    let minutes = match interval {
+        "D" => 24 * 60,
+        "W" => 7 * 24 * 60,
+        "M" => 30 * 24 * 60,
+        minutes => minutes.parse().ok()?,
+    };
+    Some(minutes * 60_000)
+}
+
+fn candle_from_row(row: &[String; 7]) -> Option<Candle> {
+    Some(Candle {
+        start: row[0].parse().ok()?,
+        open: row[1].parse().ok()?,
+        high: row[2].parse().ok()?,
+        low: row[3].parse().ok()?,
+        close: row[4].parse().ok()?,
+        volume: row[5].parse().ok()?,
+        turnover: row[6].parse().ok()?,
+    })
+}
+
+/// How long to wait before retrying a rate-limited request: until the reset
+/// time Bybit reports, or an exponential backoff when it doesn't say.
+fn retry_delay(headers: &HeaderMap, attempt: u32) -> Duration {
+    let reset = headers
+        .get("X-Bapi-Limit-Reset-Timestamp")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<i64>().ok());
+    let now = chrono::Utc::now().timestamp_millis();
+    match reset {
+        Some(reset) if reset > now => Duration::from_millis((reset - now) as u64 + 50),
+        _ => Duration::from_secs(1 << attempt),
+    }
+}
+
+/// Fetches one kline page, waiting out rate limits instead of failing.
+async fn fetch_page(client: &reqwest::Client, url: &str) -> RestResult<KlineHistoryResponse> {
+    let mut attempt = 0;
+    loop {
+        let response = client
+            .get(url)
+            .header("User-Agent", USER_AGENT)
+            .send()
+            .await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::FORBIDDEN {
+            let page: KlineHistoryResponse = response.json().await?;
+            if page.ret_code != RATE_LIMITED {
+                // Out of requests for this window: pace the next page
+                let remaining = headers
+                    .get("X-Bapi-Limit-Status")
+                    .and_then(|value| value.to_str().ok());
+                if remaining == Some("0") {
+                    sleep(retry_delay(&headers, 0)).await;
+                }
+                return Ok(page);
+            }
+        }
+        if attempt >= MAX_RETRIES {
+            return Err("rate limited by Bybit, giving up".into());
+        }
+        sleep(retry_delay(&headers, attempt)).await;
+        attempt += 1;
+    }
+}
+
+/// Downloads the closed candles of `symbol` in `[start, end)` from the public
+/// kline endpoint, oldest first.
+pub async fn fetch_candles(
+    symbol: &str,
+    interval: &str,
+    start: i64,
+    end: i64,
+) -> RestResult<Vec<Candle>> {
+    fetch_candles_with_progress(symbol, interval, start, end, |_| {}).await
+}
+
+/// Like [`fetch_candles`], calling `progress` with the number of candles
+/// downloaded so far after every page.
+pub async fn fetch_candles_with_progress(
+    symbol: &str,
+    interval: &str,
+    start: i64,
+    end: i64,
+    mut progress: impl FnMut(usize),
+) -> RestResult<Vec<Candle>> {
+    let step = interval_ms(interval).ok_or_else(|| format!("invalid interval '{}'", interval))?;
+    let client = reqwest::Client::new();
+    let mut candles = Vec::new();
+    let mut page_end = end - 1;
+    while page_end >= start {
+        let url = format!(
+            "{}/v5/market/kline?category=linear&symbol={}&interval={}&start={}&end={}&limit={}",
+            endpoints().rest,
+            symbol,
+            interval,
+            start,
+            page_end,
+            PAGE_LIMIT
+        );
+        let response = fetch_page(&client, &url).await?;
+        if response.ret_code != 0 {
+            return Err(format!("{} (code {})", response.ret_msg, response.ret_code).into());
+        }
+        let page: Vec<Candle> = response
+            .result
+            .list
+            .iter()
+            .filter_map(candle_from_row)
+            .collect();
+        let Some(oldest) = page.last().map(|c| c.start) else {
+            break;
+        };
+        candles.extend(page);
+        progress(candles.len());
+        page_end = oldest - 1;
+    }
+
+    candles.retain(|c| c.start >= start && c.start + step <= end);
+    candles.sort_by_key(|c| c.start);
+    candles.dedup_by_key(|c| c.start);
+    Ok(candles)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecentTradesResponse {
+    ret_code: i64,
+    ret_msg: String,
+    result: RecentTradesResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecentTradesResult {
+    list: Vec<RecentTrade>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecentTrade {
+    time: String,
+    side: String,
+    size: String,
+    price: String,
+    #[serde(default)]
+    is_block_trade: bool,
+}
+
+/// Downloads the latest public trades of `symbol` (at most 1000), oldest first.
+pub async fn fetch_recent_trades(symbol: &str) -> RestResult<Vec<Trade>> {
+    let url = format!(
+        "{}/v5/market/recent-trade?category=linear&symbol={}&limit={}",
+        endpoints().rest,
+        symbol,
+        PAGE_LIMIT
+    );
+    let response: RecentTradesResponse = reqwest::Client::new()
+        .get(url)
+        .header("User-Agent", USER_AGENT)
+        .send()
+        .await?
+        .json()
+        .await?;
+    if response.ret_code != 0 {
+        return Err(format!("{} (code {})", response.ret_msg, response.ret_code).into());
+    }
+    let mut trades: Vec<Trade> = response
+        .result
+        .list
+        .iter()
+        .filter_map(|trade| {
+            Some(Trade {
+                timestamp: trade.time.parse().ok()?,
+                price: trade.price.parse().ok()?,
+                size: trade.size.parse().ok()?,
+                side: match trade.side.as_str() {
+                    "Buy" => Side::Buy,
+                    "Sell" => Side::Sell,
+                    _ => return None,
+                },
+                block_trade: trade.is_block_trade,
+            })
+        })
+        .collect();
+    trades.sort_by_key(|t| t.timestamp);
+    Ok(trades)
+}
+
+/// Reads candles from a CSV file with a `start,open,high,low,close,volume,turnover` header.
+pub fn load_csv(path: &Path) -> Result<Vec<Candle>, csv::Error> {
+    let mut candles = csv::Reader::from_path(path)?
+        .deserialize()
+        .collect::<Result<Vec<Candle>, _>>()?;
+    candles.sort_by_key(|c| c.start);
+    Ok(candles)
+}
+
+/// Writes candles to a CSV file in the format [`load_csv`] reads, creating
+/// the parent directory if needed.
+pub fn write_csv(path: &Path, candles: &[Candle]) -> Result<(), csv::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut writer = csv::Writer::from_path(path)?;
+    for candle in candles {
+        writer.serialize(candle)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
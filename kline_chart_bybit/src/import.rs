@@ -0,0 +1,227 @@
+use crate::models::Candle;
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use clap::ValueEnum;
+use serde_json::Value;
+use std::{error::Error, fs, path::Path, str::FromStr};
+
+pub type ImportResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// Layout of a file to import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// CSV with a header row
+    Csv,
+    /// A JSON array of objects, or one object per line
+    Json,
+}
+
+impl ImportFormat {
+    /// Guesses the format from the file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "csv" | "txt" => Some(Self::Csv),
+            "json" | "jsonl" | "ndjson" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Source column (CSV header or JSON key) of every candle field.
+#[derive(Debug, Clone)]
+pub struct ColumnMap {
+    pub start: String,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    pub volume: String,
+    pub turnover: String,
+}
+
+impl Default for ColumnMap {
+    fn default() -> Self {
+        Self {
+            start: String::from("start"),
+            open: String::from("open"),
+            high: String::from("high"),
+            low: String::from("low"),
+            close: String::from("close"),
+            volume: String::from("volume"),
+            turnover: String::from("turnover"),
+        }
+    }
+}
+
+impl ColumnMap {
+    /// Applies a `field=column` mapping, e.g. `start=timestamp`.
+    pub fn set(&mut self, mapping: &str) -> Result<(), String> {
+        let (field, column) = mapping
+            .split_once('=')
+            .ok_or_else(|| format!("expected FIELD=COLUMN, got '{}'", mapping))?;
+        let slot = match field.trim() {
+            "start" => &mut self.start,
+            "open" => &mut self.open,
+            "high" => &mut self.high,
+            "low" => &mut self.low,
+            "close" => &mut self.close,
+            "volume" => &mut self.volume,
+            "turnover" => &mut self.turnover,
+            other => return Err(format!("unknown candle field '{}'", other)),
+        };
+        *slot = column.trim().to_string();
+        Ok(())
+    }
+}
+
+/// How the start column is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeFormat {
+    /// Epoch seconds or milliseconds, told apart by magnitude, or RFC 3339.
+    Auto,
+    Millis,
+    Seconds,
+    Rfc3339,
+    /// A chrono pattern such as `%Y-%m-%d %H:%M`, read as UTC.
+    Pattern(String),
+}
+
+impl FromStr for TimeFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "auto" => Self::Auto,
+            "ms" => Self::Millis,
+            "s" => Self::Seconds,
+            "rfc3339" => Self::Rfc3339,
+            pattern if pattern.contains('%') => Self::Pattern(pattern.to_string()),
+            other => {
+                return Err(format!(
+                    "unknown time format '{}': use auto, ms, s, rfc3339 or a %-pattern",
+                    other
+                ))
+            }
+        })
+    }
+}
+
+/// Epoch milliseconds above this are taken as milliseconds by `auto`
+/// (it is 1973 in milliseconds and the year 5138 in seconds).
+const MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+impl TimeFormat {
+    /// Milliseconds since the epoch of `text`.
+    pub fn parse(&self, text: &str) -> Option<i64> {
+        let text = text.trim();
+        match self {
+            Self::Auto => match text.parse::<f64>() {
+                Ok(n) if n.abs() >= MILLIS_THRESHOLD as f64 => Some(n as i64),
+                Ok(n) => Some((n * 1000.0) as i64),
+                Err(_) => Self::Rfc3339.parse(text),
+            },
+            Self::Millis => text.parse::<f64>().ok().map(|n| n as i64),
+            Self::Seconds => text.parse::<f64>().ok().map(|n| (n * 1000.0) as i64),
+            Self::Rfc3339 => DateTime::parse_from_rfc3339(text)
+                .ok()
+                .map(|t| t.timestamp_millis()),
+            Self::Pattern(pattern) => NaiveDateTime::parse_from_str(text, pattern)
+                .or_else(|_| {
+                    NaiveDate::parse_from_str(text, pattern)
+                        .map(|date| date.and_time(chrono::NaiveTime::MIN))
+                })
+                .ok()
+                .map(|t| t.and_utc().timestamp_millis()),
+        }
+    }
+}
+
+/// Builds a candle from a row, looking fields up with `get`. Volume and
+/// turnover are optional and default to zero.
+fn candle_from(
+    get: impl Fn(&str) -> Option<String>,
+    columns: &ColumnMap,
+    time: &TimeFormat,
+    row: usize,
+) -> ImportResult<Candle> {
+    let text = |column: &str| {
+        get(column).ok_or_else(|| format!("row {}: missing column '{}'", row, column))
+    };
+    let number = |column: &str| -> ImportResult<f64> {
+        let value = text(column)?;
+        value
+            .trim()
+            .parse()
+            .map_err(|_| format!("row {}: '{}' in '{}' is not a number", row, value, column).into())
+    };
+    let optional = |column: &str| match get(column) {
+        Some(value) if !value.trim().is_empty() => number(column),
+        _ => Ok(0.0),
+    };
+    let start = text(&columns.start)?;
+    Ok(Candle {
+        start: time
+            .parse(&start)
+            .ok_or_else(|| format!("row {}: can't read time '{}'", row, start))?,
+        open: number(&columns.open)?,
+        high: number(&columns.high)?,
+        low: number(&columns.low)?,
+        close: number(&columns.close)?,
+        volume: optional(&columns.volume)?,
+        turnover: optional(&columns.turnover)?,
+    })
+}
+
+fn read_csv(path: &Path, columns: &ColumnMap, time: &TimeFormat) -> ImportResult<Vec<Candle>> {
+    let mut reader = csv::Reader::from_path(path)?;
+    let headers = reader.headers()?.clone();
+    let mut candles = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        let record = record?;
+        let get = |column: &str| {
+            let index = headers.iter().position(|header| header.trim() == column)?;
+            record.get(index).map(str::to_string)
+        };
+        candles.push(candle_from(get, columns, time, i + 1)?);
+    }
+    Ok(candles)
+}
+
+fn read_json(path: &Path, columns: &ColumnMap, time: &TimeFormat) -> ImportResult<Vec<Candle>> {
+    let text = fs::read_to_string(path)?;
+    let rows: Vec<Value> = if text.trim_start().starts_with('[') {
+        serde_json::from_str(&text)?
+    } else {
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?
+    };
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let get = |column: &str| match row.get(column)? {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            };
+            candle_from(get, columns, time, i + 1)
+        })
+        .collect()
+}
+
+/// Reads candles from a CSV or JSON file of any layout, oldest first and
+/// without duplicate starts.
+pub fn read_candles(
+    path: &Path,
+    format: ImportFormat,
+    columns: &ColumnMap,
+    time: &TimeFormat,
+) -> ImportResult<Vec<Candle>> {
+    let mut candles = match format {
+        ImportFormat::Csv => read_csv(path, columns, time)?,
+        ImportFormat::Json => read_json(path, columns, time)?,
+    };
+    candles.sort_by_key(|c| c.start);
+    candles.dedup_by_key(|c| c.start);
+    Ok(candles)
+}
@@ -0,0 +1,36 @@
+use crate::models::Candle;
+
+/// A computed indicator, one value per candle, `None` where it is undefined.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    /// Column name in exports, e.g. `ma50`.
+    pub name: String,
+    pub values: Vec<Option<f64>>,
+}
+
+/// Moving average of the closes, named `ma<window>`.
+pub fn ma_series(candles: &[Candle], window: usize) -> Series {
+    let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+    Series {
+        name: format!("ma{}", window),
+        values: sma(&closes, window),
+    }
+}
+
+/// Simple moving average of `values` over `window`, aligned with the input:
+/// `None` until the window first fills.
+pub fn sma(values: &[f64], window: usize) -> Vec<Option<f64>> {
+    let window = window.max(1);
+    let mut sum = 0.0;
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| {
+            sum += value;
+            if i >= window {
+                sum -= values[i - window];
+            }
+            (i + 1 >= window).then(|| sum / window as f64)
+        })
+        .collect()
+}
@@ -0,0 +1,168 @@
+use crate::{
+    config::endpoints,
+    constants::{TICK_SIZE, USER_AGENT},
+    rest::RestResult,
+};
+use serde::Deserialize;
+
+/// Contract specs of a linear perpetual, as needed for sizing orders.
+#[derive(Debug, Clone)]
+pub struct Instrument {
+    pub symbol: String,
+    pub tick_size: f64,
+    pub qty_step: f64,
+    pub min_qty: f64,
+    pub max_qty: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InstrumentsResponse {
+    ret_code: i64,
+    ret_msg: String,
+    result: InstrumentsResult,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstrumentsResult {
+    list: Vec<InstrumentInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InstrumentInfo {
+    symbol: String,
+    price_filter: PriceFilter,
+    lot_size_filter: LotSizeFilter,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PriceFilter {
+    tick_size: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LotSizeFilter {
+    qty_step: String,
+    min_order_qty: String,
+    max_order_qty: String,
+}
+
+impl Instrument {
+    /// Specs used until (or if) the exchange's are fetched.
+    pub fn fallback(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            tick_size: TICK_SIZE,
+            qty_step: 0.01,
+            min_qty: 0.01,
+            max_qty: f64::MAX,
+        }
+    }
+
+    /// Loads the specs from the public instruments-info endpoint.
+    pub async fn fetch(symbol: &str) -> RestResult<Self> {
+        let url = format!(
+            "{}/v5/market/instruments-info?category=linear&symbol={}",
+            endpoints().rest,
+            symbol
+        );
+        let response: InstrumentsResponse = reqwest::Client::new()
+            .get(url)
+            .header("User-Agent", USER_AGENT)
+            .send()
+            .await?
+            .json()
+            .await?;
+        if response.ret_code != 0 {
+            return Err(format!("{} (code {})", response.ret_msg, response.ret_code).into());
+        }
+        let info = response
+            .result
+            .list
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("unknown symbol {}", symbol))?;
+        Ok(Self {
+            symbol: info.symbol,
+            tick_size: info.price_filter.tick_size.parse()?,
+            qty_step: info.lot_size_filter.qty_step.parse()?,
+            min_qty: info.lot_size_filter.min_order_qty.parse()?,
+            max_qty: info.lot_size_filter.max_order_qty.parse()?,
+        })
+    }
+
+    /// Rounds `qty` down to the lot step; `None` below the minimum order size.
+    pub fn round_qty(&self, qty: f64) -> Option<f64> {
+        // The epsilon keeps 0.3 / 0.1 from flooring to 2
+        let steps = (qty / self.qty_step + 1e-9).floor();
+        let rounded = snap(steps * self.qty_step, self.qty_step).min(self.max_qty);
+        (rounded >= self.min_qty).then_some(rounded)
+    }
+
+    /// Rounds `price` to the nearest tick.
+    pub fn round_price(&self, price: f64) -> f64 {
+        snap(
+            (price / self.tick_size).round() * self.tick_size,
+            self.tick_size,
+        )
+    }
+
+    /// `qty` with exactly as many decimals as the lot step, as Bybit expects it.
+    pub fn format_qty(&self, qty: f64) -> String {
+        format!("{:.*}", decimals(self.qty_step), qty)
+    }
+
+    /// `price` with exactly as many decimals as the tick size.
+    pub fn format_price(&self, price: f64) -> String {
+        format!("{:.*}", decimals(self.tick_size), price)
+    }
+}
+
+/// Decimal places of a step such as 0.001 (3) or 0.5 (1).
+fn decimals(step: f64) -> usize {
+    let text = step.to_string();
+    text.split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len())
+}
+
+/// `value` cut to the decimals of `step`, dropping float noise like the
+/// trailing ...0004 of 0.1 * 3.
+fn snap(value: f64, step: f64) -> f64 {
+    format!("{:.*}", decimals(step), value)
+        .parse()
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instrument(tick_size: f64, qty_step: f64) -> Instrument {
+        Instrument {
+            symbol: String::from("BTCUSDT"),
+            tick_size,
+            qty_step,
+            min_qty: qty_step,
+            max_qty: 1000.0,
+        }
+    }
+
+    #[test]
+    fn round_qty_drops_float_noise() {
+        let instrument = instrument(0.1, 0.1);
+        assert_eq!(instrument.round_qty(0.35), Some(0.3));
+        assert_eq!(instrument.format_qty(0.1 * 3.0), "0.3");
+        assert_eq!(instrument.round_qty(0.05), None);
+    }
+
+    #[test]
+    fn round_price_snaps_to_tick() {
+        let instrument = instrument(0.1, 0.001);
+        assert_eq!(instrument.round_price(1.23), 1.2);
+        assert_eq!(instrument.format_price(1.2000000000000002), "1.2");
+        assert_eq!(instrument.format_qty(2.0), "2.000");
+    }
+}
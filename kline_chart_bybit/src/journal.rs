@@ -0,0 +1,317 @@
+use crate::{
+    backtest::ClosedTrade,
+    models::{Execution, Side},
+    paths::data_dir,
+};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// A closed round trip, one row of the journal.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub symbol: String,
+    pub side: Side,
+    pub entry_time: i64,
+    pub entry_price: f64,
+    pub exit_time: i64,
+    pub exit_price: f64,
+    pub qty: f64,
+    pub fees: f64,
+    /// Net of fees.
+    pub pnl: f64,
+    /// Strategy that traded, or "manual".
+    pub strategy: String,
+    /// Where the trade happened: "live", "paper" or "backtest".
+    pub source: String,
+    #[serde(default)]
+    pub notes: String,
+}
+
+impl JournalEntry {
+    pub fn from_closed_trade(symbol: &str, trade: &ClosedTrade, strategy: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            side: trade.side,
+            entry_time: trade.entry_time,
+            entry_price: trade.entry_price,
+            exit_time: trade.exit_time,
+            exit_price: trade.exit_price,
+            qty: trade.qty,
+            fees: trade.fees,
+            pnl: trade.pnl,
+            strategy: strategy.to_string(),
+            source: String::from("backtest"),
+            notes: String::new(),
+        }
+    }
+}
+
+/// The part of a position not yet closed, built up from fills.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OpenTrade {
+    symbol: String,
+    side: Side,
+    entry_time: i64,
+    entry_price: f64,
+    qty: f64,
+    /// Entry fees not yet charged to a journal row.
+    fees: f64,
+}
+
+/// Pairs fills into round trips and appends them to a CSV file.
+pub struct Journal {
+    path: PathBuf,
+    source: String,
+    open: HashMap<String, OpenTrade>,
+    /// Where the open trades are kept between runs, if anywhere.
+    open_path: Option<PathBuf>,
+}
+
+/// `journal.csv` in the data directory.
+pub fn default_path() -> PathBuf {
+    data_dir().join("journal.csv")
+}
+
+/// Where the trades of `source` still open at exit are kept.
+pub fn open_trades_path(source: &str) -> PathBuf {
+    data_dir().join(format!("journal-open-{}.csv", source))
+}
+
+impl Journal {
+    pub fn new(path: PathBuf, source: &str) -> Self {
+        Self {
+            path,
+            source: source.to_string(),
+            open: HashMap::new(),
+            open_path: None,
+        }
+    }
+
+    /// Picks up the trades left open at `path` by an earlier run and keeps
+    /// them there from now on, so closing one after a restart pairs it with
+    /// its entry instead of opening a new trade.
+    pub fn with_open_trades(mut self, path: PathBuf) -> Result<Self, csv::Error> {
+        if path.exists() {
+            for trade in csv::Reader::from_path(&path)?.deserialize() {
+                let trade: OpenTrade = trade?;
+                self.open.insert(trade.symbol.clone(), trade);
+            }
+        }
+        self.open_path = Some(path);
+        Ok(self)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Nets `fill` against the open trade of its symbol. Whatever it closes is
+    /// written to the journal and returned; the rest opens or adds to a trade.
+    pub fn record_fill(
+        &mut self,
+        fill: &Execution,
+        strategy: &str,
+    ) -> Result<Option<JournalEntry>, csv::Error> {
+        let entry = self.net_fill(fill, strategy)?;
+        self.save_open_trades()?;
+        Ok(entry)
+    }
+
+    fn save_open_trades(&self) -> Result<(), csv::Error> {
+        let Some(path) = &self.open_path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut writer = csv::Writer::from_path(path)?;
+        for trade in self.open.values() {
+            writer.serialize(trade)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn net_fill(
+        &mut self,
+        fill: &Execution,
+        strategy: &str,
+    ) -> Result<Option<JournalEntry>, csv::Error> {
+        let Some(open) = self.open.get_mut(&fill.symbol) else {
+            self.open
+                .insert(fill.symbol.clone(), opening(fill, fill.qty, fill.fee));
+            return Ok(None);
+        };
+
+        if open.side == fill.side {
+            let qty = open.qty + fill.qty;
+            open.entry_price = (open.entry_price * open.qty + fill.price * fill.qty) / qty;
+            open.qty = qty;
+            open.fees += fill.fee;
+            return Ok(None);
+        }
+
+        let closed = fill.qty.min(open.qty);
+        let entry_fees = open.fees * closed / open.qty;
+        let exit_fees = fill.fee * closed / fill.qty;
+        let direction = match open.side {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        };
+        let fees = entry_fees + exit_fees;
+        let entry = JournalEntry {
+            symbol: fill.symbol.clone(),
+            side: open.side,
+            entry_time: open.entry_time,
+            entry_price: open.entry_price,
+            exit_time: fill.timestamp,
+            exit_price: fill.price,
+            qty: closed,
+            fees,
+            pnl: (fill.price - open.entry_price) * closed * direction - fees,
+            strategy: strategy.to_string(),
+            source: self.source.clone(),
+            notes: String::new(),
+        };
+
+        open.qty -= closed;
+        open.fees -= entry_fees;
+        let remainder = fill.qty - closed;
+        if remainder > f64::EPSILON {
+            // Flipped through flat: the rest of the fill opens the other side
+            let trade = opening(fill, remainder, fill.fee - exit_fees);
+            self.open.insert(fill.symbol.clone(), trade);
+        } else if open.qty <= f64::EPSILON {
+            self.open.remove(&fill.symbol);
+        }
+
+        append(&self.path, std::slice::from_ref(&entry))?;
+        Ok(Some(entry))
+    }
+}
+
+fn opening(fill: &Execution, qty: f64, fees: f64) -> OpenTrade {
+    OpenTrade {
+        symbol: fill.symbol.clone(),
+        side: fill.side,
+        entry_time: fill.timestamp,
+        entry_price: fill.price,
+        qty,
+        fees,
+    }
+}
+
+/// Appends `entries` to the journal at `path`, writing the header if the file
+/// is new.
+pub fn append(path: &Path, entries: &[JournalEntry]) -> Result<(), csv::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let is_new = fs::metadata(path).map_or(true, |m| m.len() == 0);
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(is_new)
+        .from_writer(file);
+    for entry in entries {
+        writer.serialize(entry)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads every entry of the journal at `path`; a missing file is empty.
+pub fn load(path: &Path) -> Result<Vec<JournalEntry>, csv::Error> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    csv::Reader::from_path(path)?.deserialize().collect()
+}
+
+/// Rewrites the whole journal, e.g. after editing notes.
+pub fn save(path: &Path, entries: &[JournalEntry]) -> Result<(), csv::Error> {
+    let mut writer = csv::Writer::from_path(path)?;
+    for entry in entries {
+        writer.serialize(entry)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes `entries` to `out` as CSV with a header or as a JSON array.
+pub fn export(
+    entries: &[JournalEntry],
+    format: ExportFormat,
+    out: impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            for entry in entries {
+                writer.serialize(entry)?;
+            }
+            writer.flush()?;
+        }
+        ExportFormat::Json => serde_json::to_writer_pretty(out, entries)?,
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(side: Side, price: f64, timestamp: i64) -> Execution {
+        Execution {
+            exec_id: timestamp.to_string(),
+            order_id: timestamp.to_string(),
+            symbol: String::from("BTCUSDT"),
+            timestamp,
+            side,
+            price,
+            qty: 2.0,
+            fee: 0.0,
+        }
+    }
+
+    #[test]
+    fn open_trade_survives_a_restart() {
+        let dir = std::env::temp_dir().join(format!("journal-test-{}", std::process::id()));
+        let (rows, open) = (dir.join("journal.csv"), dir.join("journal-open-live.csv"));
+
+        let mut before = Journal::new(rows.clone(), "live")
+            .with_open_trades(open.clone())
+            .unwrap();
+        assert!(before
+            .record_fill(&fill(Side::Buy, 100.0, 1), "manual")
+            .unwrap()
+            .is_none());
+        drop(before);
+
+        let mut after = Journal::new(rows.clone(), "live")
+            .with_open_trades(open)
+            .unwrap();
+        let entry = after
+            .record_fill(&fill(Side::Sell, 110.0, 2), "manual")
+            .unwrap()
+            .expect("the sell closes the trade opened before the restart");
+        let written = load(&rows).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!((entry.entry_time, entry.entry_price), (1, 100.0));
+        assert_eq!(entry.pnl, 20.0);
+        assert_eq!(written.len(), 1);
+        assert!(after.open.is_empty());
+    }
+}
@@ -1,3 +1,38 @@
+pub mod account;
+pub mod actions;
+pub mod alerts;
+pub mod annotations;
+pub mod api;
+pub mod app;
+pub mod auth;
+pub mod backtest;
+pub mod cli;
+pub mod config;
 pub mod constants;
+pub mod credentials;
+pub mod drawings;
+pub mod export;
+pub mod fees;
+pub mod grpc;
+pub mod headless;
+pub mod history;
+pub mod import;
+pub mod indicators;
+pub mod instrument;
+pub mod journal;
+pub mod margin;
+pub mod metrics;
 pub mod models;
+pub mod orders;
+pub mod paper;
+pub mod paths;
+pub mod recovery;
+pub mod replay;
+pub mod rest;
+pub mod secrets;
+pub mod session;
+pub mod sinks;
+pub mod sizing;
+pub mod store;
+pub mod strategy;
 pub mod ui;
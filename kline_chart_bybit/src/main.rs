@@ -1,55 +1,814 @@
+use chrono::{NaiveDate, NaiveTime, Utc};
+use clap::Parser;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{
+    stream::{self, SplitSink, SplitStream},
+    SinkExt, Stream, StreamExt,
+};
 use kline_chart_bybit::{
-    constants::{USER_AGENT, VISIBLE_RANGE, WEBSOCKET_URL},
-    models::{KlineResponse, SubscribeMessage},
-    ui::CandlestickChart,
+    actions::{spawn_action, ActionResult},
+    alerts::Channel,
+    api::{self, SharedSnapshot},
+    app::App,
+    auth::{connect_private, Signer, WsStream},
+    backtest::{
+        self, grid_search, optimize::parse_range, walk_forward, walk_forward::parse_windows,
+        write_report, BacktestConfig, WalkForward,
+    },
+    cli::{
+        BacktestArgs, CandleSourceArgs, Cli, Command, DownloadArgs, DownloadFormat, ExportArgs,
+        ImportArgs, JournalCommand, OptimizeArgs,
+    },
+    config::{endpoints, ChartConfig, Config, DEFAULT_PROFILE},
+    constants::{ORDERBOOK_DEPTH, TICK_SIZE, USER_AGENT},
+    credentials::store_keys_interactive,
+    export::{write_candles, write_indicators, write_trades},
+    fees::FeeModel,
+    grpc,
+    headless::HeadlessFeed,
+    history::{
+        fetch_candles, fetch_candles_with_progress, fetch_recent_trades, load_csv,
+        normalize_interval, write_csv,
+    },
+    import::{self, ColumnMap, ImportFormat},
+    indicators::ma_series,
+    instrument::Instrument,
+    journal::{self, Journal, JournalEntry},
+    metrics::{Metrics, METRICS},
+    models::{Candle, SpreadSeries, SubscribeMessage},
+    recovery::{self, RunMarker},
+    replay::{self, Recorder},
+    rest::RestClient,
+    secrets::{encrypt_keys_interactive, unlock_interactive},
+    session::SessionState,
+    sinks,
+    store::{self, CandleStore},
+    strategy::{PositionCaps, StrategyKind},
+    ui::GraphicsRenderer,
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::{io, time::Duration};
-use tokio::time::sleep;
+use std::{
+    io::{self, Write},
+    pin::Pin,
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    time::{interval, MissedTickBehavior},
+};
 use tokio_tungstenite::{
     connect_async,
-    tungstenite::{client::IntoClientRequest, protocol::Message},
+    tungstenite::{client::IntoClientRequest, protocol::Message, Error as WsError},
 };
 
+/// Bybit drops idle connections, so the private stream is pinged this often.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(20);
+
+/// First wait before reconnecting a dropped stream; it doubles per failed try.
+const RECONNECT_MIN_DELAY: Duration = Duration::from_secs(1);
+
+/// Longest wait between reconnect tries.
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+
+type PrivateConnection = (SplitSink<WsStream, Message>, SplitStream<WsStream>);
+
+/// Public market data, from the exchange or a replay.
+type PublicStream = Pin<Box<dyn Stream<Item = Result<Message, WsError>>>>;
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // WebSocket setup
-    let mut request = WEBSOCKET_URL.into_client_request()?;
+    let cli = Cli::parse();
+    let mut config = Config::load()?;
+    config.endpoints.clone().install();
+    match &cli.command {
+        Some(Command::Backtest(args)) => return run_backtest(args, &config).await,
+        Some(Command::Optimize(args)) => return run_optimize(args, &config).await,
+        Some(Command::Export(args)) => return run_export(args, &config).await,
+        Some(Command::Download(args)) => return run_download(args, &config).await,
+        Some(Command::Import(args)) => return run_import(args, &config),
+        Some(Command::Journal(command)) => return run_journal(command),
+        None => {}
+    }
+    if cli.store_keys {
+        return store_keys_interactive(cli.profile.as_deref().unwrap_or(DEFAULT_PROFILE));
+    }
+    if cli.encrypt_keys {
+        return encrypt_keys_interactive(cli.profile.as_deref().unwrap_or(DEFAULT_PROFILE));
+    }
+    // Flags win over the config file
+    if let Some(symbol) = &cli.symbol {
+        config.chart.symbol = symbol.clone();
+    }
+    if let Some(interval) = &cli.interval {
+        config.chart.interval = interval.clone();
+    }
+    let colors = config.colors.chart_colors()?;
+    // Headless mode only reads market data, so it never needs the credentials
+    if !cli.headless {
+        if let Some(secrets) = unlock_interactive()? {
+            config.set_secrets(secrets);
+        }
+    }
+    let spread = match &cli.spread {
+        Some(spec) => Some(
+            SpreadSeries::parse(spec, cli.spread_op)
+                .ok_or_else(|| format!("invalid --spread '{spec}', expected BASE/QUOTE"))?,
+        ),
+        None => None,
+    };
+
+    // Market data: the live public stream, or a recorded session. Only the
+    // live one has topics to resubscribe to after a reconnect
+    let topics = match &cli.replay {
+        Some(_) => None,
+        None => Some(public_topics(
+            &config.chart,
+            cli.compare.as_deref(),
+            spread.as_ref(),
+        )),
+    };
+    let mut read: PublicStream = match &cli.replay {
+        Some(path) => {
+            let events = replay::load(path, &config.chart.symbol, &config.chart.interval)?;
+            eprintln!("Replaying {} frames from {}", events.len(), path.display());
+            let frames = replay::replay_stream(events, cli.speed);
+            if cli.headless {
+                Box::pin(frames)
+            } else {
+                // Keep the chart up for browsing after the last frame
+                Box::pin(frames.chain(stream::pending()))
+            }
+        }
+        None => {
+            let stream = connect_public(topics.as_deref().unwrap_or_default()).await?;
+            eprintln!("WebSocket connected");
+            Box::pin(stream)
+        }
+    };
+    if cli.headless {
+        let strategy = cli.strategy.map(|kind| kind.build(&cli.strategy_params));
+        return run_headless(&mut read, HeadlessFeed::new(&config.chart, strategy)).await;
+    }
+
+    // Authenticated private stream and REST client, when credentials are configured
+    // Paper trading never talks to the account, so it skips the private side
+    // A replay never trades the account either
+    let profile = match cli.paper {
+        Some(_) => None,
+        None if cli.replay.is_some() => None,
+        None => config.initial_profile(cli.profile.as_deref())?,
+    };
+    let (mut private, rest) = match &profile {
+        Some(name) => {
+            let (connection, rest) = connect_profile(&config, name).await?;
+            println!("Private WebSocket connected ({name})");
+            (Some(connection), Some(rest))
+        }
+        None => (None, None),
+    };
+
+    let symbol = &config.chart.symbol;
+    let instrument = match Instrument::fetch(symbol).await {
+        Ok(instrument) => instrument,
+        Err(e) => {
+            eprintln!("Using default contract specs for {symbol}: {e}");
+            Instrument::fallback(symbol)
+        }
+    };
+
+    // Terminal setup
+    let terminal = setup_terminal()?;
+    let mut app = App::new(&config.chart);
+    app.chart.set_colors(colors);
+    app.set_keys(config.keys.clone());
+    app.set_instrument(instrument);
+    app.set_hotkeys(config.hotkeys.clone());
+    app.set_export_dir(config.export_dir(), config.export_format);
+    // Replayed candles are not stored, so they cannot mix with live history
+    let mut marker = None;
+    if spread.is_none() && cli.replay.is_none() {
+        let (run_marker, unclean) = RunMarker::acquire()?;
+        marker = Some(run_marker);
+        match CandleStore::open(&store::default_path()) {
+            Ok(store) => {
+                // After a crash, close the gap the chart would otherwise show
+                let recovered = if unclean {
+                    Some(recovery::backfill(&store).await)
+                } else {
+                    None
+                };
+                app.set_store(store);
+                match recovered {
+                    Some(Ok(added)) => app.set_status(format!(
+                        "Recovered from an unclean exit, backfilled {} candles",
+                        added
+                    )),
+                    Some(Err(e)) => app.set_status(format!("Backfill after crash failed: {}", e)),
+                    None => {}
+                }
+            }
+            Err(e) => app.set_status(format!("Candle store unavailable: {}", e)),
+        }
+    }
+    if let Some(strategy) = cli.strategy {
+        let qty = cli.strategy_qty.unwrap_or(config.hotkeys.size);
+        let caps = PositionCaps {
+            qty,
+            max_position: cli.max_position.unwrap_or(qty),
+        };
+        app.set_strategy(
+            strategy.build(&cli.strategy_params),
+            cli.live_strategy,
+            caps,
+        );
+    }
+    app.set_trading_enabled(rest.is_some());
+    match cli.paper {
+        Some(balance) => app.set_paper_trading(balance, config.fees.clone()),
+        None => app.set_profiles(config.profile_names(), profile),
+    }
+    if cli.paper.is_some() || rest.is_some() {
+        let source = if cli.paper.is_some() { "paper" } else { "live" };
+        let mut journal = Journal::new(journal::default_path(), source);
+        // The paper account starts flat every run; a live one may not
+        if cli.paper.is_none() {
+            journal = journal.with_open_trades(journal::open_trades_path(source))?;
+        }
+        app.set_journal(journal);
+    }
+    if let Some(state) = SessionState::load() {
+        app.restore_session(&state);
+    }
+    if let Some(path) = &cli.record {
+        app.set_recorder(Recorder::open(path)?);
+    }
+    if let Some(compare) = cli.compare {
+        app.chart.set_comparison_symbol(compare);
+    }
+    if let Some(spread) = spread {
+        app.set_spread(spread);
+    }
+    if let Some(redis) = config.redis.clone() {
+        let errors = app.sinks().error_sender();
+        app.sinks().add(sinks::redis::spawn(redis, errors)?);
+    }
+    if let Some(kafka) = config.kafka.clone() {
+        let errors = app.sinks().error_sender();
+        app.sinks().add(sinks::kafka::spawn(kafka, errors)?);
+    }
+    if let Some(mqtt) = config.mqtt.clone() {
+        let errors = app.sinks().error_sender();
+        app.sinks().add(sinks::mqtt::spawn(mqtt, errors));
+    }
+    let mut alert_channels = Vec::new();
+    if let Some(telegram) = config.telegram.clone() {
+        let errors = app.sinks().error_sender();
+        app.sinks().add(sinks::telegram::spawn(telegram, errors));
+        alert_channels.push(Channel::Telegram);
+    }
+    if let Some(discord) = config.discord.clone() {
+        let errors = app.sinks().error_sender();
+        app.sinks().add(sinks::discord::spawn(discord, errors));
+        alert_channels.push(Channel::Discord);
+    }
+    if let Some(email) = config.email.clone() {
+        let errors = app.sinks().error_sender();
+        app.sinks().add(sinks::email::spawn(email, errors)?);
+        alert_channels.push(Channel::Email);
+    }
+    if let Some(webhook) = config.webhook.clone() {
+        let errors = app.sinks().error_sender();
+        app.sinks().add(sinks::webhook::spawn(webhook, errors));
+        alert_channels.push(Channel::Webhook);
+    }
+    app.set_alert_routes(config.alerts.routes(&alert_channels));
+    if let Some(target) = &cli.arrow_stream {
+        let errors = app.sinks().error_sender();
+        app.sinks().add(sinks::arrow::spawn(target, errors)?);
+    }
+    if let Some(addr) = cli.grpc {
+        let feed = grpc::candle_channel();
+        let errors = app.sinks().error_sender();
+        grpc::serve(addr, store::default_path(), feed.clone(), errors).await?;
+        app.set_candle_feed(feed);
+    }
+    if let Some(addr) = cli.serve {
+        let snapshot = SharedSnapshot::default();
+        let errors = app.sinks().error_sender();
+        api::serve(addr, snapshot.clone(), errors).await?;
+        app.set_api(snapshot);
+    }
+    let mut graphics = cli.graphics.protocol().map(GraphicsRenderer::new);
+    app.chart.set_raster(graphics.is_some());
+
+    // Main event loop
+    let mut executor = Executor::new(rest);
+    run_event_loop(
+        &mut app,
+        &mut read,
+        topics.as_deref(),
+        &mut private,
+        &mut executor,
+        &config,
+        terminal,
+        &mut graphics,
+    )
+    .await?;
+    if let Err(e) = app.session_state().save() {
+        eprintln!("Could not save the session: {e}");
+    }
+    if let Some(marker) = marker {
+        marker.release()?;
+    }
+
+    if let Some(graphics) = &graphics {
+        graphics.clear(&mut io::stdout())?;
+    }
+
+    // Cleanup
+    cleanup_terminal()?;
+
+    Ok(())
+}
+
+/// The chart's public topics: kline, public trades and the order book, plus
+/// the klines of the comparison and spread symbols.
+fn public_topics(
+    chart: &ChartConfig,
+    compare: Option<&str>,
+    spread: Option<&SpreadSeries>,
+) -> Vec<String> {
+    let symbol = &chart.symbol;
+    let mut args = vec![
+        format!("kline.{}.{symbol}", chart.interval),
+        format!("publicTrade.{symbol}"),
+        format!("orderbook.{ORDERBOOK_DEPTH}.{symbol}"),
+    ];
+    let mut extra = vec![format!("kline.D.{symbol}")];
+    if let Some(compare) = compare {
+        extra.push(format!("kline.1.{compare}"));
+    }
+    if let Some(spread) = spread {
+        for leg in [&spread.base, &spread.quote] {
+            extra.push(format!("kline.1.{leg}"));
+        }
+    }
+    for topic in extra {
+        if !args.contains(&topic) {
+            args.push(topic);
+        }
+    }
+    args
+}
+
+/// Connects to the public stream and subscribes to `topics`.
+async fn connect_public(
+    topics: &[String],
+) -> Result<SplitStream<WsStream>, Box<dyn std::error::Error>> {
+    let mut request = endpoints().public_ws.as_str().into_client_request()?;
     request
         .headers_mut()
         .insert("User-Agent", USER_AGENT.parse()?);
 
     let (ws_stream, _) = connect_async(request).await?;
-    println!("WebSocket connected");
 
-    let (mut write, mut read) = ws_stream.split();
-
-    // Subscribe to ETHUSDT kline
+    let (mut write, read) = ws_stream.split();
     let subscribe_msg = SubscribeMessage {
         op: "subscribe".to_string(),
-        args: vec!["kline.1.ETHUSDT".to_string()],
+        args: topics.to_vec(),
     };
 
     write
         .send(Message::Text(serde_json::to_string(&subscribe_msg)?))
         .await?;
+    Ok(read)
+}
 
-    // Terminal setup
-    let terminal = setup_terminal()?;
-    let mut chart = CandlestickChart::new(VISIBLE_RANGE);
+/// Prints the feed's NDJSON until the stream ends, Ctrl-C or stdout closes.
+async fn run_headless(
+    read: &mut PublicStream,
+    mut feed: HeadlessFeed,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut stdout = io::stdout();
+    loop {
+        tokio::select! {
+            msg = read.next() => match msg {
+                Some(Ok(Message::Text(text))) => {
+                    for line in feed.handle_message(&text) {
+                        if writeln!(stdout, "{line}").is_err() {
+                            return Ok(());
+                        }
+                    }
+                }
+                Some(Err(e)) => return Err(e.into()),
+                None => return Ok(()),
+                _ => {}
+            },
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
 
-    // Main event loop
-    run_event_loop(&mut chart, &mut read, terminal).await?;
+/// Candles for a backtest, from a CSV file or downloaded.
+async fn load_candles(
+    source: &CandleSourceArgs,
+    symbol: &str,
+) -> Result<Vec<Candle>, Box<dyn std::error::Error>> {
+    let candles = match &source.file {
+        Some(path) => load_csv(path)?,
+        None if source.from_store => {
+            let interval = normalize_interval(&source.interval)
+                .ok_or_else(|| format!("invalid interval '{}'", source.interval))?;
+            CandleStore::open(&store::default_path())?.series(symbol, &interval)?
+        }
+        None => {
+            let end = Utc::now().timestamp_millis();
+            let start = end - source.days * 24 * 60 * 60 * 1000;
+            println!(
+                "Downloading {} days of {} {} candles...",
+                source.days, source.interval, symbol
+            );
+            fetch_candles(symbol, &source.interval, start, end)
+                .await
+                .map_err(|e| e.to_string())?
+        }
+    };
+    if candles.is_empty() {
+        return Err("no candles to backtest".into());
+    }
+    Ok(candles)
+}
 
-    // Cleanup
-    cleanup_terminal()?;
+/// Headless `optimize` subcommand: grid-searches the strategy parameters and
+/// prints the best sets.
+async fn run_optimize(
+    args: &OptimizeArgs,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let fast = parse_range(&args.fast)?;
+    let slow = parse_range(&args.slow)?;
+    let symbol = args
+        .source
+        .symbol
+        .as_deref()
+        .unwrap_or(&config.chart.symbol);
+    let candles = load_candles(&args.source, symbol).await?;
+    let backtest_config = BacktestConfig {
+        capital: args.capital,
+        fees: if args.no_fees {
+            FeeModel::zero()
+        } else {
+            config.fees.clone()
+        },
+        tick_size: TICK_SIZE,
+    };
+
+    if let Some(windows) = &args.walk_forward {
+        let (in_sample, out_of_sample) = parse_windows(windows)?;
+        let result = walk_forward(
+            &candles,
+            args.strategy,
+            &fast,
+            &slow,
+            &backtest_config,
+            args.metric,
+            in_sample,
+            out_of_sample,
+        );
+        print_walk_forward(&result);
+        return Ok(());
+    }
+
+    let results = grid_search(
+        &candles,
+        args.strategy,
+        &fast,
+        &slow,
+        &backtest_config,
+        args.metric,
+    );
+    println!(
+        "{} parameter sets over {} candles, best by {:?}:",
+        results.len(),
+        candles.len(),
+        args.metric
+    );
+    println!(
+        "{:>5} {:>5} {:>9} {:>7} {:>7} {:>8} {:>7} {:>6}",
+        "fast", "slow", "return%", "trades", "win%", "pf", "maxdd%", "sharpe"
+    );
+    for result in results.iter().take(args.top) {
+        let m = &result.metrics;
+        println!(
+            "{:>5} {:>5} {:>+9.2} {:>7} {:>7.1} {:>8.2} {:>7.2} {:>6.2}",
+            result.params.fast,
+            result.params.slow,
+            m.total_return,
+            m.trades,
+            m.win_rate,
+            m.profit_factor,
+            m.max_drawdown,
+            m.sharpe
+        );
+    }
+    Ok(())
+}
+
+fn print_walk_forward(result: &WalkForward) {
+    println!(
+        "{:<16} {:<16} {:>5} {:>5} {:>8} {:>8} {:>7}",
+        "out-of-sample", "until", "fast", "slow", "IS ret%", "OOS ret%", "trades"
+    );
+    for fold in &result.folds {
+        println!(
+            "{:<16} {:<16} {:>5} {:>5} {:>+8.2} {:>+8.2} {:>7}",
+            format_time(fold.start),
+            format_time(fold.end),
+            fold.params.fast,
+            fold.params.slow,
+            fold.in_sample.total_return,
+            fold.out_of_sample.total_return,
+            fold.out_of_sample.trades
+        );
+    }
+    let m = &result.metrics;
+    println!();
+    println!("Out-of-sample, {} folds:", result.folds.len());
+    println!("Total return:  {:+.2}%", m.total_return);
+    println!("Trades:        {}", m.trades);
+    println!("Win rate:      {:.1}%", m.win_rate);
+    println!("Profit factor: {:.2}", m.profit_factor);
+    println!("Max drawdown:  {:.2}%", m.max_drawdown);
+    println!("Sharpe:        {:.2}", m.sharpe);
+}
+
+fn format_time(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+/// Headless `backtest` subcommand: loads the candles, runs and prints the metrics.
+async fn run_backtest(
+    args: &BacktestArgs,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let symbol = args
+        .source
+        .symbol
+        .as_deref()
+        .unwrap_or(&config.chart.symbol);
+    let candles = load_candles(&args.source, symbol).await?;
+
+    let mut strategy = args.strategy.build(&args.strategy_params);
+    let backtest_config = BacktestConfig {
+        capital: args.capital,
+        fees: if args.no_fees {
+            FeeModel::zero()
+        } else {
+            config.fees.clone()
+        },
+        tick_size: TICK_SIZE,
+    };
+    let result = backtest::run(&candles, strategy.as_mut(), &backtest_config);
+    let metrics = &result.metrics;
+    println!("Strategy:      {}", result.strategy);
+    println!("Candles:       {}", candles.len());
+    println!("Trades:        {}", metrics.trades);
+    println!("Total return:  {:+.2}%", metrics.total_return);
+    println!("Win rate:      {:.1}%", metrics.win_rate);
+    println!("Profit factor: {:.2}", metrics.profit_factor);
+    println!("Max drawdown:  {:.2}%", metrics.max_drawdown);
+    println!("Sharpe:        {:.2}", metrics.sharpe);
+    let fees: f64 = result.trades.iter().map(|t| t.fees).sum();
+    println!("Fees paid:     {:.2}", fees);
+
+    if let Some(path) = &args.report {
+        let mut parameters = vec![
+            (String::from("Symbol"), symbol.to_string()),
+            (String::from("Strategy"), result.strategy.clone()),
+            (String::from("Candles"), candles.len().to_string()),
+            (String::from("Capital"), format!("{:.2}", args.capital)),
+            (
+                String::from("Fees"),
+                format!(
+                    "maker {} / taker {}, slippage {:?}",
+                    backtest_config.fees.maker,
+                    backtest_config.fees.taker,
+                    backtest_config.fees.slippage
+                ),
+            ),
+        ];
+        if args.strategy == StrategyKind::MaCross {
+            parameters.push((String::from("Fast"), args.strategy_params.fast.to_string()));
+            parameters.push((String::from("Slow"), args.strategy_params.slow.to_string()));
+        }
+        write_report(path, &result, &parameters)?;
+        println!("Report written to {}", path.display());
+    }
+
+    if args.journal {
+        let entries: Vec<JournalEntry> = result
+            .trades
+            .iter()
+            .map(|trade| JournalEntry::from_closed_trade(symbol, trade, &result.strategy))
+            .collect();
+        let path = journal::default_path();
+        journal::append(&path, &entries)?;
+        println!("{} trades journaled to {}", entries.len(), path.display());
+    }
+    Ok(())
+}
+
+/// Headless `export` subcommand: downloads a range of candles, or the latest
+/// public trades, into a CSV or Parquet file.
+async fn run_export(args: &ExportArgs, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let format = args.format.unwrap_or(config.export_format);
+    let symbol = args.symbol.as_deref().unwrap_or(&config.chart.symbol);
+    if args.trades {
+        let trades = fetch_recent_trades(symbol)
+            .await
+            .map_err(|e| e.to_string())?;
+        let path = args.out.clone().unwrap_or_else(|| {
+            let now = Utc::now().timestamp_millis();
+            config
+                .export_dir()
+                .join(format!("{}-trades-{}.{}", symbol, now, format.extension()))
+        });
+        write_trades(&path, &trades, format).map_err(|e| e.to_string())?;
+        println!("{} trades written to {}", trades.len(), path.display());
+        return Ok(());
+    }
+
+    let day_start = |date: NaiveDate| date.and_time(NaiveTime::MIN).and_utc().timestamp_millis();
+    let end = args
+        .to
+        .map_or_else(|| Utc::now().timestamp_millis(), day_start);
+    let start = args
+        .from
+        .map_or(end - args.days * 24 * 60 * 60 * 1000, day_start);
+    if start >= end {
+        return Err("--from must be before --to".into());
+    }
+
+    let candles = fetch_candles(symbol, &args.interval, start, end)
+        .await
+        .map_err(|e| e.to_string())?;
+    let path = args.out.clone().unwrap_or_else(|| {
+        config.export_dir().join(format!(
+            "{}-{}-{}-{}.{}",
+            symbol,
+            args.interval,
+            format_date(start),
+            format_date(end),
+            format.extension()
+        ))
+    });
+    write_candles(&path, &candles, format).map_err(|e| e.to_string())?;
+    println!("{} candles written to {}", candles.len(), path.display());
+
+    if !args.ma.is_empty() {
+        let series: Vec<_> = args
+            .ma
+            .iter()
+            .map(|&window| ma_series(&candles, window))
+            .collect();
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let indicators_path =
+            path.with_file_name(format!("{}-indicators.{}", stem, format.extension()));
+        write_indicators(&indicators_path, &candles, &series, format).map_err(|e| e.to_string())?;
+        println!(
+            "{} indicator series written to {}",
+            series.len(),
+            indicators_path.display()
+        );
+    }
+    Ok(())
+}
 
+/// Headless `download` subcommand: pages a long range of candles from the
+/// REST endpoint into the candle store or a CSV.
+async fn run_download(
+    args: &DownloadArgs,
+    config: &Config,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let symbol = args.symbol.as_deref().unwrap_or(&config.chart.symbol);
+    let interval = normalize_interval(&args.interval)
+        .ok_or_else(|| format!("invalid interval '{}'", args.interval))?;
+    let day_start = |date: NaiveDate| date.and_time(NaiveTime::MIN).and_utc().timestamp_millis();
+    let start = day_start(args.from);
+    let end = args
+        .to
+        .map_or_else(|| Utc::now().timestamp_millis(), day_start);
+    if start >= end {
+        return Err("--from must be before --to".into());
+    }
+
+    let candles = fetch_candles_with_progress(symbol, &interval, start, end, |count| {
+        eprint!("\rDownloaded {} candles", count);
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    eprintln!();
+
+    let path = match (args.format, &args.out) {
+        (_, Some(path)) => path.clone(),
+        (DownloadFormat::Sqlite, None) => store::default_path(),
+        (DownloadFormat::Csv, None) => config.export_dir().join(format!(
+            "{}-{}-{}-{}.csv",
+            symbol,
+            interval,
+            format_date(start),
+            format_date(end)
+        )),
+    };
+    match args.format {
+        DownloadFormat::Csv => write_csv(&path, &candles)?,
+        DownloadFormat::Sqlite => {
+            CandleStore::open(&path)?.insert_all(symbol, &interval, &candles)?
+        }
+    }
+    println!("{} candles written to {}", candles.len(), path.display());
+    Ok(())
+}
+
+/// Headless `import` subcommand: reads candles from an external file into
+/// the candle store, where the chart and `backtest --from-store` find them.
+fn run_import(args: &ImportArgs, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let symbol = args.symbol.as_deref().unwrap_or(&config.chart.symbol);
+    let interval = normalize_interval(&args.interval)
+        .ok_or_else(|| format!("invalid interval '{}'", args.interval))?;
+    let format = args
+        .format
+        .or_else(|| ImportFormat::from_path(&args.path))
+        .ok_or("can't tell the file format from its extension, pass --format")?;
+    let mut columns = ColumnMap::default();
+    for mapping in &args.columns {
+        columns.set(mapping)?;
+    }
+
+    let candles = import::read_candles(&args.path, format, &columns, &args.time_format)
+        .map_err(|e| e.to_string())?;
+    let (Some(first), Some(last)) = (candles.first(), candles.last()) else {
+        return Err("no candles in the file".into());
+    };
+    let path = args.store.clone().unwrap_or_else(store::default_path);
+    CandleStore::open(&path)?.insert_all(symbol, &interval, &candles)?;
+    println!(
+        "{} {} {} candles from {} to {} imported into {}",
+        candles.len(),
+        symbol,
+        interval,
+        format_time(first.start),
+        format_time(last.start),
+        path.display()
+    );
+    Ok(())
+}
+
+fn format_date(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .map(|t| t.format("%Y%m%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Headless `journal` subcommands.
+fn run_journal(command: &JournalCommand) -> Result<(), Box<dyn std::error::Error>> {
+    let path = journal::default_path();
+    let mut entries = journal::load(&path)?;
+    match command {
+        JournalCommand::Export {
+            format,
+            out,
+            since,
+            source,
+        } => {
+            if let Some(date) = since {
+                let start = date.and_time(NaiveTime::MIN).and_utc().timestamp_millis();
+                entries.retain(|entry| entry.exit_time >= start);
+            }
+            if let Some(source) = source {
+                entries.retain(|entry| &entry.source == source);
+            }
+            match out {
+                Some(out) => {
+                    journal::export(&entries, *format, std::fs::File::create(out)?)?;
+                    println!("{} trades exported to {}", entries.len(), out.display());
+                }
+                None => journal::export(&entries, *format, io::stdout().lock())?,
+            }
+        }
+        JournalCommand::Note { text } => {
+            let last = entries.last_mut().ok_or("the journal is empty")?;
+            last.notes = text.clone();
+            journal::save(&path, &entries)?;
+        }
+    }
     Ok(())
 }
 
@@ -67,56 +826,234 @@ fn cleanup_terminal() -> io::Result<()> {
     Ok(())
 }
 
+/// When to try a dropped stream again: after a delay that doubles with every
+/// failed try, up to [`RECONNECT_MAX_DELAY`].
+#[derive(Debug, Default)]
+struct Backoff {
+    attempts: u32,
+    next: Option<Instant>,
+}
+
+impl Backoff {
+    /// Schedules the next try.
+    fn schedule(&mut self) {
+        let delay = RECONNECT_MIN_DELAY
+            .saturating_mul(1 << self.attempts.min(6))
+            .min(RECONNECT_MAX_DELAY);
+        self.next = Some(Instant::now() + delay);
+        self.attempts += 1;
+    }
+
+    /// Whether a try is scheduled and its time has come.
+    fn due(&self) -> bool {
+        self.next.is_some_and(|next| Instant::now() >= next)
+    }
+
+    /// Back to the short delay once connected.
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Runs the TUI until the user quits. Dropped streams are reconnected with
+/// [`Backoff`]: the public one resubscribing to `topics`, unless it is a
+/// replay (`topics` is `None`), whose end or error quits instead.
+#[allow(clippy::too_many_arguments)]
 async fn run_event_loop(
-    chart: &mut CandlestickChart,
-    read: &mut futures_util::stream::SplitStream<
-        tokio_tungstenite::WebSocketStream<
-            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
-        >,
-    >,
+    app: &mut App,
+    read: &mut PublicStream,
+    topics: Option<&[String]>,
+    private: &mut Option<PrivateConnection>,
+    executor: &mut Executor,
+    config: &Config,
     mut terminal: Terminal<CrosstermBackend<io::Stdout>>,
+    graphics: &mut Option<GraphicsRenderer>,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let mut heartbeat = interval(HEARTBEAT_INTERVAL);
+    // One tick for the whole loop, so a busy feed can't keep restarting the wait
+    let mut tick = interval(Duration::from_millis(100));
+    tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut public_connected = true;
+    let (mut public_retry, mut private_retry) = (Backoff::default(), Backoff::default());
     loop {
         tokio::select! {
-            msg = read.next() => {
+            msg = next_private(private) => {
                 match msg {
+                    Some(Ok(Message::Text(text))) => app.handle_private_message(&text),
+                    Some(Err(e)) => {
+                        app.set_status(format!("Private WebSocket error: {}, reconnecting", e));
+                        *private = None;
+                        private_retry.schedule();
+                    }
+                    None => {
+                        app.set_status("Private WebSocket closed, reconnecting".to_string());
+                        *private = None;
+                        private_retry.schedule();
+                    }
+                    _ => {}
+                }
+            }
+
+            Some(result) = executor.results.recv() => app.handle_action_result(result),
+
+            _ = heartbeat.tick() => {
+                if let Some((write, _)) = private.as_mut() {
+                    let ping = Message::Text(r#"{"op":"ping"}"#.to_string());
+                    if let Err(e) = write.send(ping).await {
+                        app.set_status(format!("Private WebSocket error: {}, reconnecting", e));
+                        *private = None;
+                        private_retry.schedule();
+                    }
+                }
+            }
+
+            msg = read.next() => {
+                let dropped = match msg {
                     Some(Ok(Message::Text(text))) => {
-                        if let Ok(response) = serde_json::from_str::<KlineResponse>(&text) {
-                            for kline_data in response.data {
-                                chart.update_from_kline(&kline_data);
-                            }
-                        }
+                        app.handle_message(&text);
+                        None
                     }
-                    Some(Err(e)) => {
-                        eprintln!("WebSocket error: {}", e);
+                    Some(Err(e)) => Some(format!("WebSocket error: {}", e)),
+                    None => Some(String::from("WebSocket closed")),
+                    _ => None,
+                };
+                if let Some(reason) = dropped {
+                    if topics.is_none() {
                         break;
                     }
-                    None => break,
-                    _ => {}
+                    app.set_status(format!("{}, reconnecting", reason));
+                    // Nothing to read until the reconnect replaces it
+                    *read = Box::pin(stream::pending());
+                    public_connected = false;
+                    public_retry.schedule();
                 }
             }
 
-            _ = sleep(Duration::from_millis(100)) => {
-                if check_quit()? {
+            _ = tick.tick() => {
+                if handle_input(app)? {
                     break;
                 }
+                if let Some(profile) = app.take_profile_switch() {
+                    private_retry.reset();
+                    match connect_profile(config, &profile).await {
+                        Ok((connection, rest)) => {
+                            *private = Some(connection);
+                            executor.rest = Some(rest);
+                        }
+                        Err(e) => {
+                            app.set_status(format!("Profile '{}' failed: {}", profile, e));
+                            *private = None;
+                            executor.rest = None;
+                        }
+                    }
+                }
+                if let (Some(topics), true) = (topics, public_retry.due()) {
+                    Metrics::increment(&METRICS.public_reconnects);
+                    match connect_public(topics).await {
+                        Ok(stream) => {
+                            *read = Box::pin(stream);
+                            public_connected = true;
+                            public_retry.reset();
+                            app.set_status(String::from("WebSocket reconnected"));
+                        }
+                        Err(e) => {
+                            app.set_status(format!("WebSocket reconnect failed: {}", e));
+                            public_retry.schedule();
+                        }
+                    }
+                }
+                if let (Some(profile), true) = (app.profile(), private.is_none() && private_retry.due()) {
+                    let profile = profile.to_string();
+                    Metrics::increment(&METRICS.private_reconnects);
+                    match connect_profile(config, &profile).await {
+                        Ok((connection, rest)) => {
+                            *private = Some(connection);
+                            executor.rest = Some(rest);
+                            private_retry.reset();
+                            app.set_status(format!("Private WebSocket reconnected ({})", profile));
+                        }
+                        Err(e) => {
+                            app.set_status(format!("Private WebSocket reconnect failed: {}", e));
+                            private_retry.schedule();
+                        }
+                    }
+                }
+                METRICS
+                    .private_connected
+                    .store(private.is_some() as u64, Ordering::Relaxed);
+                executor.run(app);
+                app.publish_snapshot(public_connected, private.is_some());
 
-                terminal.draw(|f| {
-                    chart.draw(f, f.size());
-                })?;
+                let started = Instant::now();
+                terminal.draw(|f| app.draw(f))?;
+                let render = started.elapsed().as_micros() as u64;
+                METRICS.render_micros.store(render, Ordering::Relaxed);
+                if let Some(graphics) = graphics {
+                    graphics.present(terminal.backend_mut(), &app.chart, app.chart_area())?;
+                }
             }
         }
     }
     Ok(())
 }
 
-fn check_quit() -> io::Result<bool> {
-    if event::poll(Duration::from_millis(0))? {
-        if let Event::Key(key) = event::read()? {
-            if key.code == KeyCode::Char('q') {
-                return Ok(true);
+/// Opens the private stream and REST client for one API-key profile.
+async fn connect_profile(
+    config: &Config,
+    name: &str,
+) -> Result<(PrivateConnection, RestClient), Box<dyn std::error::Error>> {
+    let api = config
+        .profile(name)
+        .ok_or_else(|| format!("no API profile named '{name}'"))?;
+    let signer = Signer::new(&api);
+    let stream = connect_private(&signer).await?;
+    Ok((stream.split(), RestClient::new(signer)))
+}
+
+/// Runs the UI's network actions in the background and collects their results.
+struct Executor {
+    rest: Option<RestClient>,
+    sender: UnboundedSender<ActionResult>,
+    results: UnboundedReceiver<ActionResult>,
+}
+
+impl Executor {
+    fn new(rest: Option<RestClient>) -> Self {
+        let (sender, results) = mpsc::unbounded_channel();
+        Self {
+            rest,
+            sender,
+            results,
+        }
+    }
+
+    fn run(&self, app: &mut App) {
+        for action in app.take_actions() {
+            match &self.rest {
+                Some(rest) => spawn_action(rest.clone(), action, self.sender.clone()),
+                None => app.set_status("No API credentials configured".to_string()),
             }
         }
     }
+}
+
+/// Next frame from the private stream, or never if there is no private connection.
+async fn next_private(
+    private: &mut Option<PrivateConnection>,
+) -> Option<Result<Message, tokio_tungstenite::tungstenite::Error>> {
+    match private {
+        Some((_, read)) => read.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn handle_input(app: &mut App) -> io::Result<bool> {
+    while event::poll(Duration::from_millis(0))? {
+        match event::read()? {
+            Event::Key(key) if app.handle_key(key.code) => return Ok(true),
+            Event::Mouse(mouse) => app.handle_mouse(mouse),
+            _ => {}
+        }
+    }
     Ok(false)
 }
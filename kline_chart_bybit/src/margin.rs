@@ -0,0 +1,74 @@
+use crate::{models::MarginMode, orders::decimal, rest::RestClient, rest::RestResult};
+use serde::{Deserialize, Serialize};
+
+/// Bybit's answer when the requested leverage or mode is already set.
+const NOT_MODIFIED_CODES: &[&str] = &["(code 110043)", "(code 110026)"];
+
+#[derive(Debug, Deserialize)]
+struct EmptyResult {}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SetLeverageRequest<'a> {
+    category: &'static str,
+    symbol: &'a str,
+    buy_leverage: String,
+    sell_leverage: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SwitchIsolatedRequest<'a> {
+    category: &'static str,
+    symbol: &'a str,
+    /// 0 for cross margin, 1 for isolated.
+    trade_mode: u8,
+    buy_leverage: String,
+    sell_leverage: String,
+}
+
+/// Treats "nothing to change" as success, so applying the dialog twice is harmless.
+fn allow_unchanged(result: RestResult<EmptyResult>) -> RestResult<()> {
+    match result {
+        Ok(_) => Ok(()),
+        Err(e)
+            if NOT_MODIFIED_CODES
+                .iter()
+                .any(|code| e.to_string().contains(code)) =>
+        {
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Sets the same leverage for longs and shorts in `symbol`.
+pub async fn set_leverage(client: &RestClient, symbol: &str, leverage: f64) -> RestResult<()> {
+    let request = SetLeverageRequest {
+        category: "linear",
+        symbol,
+        buy_leverage: decimal(leverage),
+        sell_leverage: decimal(leverage),
+    };
+    allow_unchanged(client.post("/v5/position/set-leverage", &request).await)
+}
+
+/// Switches `symbol` between cross and isolated margin at `leverage`.
+pub async fn switch_margin_mode(
+    client: &RestClient,
+    symbol: &str,
+    mode: MarginMode,
+    leverage: f64,
+) -> RestResult<()> {
+    let request = SwitchIsolatedRequest {
+        category: "linear",
+        symbol,
+        trade_mode: match mode {
+            MarginMode::Cross => 0,
+            MarginMode::Isolated => 1,
+        },
+        buy_leverage: decimal(leverage),
+        sell_leverage: decimal(leverage),
+    };
+    allow_unchanged(client.post("/v5/position/switch-isolated", &request).await)
+}
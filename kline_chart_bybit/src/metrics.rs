@@ -0,0 +1,101 @@
+use std::{
+    fmt::Write,
+    sync::atomic::{AtomicI64, AtomicU64, Ordering},
+};
+
+/// Process-wide counters and gauges, exported at `/metrics` of `--serve` in
+/// the Prometheus text format.
+pub struct Metrics {
+    pub public_messages: AtomicU64,
+    pub private_messages: AtomicU64,
+    /// Tries to reconnect a dropped stream, failed ones included.
+    pub public_reconnects: AtomicU64,
+    pub private_reconnects: AtomicU64,
+    /// 1 while the private stream is connected, otherwise 0.
+    pub private_connected: AtomicU64,
+    pub alert_triggers: AtomicU64,
+    /// Receive time minus exchange time of the latest kline, in milliseconds.
+    pub candle_lag_ms: AtomicI64,
+    /// Duration of the latest frame draw, in microseconds.
+    pub render_micros: AtomicU64,
+}
+
+pub static METRICS: Metrics = Metrics {
+    public_messages: AtomicU64::new(0),
+    private_messages: AtomicU64::new(0),
+    public_reconnects: AtomicU64::new(0),
+    private_reconnects: AtomicU64::new(0),
+    private_connected: AtomicU64::new(0),
+    alert_triggers: AtomicU64::new(0),
+    candle_lag_ms: AtomicI64::new(0),
+    render_micros: AtomicU64::new(0),
+};
+
+impl Metrics {
+    pub fn increment(counter: &AtomicU64) {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Everything in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let load = |counter: &AtomicU64| counter.load(Ordering::Relaxed) as f64;
+        let mut text = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(&str, f64)]| {
+            let _ = writeln!(text, "# HELP {} {}", name, help);
+            let _ = writeln!(text, "# TYPE {} {}", name, kind);
+            for (labels, value) in samples {
+                if labels.is_empty() {
+                    let _ = writeln!(text, "{} {}", name, value);
+                } else {
+                    let _ = writeln!(text, "{}{{{}}} {}", name, labels, value);
+                }
+            }
+        };
+        metric(
+            "kline_chart_messages_received_total",
+            "counter",
+            "Websocket messages received, by stream",
+            &[
+                ("stream=\"public\"", load(&self.public_messages)),
+                ("stream=\"private\"", load(&self.private_messages)),
+            ],
+        );
+        metric(
+            "kline_chart_reconnects_total",
+            "counter",
+            "Tries to reconnect a dropped websocket stream, by stream",
+            &[
+                ("stream=\"public\"", load(&self.public_reconnects)),
+                ("stream=\"private\"", load(&self.private_reconnects)),
+            ],
+        );
+        metric(
+            "kline_chart_private_stream_connected",
+            "gauge",
+            "Whether the private stream is connected",
+            &[("", load(&self.private_connected))],
+        );
+        metric(
+            "kline_chart_alert_triggers_total",
+            "counter",
+            "Price alerts that fired",
+            &[("", load(&self.alert_triggers))],
+        );
+        metric(
+            "kline_chart_candle_lag_seconds",
+            "gauge",
+            "Delay between the exchange stamping the latest kline and receiving it",
+            &[(
+                "",
+                self.candle_lag_ms.load(Ordering::Relaxed) as f64 / 1000.0,
+            )],
+        );
+        metric(
+            "kline_chart_render_seconds",
+            "gauge",
+            "Time spent drawing the latest frame",
+            &[("", load(&self.render_micros) / 1_000_000.0)],
+        );
+        text
+    }
+}
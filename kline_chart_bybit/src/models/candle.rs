@@ -1,24 +1,57 @@
 use super::websocket::KlineData;
+use kline_core::Ohlc;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Candle {
+    pub start: i64,
     pub open: f64,
     pub high: f64,
     pub low: f64,
     pub close: f64,
+    pub volume: f64,
+    pub turnover: f64,
 }
 
 impl Candle {
     pub fn from_kline_data(data: &KlineData) -> Option<Self> {
         Some(Self {
+            start: data.start,
             open: data.open.parse().ok()?,
             high: data.high.parse().ok()?,
             low: data.low.parse().ok()?,
             close: data.close.parse().ok()?,
+            volume: data.volume.parse().ok()?,
+            turnover: data.turnover.parse().ok()?,
         })
     }
 
     pub fn is_bullish(&self) -> bool {
         self.close >= self.open
     }
+
+    pub fn change_percent(&self) -> f64 {
+        if self.open == 0.0 {
+            return 0.0;
+        }
+        (self.close - self.open) / self.open * 100.0
+    }
+}
+
+impl Ohlc for Candle {
+    fn open(&self) -> f64 {
+        self.open
+    }
+
+    fn high(&self) -> f64 {
+        self.high
+    }
+
+    fn low(&self) -> f64 {
+        self.low
+    }
+
+    fn close(&self) -> f64 {
+        self.close
+    }
 }
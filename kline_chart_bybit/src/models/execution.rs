@@ -0,0 +1,38 @@
+use super::{private::ExecutionData, trade::Side};
+
+/// One of our own fills.
+#[derive(Debug, Clone)]
+pub struct Execution {
+    pub exec_id: String,
+    pub order_id: String,
+    pub symbol: String,
+    pub timestamp: i64,
+    pub side: Side,
+    pub price: f64,
+    pub qty: f64,
+    pub fee: f64,
+}
+
+impl Execution {
+    /// Returns `None` for non-trade executions such as funding.
+    pub fn from_execution_data(data: &ExecutionData) -> Option<Self> {
+        if data.exec_type != "Trade" {
+            return None;
+        }
+        let side = match data.side.as_str() {
+            "Buy" => Side::Buy,
+            "Sell" => Side::Sell,
+            _ => return None,
+        };
+        Some(Self {
+            exec_id: data.exec_id.clone(),
+            order_id: data.order_id.clone(),
+            symbol: data.symbol.clone(),
+            timestamp: data.exec_time.parse().ok()?,
+            side,
+            price: data.exec_price.parse().ok()?,
+            qty: data.exec_qty.parse().ok()?,
+            fee: data.exec_fee.parse().unwrap_or_default(),
+        })
+    }
+}
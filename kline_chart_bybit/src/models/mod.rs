@@ -1,5 +1,26 @@
 pub mod candle;
+pub mod execution;
+pub mod order;
+pub mod orderbook;
+pub mod position;
+pub mod private;
+pub mod session;
+pub mod spread;
+pub mod trade;
+pub mod wallet;
 pub mod websocket;
 
 pub use candle::Candle;
-pub use websocket::{KlineData, KlineResponse, SubscribeMessage};
+pub use execution::Execution;
+pub use order::OpenOrder;
+pub use orderbook::OrderBook;
+pub use position::{MarginMode, Position};
+pub use private::{CoinData, ExecutionData, OrderData, PositionData, PrivateResponse, WalletData};
+pub use session::SessionLevels;
+pub use spread::{SpreadOp, SpreadSeries};
+pub use trade::{Side, Trade};
+pub use wallet::{CoinBalance, WalletBalance};
+pub use websocket::{
+    KlineData, KlineResponse, OpResponse, OrderbookData, OrderbookResponse, SubscribeMessage,
+    TopicEnvelope, TradeData, TradeResponse,
+};
@@ -0,0 +1,50 @@
+use super::{private::OrderData, trade::Side};
+
+/// One of our resting orders.
+#[derive(Debug, Clone)]
+pub struct OpenOrder {
+    pub order_id: String,
+    pub symbol: String,
+    pub side: Side,
+    pub order_type: String,
+    /// Limit price; `None` for market orders waiting on a trigger.
+    pub price: Option<f64>,
+    pub qty: f64,
+    /// Quantity not filled yet.
+    pub leaves_qty: f64,
+    pub trigger_price: Option<f64>,
+    pub reduce_only: bool,
+    pub created_time: i64,
+}
+
+/// Statuses in which an order is still working on the book or waiting to trigger.
+const OPEN_STATUSES: &[&str] = &["New", "PartiallyFilled", "Untriggered"];
+
+impl OpenOrder {
+    /// Returns `None` once the order is filled, cancelled or rejected.
+    pub fn from_order_data(data: &OrderData) -> Option<Self> {
+        if !OPEN_STATUSES.contains(&data.order_status.as_str()) {
+            return None;
+        }
+        let side = match data.side.as_str() {
+            "Buy" => Side::Buy,
+            "Sell" => Side::Sell,
+            _ => return None,
+        };
+        let qty = data.qty.parse().ok()?;
+        // Zero means "not set" for Bybit's optional prices
+        let positive = |s: &str| s.parse::<f64>().ok().filter(|p| *p > 0.0);
+        Some(Self {
+            order_id: data.order_id.clone(),
+            symbol: data.symbol.clone(),
+            side,
+            order_type: data.order_type.clone(),
+            price: positive(&data.price),
+            qty,
+            leaves_qty: data.leaves_qty.parse().unwrap_or(qty),
+            trigger_price: positive(&data.trigger_price),
+            reduce_only: data.reduce_only,
+            created_time: data.created_time.parse().unwrap_or_default(),
+        })
+    }
+}
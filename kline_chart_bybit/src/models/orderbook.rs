@@ -0,0 +1,73 @@
+use super::websocket::OrderbookResponse;
+use std::collections::BTreeMap;
+
+/// Local copy of the order book, keyed by price in whole ticks.
+#[derive(Debug, Clone)]
+pub struct OrderBook {
+    tick_size: f64,
+    bids: BTreeMap<i64, f64>,
+    asks: BTreeMap<i64, f64>,
+}
+
+impl OrderBook {
+    pub fn new(tick_size: f64) -> Self {
+        Self {
+            tick_size,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+        }
+    }
+
+    pub fn apply(&mut self, response: &OrderbookResponse) {
+        if response.response_type == "snapshot" {
+            self.bids.clear();
+            self.asks.clear();
+        }
+        let tick_size = self.tick_size;
+        Self::apply_levels(&mut self.bids, &response.data.bids, tick_size);
+        Self::apply_levels(&mut self.asks, &response.data.asks, tick_size);
+    }
+
+    fn apply_levels(side: &mut BTreeMap<i64, f64>, levels: &[[String; 2]], tick_size: f64) {
+        for [price, size] in levels {
+            let (Ok(price), Ok(size)) = (price.parse::<f64>(), size.parse::<f64>()) else {
+                continue;
+            };
+            let tick = (price / tick_size).round() as i64;
+            // A zero size means the level was removed
+            if size == 0.0 {
+                side.remove(&tick);
+            } else {
+                side.insert(tick, size);
+            }
+        }
+    }
+
+    pub fn tick_size(&self) -> f64 {
+        self.tick_size
+    }
+
+    pub fn to_tick(&self, price: f64) -> i64 {
+        (price / self.tick_size).round() as i64
+    }
+
+    pub fn best_bid(&self) -> Option<i64> {
+        self.bids.keys().next_back().copied()
+    }
+
+    pub fn best_ask(&self) -> Option<i64> {
+        self.asks.keys().next().copied()
+    }
+
+    pub fn bid_size(&self, tick: i64) -> Option<f64> {
+        self.bids.get(&tick).copied()
+    }
+
+    pub fn ask_size(&self, tick: i64) -> Option<f64> {
+        self.asks.get(&tick).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+}
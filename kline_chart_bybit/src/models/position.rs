@@ -0,0 +1,75 @@
+use super::{private::PositionData, trade::Side};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarginMode {
+    #[default]
+    Cross,
+    Isolated,
+}
+
+impl MarginMode {
+    pub fn toggled(self) -> Self {
+        match self {
+            MarginMode::Cross => MarginMode::Isolated,
+            MarginMode::Isolated => MarginMode::Cross,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            MarginMode::Cross => "cross",
+            MarginMode::Isolated => "isolated",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub symbol: String,
+    pub side: Side,
+    pub size: f64,
+    pub entry_price: f64,
+    pub mark_price: f64,
+    pub unrealised_pnl: f64,
+    /// Empty on the exchange side when there is no liquidation price.
+    pub liq_price: Option<f64>,
+    /// `None` when unknown, e.g. for paper positions.
+    pub leverage: Option<f64>,
+    pub margin_mode: MarginMode,
+}
+
+impl Position {
+    /// Returns `None` for unparsable data; a flat position has side `None`
+    /// on the exchange and comes back with a zero size.
+    pub fn from_position_data(data: &PositionData) -> Option<Self> {
+        let side = match data.side.as_str() {
+            "Sell" => Side::Sell,
+            _ => Side::Buy,
+        };
+        Some(Self {
+            symbol: data.symbol.clone(),
+            side,
+            size: data.size.parse().ok()?,
+            entry_price: data.entry_price.parse().unwrap_or_default(),
+            mark_price: data.mark_price.parse().unwrap_or_default(),
+            unrealised_pnl: data.unrealised_pnl.parse().unwrap_or_default(),
+            liq_price: data.liq_price.parse().ok().filter(|p| *p > 0.0),
+            leverage: data.leverage.parse().ok(),
+            margin_mode: if data.trade_mode == 1 {
+                MarginMode::Isolated
+            } else {
+                MarginMode::Cross
+            },
+        })
+    }
+
+    /// Re-marks the position at `price` and recomputes its unrealised PnL.
+    pub fn mark_at(&mut self, price: f64) {
+        let direction = match self.side {
+            Side::Buy => 1.0,
+            Side::Sell => -1.0,
+        };
+        self.mark_price = price;
+        self.unrealised_pnl = (price - self.entry_price) * self.size * direction;
+    }
+}
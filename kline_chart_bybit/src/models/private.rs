@@ -0,0 +1,95 @@
+use serde::Deserialize;
+
+/// Frame from a private topic such as `position` or `wallet`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateResponse<T> {
+    pub topic: String,
+    pub creation_time: i64,
+    pub data: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionData {
+    pub symbol: String,
+    pub side: String,
+    pub size: String,
+    #[serde(alias = "avgPrice")]
+    pub entry_price: String,
+    pub mark_price: String,
+    pub unrealised_pnl: String,
+    #[serde(default)]
+    pub liq_price: String,
+    #[serde(default)]
+    pub leverage: String,
+    /// 0 for cross margin, 1 for isolated.
+    #[serde(default)]
+    pub trade_mode: i64,
+}
+
+/// One account from the `wallet` topic or `/v5/account/wallet-balance`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletData {
+    pub account_type: String,
+    #[serde(default)]
+    pub total_equity: String,
+    #[serde(default)]
+    pub total_available_balance: String,
+    #[serde(default)]
+    pub total_initial_margin: String,
+    #[serde(default)]
+    pub coin: Vec<CoinData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinData {
+    pub coin: String,
+    #[serde(default)]
+    pub wallet_balance: String,
+    #[serde(default)]
+    pub equity: String,
+    #[serde(default)]
+    pub unrealised_pnl: String,
+    #[serde(default)]
+    pub cum_realised_pnl: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionData {
+    pub symbol: String,
+    pub side: String,
+    pub order_id: String,
+    pub exec_id: String,
+    pub exec_price: String,
+    pub exec_qty: String,
+    pub exec_fee: String,
+    /// "Trade" for fills; funding and settlement also arrive on this topic.
+    pub exec_type: String,
+    pub exec_time: String,
+}
+
+/// An order from the `order` topic or `/v5/order/realtime`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderData {
+    pub order_id: String,
+    pub symbol: String,
+    pub side: String,
+    pub order_type: String,
+    #[serde(default)]
+    pub price: String,
+    pub qty: String,
+    #[serde(default)]
+    pub leaves_qty: String,
+    #[serde(default)]
+    pub trigger_price: String,
+    pub order_status: String,
+    #[serde(default)]
+    pub reduce_only: bool,
+    #[serde(default)]
+    pub created_time: String,
+}
@@ -0,0 +1,42 @@
+use super::{candle::Candle, websocket::KlineData};
+
+/// Reference levels derived from the daily candle stream.
+#[derive(Debug, Default)]
+pub struct SessionLevels {
+    current: Option<Candle>,
+    previous_close: Option<f64>,
+}
+
+impl SessionLevels {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update_from_kline(&mut self, kline_data: &KlineData) {
+        let Some(candle) = Candle::from_kline_data(kline_data) else {
+            return;
+        };
+        // A new daily candle means the stored one is the previous session
+        if let Some(current) = &self.current {
+            if current.start < candle.start {
+                self.previous_close = Some(current.close);
+            }
+        }
+        self.current = Some(candle);
+    }
+
+    pub fn high(&self) -> Option<f64> {
+        self.current.as_ref().map(|c| c.high)
+    }
+
+    pub fn low(&self) -> Option<f64> {
+        self.current.as_ref().map(|c| c.low)
+    }
+
+    /// Previous session's close; until a rollover is seen, the current session's
+    /// open stands in for it since the market trades continuously.
+    pub fn previous_close(&self) -> Option<f64> {
+        self.previous_close
+            .or_else(|| self.current.as_ref().map(|c| c.open))
+    }
+}
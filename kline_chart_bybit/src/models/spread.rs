@@ -0,0 +1,106 @@
+use super::candle::Candle;
+use clap::ValueEnum;
+use std::collections::BTreeMap;
+
+/// Number of candles kept per leg while waiting for the other leg to catch up.
+const LEG_CAPACITY: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SpreadOp {
+    /// base / quote
+    Ratio,
+    /// base - quote
+    Diff,
+}
+
+/// Synthetic candle series built from two symbols' candles with matching start times.
+#[derive(Debug)]
+pub struct SpreadSeries {
+    pub base: String,
+    pub quote: String,
+    pub op: SpreadOp,
+    base_candles: BTreeMap<i64, Candle>,
+    quote_candles: BTreeMap<i64, Candle>,
+}
+
+impl SpreadSeries {
+    /// Parses a `BASE/QUOTE` pair such as `ETHUSDT/BTCUSDT`.
+    pub fn parse(spec: &str, op: SpreadOp) -> Option<Self> {
+        let (base, quote) = spec.split_once('/')?;
+        if base.is_empty() || quote.is_empty() {
+            return None;
+        }
+        Some(Self {
+            base: base.to_uppercase(),
+            quote: quote.to_uppercase(),
+            op,
+            base_candles: BTreeMap::new(),
+            quote_candles: BTreeMap::new(),
+        })
+    }
+
+    pub fn name(&self) -> String {
+        match self.op {
+            SpreadOp::Ratio => format!("{}/{}", self.base, self.quote),
+            SpreadOp::Diff => format!("{}-{}", self.base, self.quote),
+        }
+    }
+
+    pub fn is_leg(&self, symbol: &str) -> bool {
+        symbol == self.base || symbol == self.quote
+    }
+
+    /// Stores a leg's candle and returns the recomputed spread candle for its start time,
+    /// once both legs have data for it.
+    pub fn update(&mut self, symbol: &str, candle: Candle) -> Option<Candle> {
+        let start = candle.start;
+        let leg = if symbol == self.base {
+            &mut self.base_candles
+        } else if symbol == self.quote {
+            &mut self.quote_candles
+        } else {
+            return None;
+        };
+        leg.insert(start, candle);
+        while leg.len() > LEG_CAPACITY {
+            leg.pop_first();
+        }
+
+        let base = self.base_candles.get(&start)?;
+        let quote = self.quote_candles.get(&start)?;
+        self.combine(base, quote)
+    }
+
+    fn combine(&self, base: &Candle, quote: &Candle) -> Option<Candle> {
+        let (open, close, high, low) = match self.op {
+            SpreadOp::Ratio => {
+                if quote.open == 0.0 || quote.close == 0.0 || quote.low == 0.0 {
+                    return None;
+                }
+                // Wicks span the widest range the two legs allow
+                (
+                    base.open / quote.open,
+                    base.close / quote.close,
+                    base.high / quote.low,
+                    base.low / quote.high,
+                )
+            }
+            SpreadOp::Diff => (
+                base.open - quote.open,
+                base.close - quote.close,
+                base.high - quote.low,
+                base.low - quote.high,
+            ),
+        };
+
+        Some(Candle {
+            start: base.start,
+            open,
+            high: high.max(open).max(close),
+            low: low.min(open).min(close),
+            close,
+            volume: 0.0,
+            turnover: 0.0,
+        })
+    }
+}
@@ -0,0 +1,34 @@
+use super::websocket::TradeData;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Trade {
+    pub timestamp: i64,
+    pub price: f64,
+    pub size: f64,
+    pub side: Side,
+    pub block_trade: bool,
+}
+
+impl Trade {
+    pub fn from_trade_data(data: &TradeData) -> Option<Self> {
+        let side = match data.side.as_str() {
+            "Buy" => Side::Buy,
+            "Sell" => Side::Sell,
+            _ => return None,
+        };
+        Some(Self {
+            timestamp: data.timestamp,
+            price: data.price.parse().ok()?,
+            size: data.size.parse().ok()?,
+            side,
+            block_trade: data.block_trade,
+        })
+    }
+}
@@ -0,0 +1,45 @@
+use super::private::WalletData;
+
+#[derive(Debug, Clone)]
+pub struct CoinBalance {
+    pub coin: String,
+    pub wallet_balance: f64,
+    pub equity: f64,
+    pub unrealised_pnl: f64,
+    pub cum_realised_pnl: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct WalletBalance {
+    pub account_type: String,
+    pub total_equity: f64,
+    /// Margin still free for new orders, in USD.
+    pub available_balance: f64,
+    pub initial_margin: f64,
+    pub coins: Vec<CoinBalance>,
+}
+
+impl WalletBalance {
+    /// Bybit sends empty strings for fields that don't apply to the account
+    /// type, so those read as zero.
+    pub fn from_wallet_data(data: &WalletData) -> Self {
+        let number = |s: &str| s.parse().unwrap_or_default();
+        Self {
+            account_type: data.account_type.clone(),
+            total_equity: number(&data.total_equity),
+            available_balance: number(&data.total_available_balance),
+            initial_margin: number(&data.total_initial_margin),
+            coins: data
+                .coin
+                .iter()
+                .map(|coin| CoinBalance {
+                    coin: coin.coin.clone(),
+                    wallet_balance: number(&coin.wallet_balance),
+                    equity: number(&coin.equity),
+                    unrealised_pnl: number(&coin.unrealised_pnl),
+                    cum_realised_pnl: number(&coin.cum_realised_pnl),
+                })
+                .collect(),
+        }
+    }
+}
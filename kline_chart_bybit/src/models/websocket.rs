@@ -29,3 +29,65 @@ pub struct KlineResponse {
     #[serde(rename = "type")]
     pub response_type: String,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct TradeData {
+    #[serde(rename = "T")]
+    pub timestamp: i64,
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "S")]
+    pub side: String,
+    #[serde(rename = "v")]
+    pub size: String,
+    #[serde(rename = "p")]
+    pub price: String,
+    #[serde(rename = "BT", default)]
+    pub block_trade: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TradeResponse {
+    pub topic: String,
+    pub data: Vec<TradeData>,
+    pub ts: i64,
+    #[serde(rename = "type")]
+    pub response_type: String,
+}
+
+/// Minimal view of any inbound frame, used to route it by topic.
+#[derive(Debug, Deserialize)]
+pub struct TopicEnvelope {
+    pub topic: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OrderbookData {
+    #[serde(rename = "s")]
+    pub symbol: String,
+    #[serde(rename = "b")]
+    pub bids: Vec<[String; 2]>,
+    #[serde(rename = "a")]
+    pub asks: Vec<[String; 2]>,
+    #[serde(rename = "u")]
+    pub update_id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OrderbookResponse {
+    pub topic: String,
+    pub data: OrderbookData,
+    pub ts: i64,
+    #[serde(rename = "type")]
+    pub response_type: String,
+}
+
+/// Reply to an `op` request such as `subscribe` or `auth`.
+#[derive(Debug, Deserialize)]
+pub struct OpResponse {
+    pub op: String,
+    #[serde(default)]
+    pub success: bool,
+    #[serde(default)]
+    pub ret_msg: String,
+}
@@ -0,0 +1,435 @@
+use crate::{
+    models::{OrderData, Side},
+    rest::RestClient,
+    rest::RestResult,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum OrderType {
+    Market,
+    Limit,
+}
+
+/// Which price stream a trigger is evaluated against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TriggerBy {
+    LastPrice,
+    MarkPrice,
+    IndexPrice,
+}
+
+/// Body of `POST /v5/order/create` for linear contracts.
+///
+/// A plain order has no trigger. Setting `trigger_price` makes it conditional:
+/// it rests on the exchange until price moves through the trigger in
+/// `trigger_direction` (1 = rises to it, 2 = falls to it), then enters as a
+/// market or limit order. `take_profit`/`stop_loss` attach TP/SL to the
+/// resulting position, together forming a bracket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderRequest {
+    pub category: &'static str,
+    pub symbol: String,
+    pub side: Side,
+    pub order_type: OrderType,
+    pub qty: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_price: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_direction: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_by: Option<TriggerBy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub take_profit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_loss: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tp_trigger_by: Option<TriggerBy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sl_trigger_by: Option<TriggerBy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tpsl_mode: Option<&'static str>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub reduce_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OrderResponse {
+    pub order_id: String,
+}
+
+/// Exchange-friendly decimal text, e.g. `0.1` or `3000`.
+pub fn decimal(value: f64) -> String {
+    format!("{}", value)
+}
+
+impl OrderRequest {
+    fn new(symbol: &str, side: Side, order_type: OrderType, qty: f64) -> Self {
+        Self {
+            category: "linear",
+            symbol: symbol.to_string(),
+            side,
+            order_type,
+            qty: decimal(qty),
+            price: None,
+            trigger_price: None,
+            trigger_direction: None,
+            trigger_by: None,
+            take_profit: None,
+            stop_loss: None,
+            tp_trigger_by: None,
+            sl_trigger_by: None,
+            tpsl_mode: None,
+            reduce_only: false,
+        }
+    }
+
+    pub fn market(symbol: &str, side: Side, qty: f64) -> Self {
+        Self::new(symbol, side, OrderType::Market, qty)
+    }
+
+    pub fn limit(symbol: &str, side: Side, qty: f64, price: f64) -> Self {
+        let mut order = Self::new(symbol, side, OrderType::Limit, qty);
+        order.price = Some(decimal(price));
+        order
+    }
+
+    /// Market order sent once price reaches `trigger`. `last_price` decides
+    /// whether that means rising or falling to it.
+    pub fn stop_market(symbol: &str, side: Side, qty: f64, trigger: f64, last_price: f64) -> Self {
+        Self::market(symbol, side, qty).with_trigger(trigger, last_price)
+    }
+
+    /// Limit order at `price` placed once price reaches `trigger`.
+    pub fn stop_limit(
+        symbol: &str,
+        side: Side,
+        qty: f64,
+        trigger: f64,
+        price: f64,
+        last_price: f64,
+    ) -> Self {
+        Self::limit(symbol, side, qty, price).with_trigger(trigger, last_price)
+    }
+
+    fn with_trigger(mut self, trigger: f64, last_price: f64) -> Self {
+        self.trigger_price = Some(decimal(trigger));
+        self.trigger_by = Some(TriggerBy::LastPrice);
+        self.with_trigger_direction_at(last_price)
+    }
+
+    /// Points the trigger direction at `last_price` again, for an order sent
+    /// a while after it was typed: rising to a trigger above it, falling to
+    /// one below. Orders without a trigger are unchanged.
+    pub fn with_trigger_direction_at(mut self, last_price: f64) -> Self {
+        if let Some(trigger) = self
+            .trigger_price
+            .as_deref()
+            .and_then(|t| t.parse::<f64>().ok())
+        {
+            self.trigger_direction = Some(if trigger >= last_price { 1 } else { 2 });
+        }
+        self
+    }
+
+    pub fn with_take_profit(mut self, price: f64) -> Self {
+        self.take_profit = Some(decimal(price));
+        self.tp_trigger_by = Some(self.trigger_by.unwrap_or(TriggerBy::LastPrice));
+        self.tpsl_mode = Some("Full");
+        self
+    }
+
+    pub fn with_stop_loss(mut self, price: f64) -> Self {
+        self.stop_loss = Some(decimal(price));
+        self.sl_trigger_by = Some(self.trigger_by.unwrap_or(TriggerBy::LastPrice));
+        self.tpsl_mode = Some("Full");
+        self
+    }
+
+    /// Evaluates the entry trigger and any TP/SL against `trigger_by`.
+    pub fn with_trigger_by(mut self, trigger_by: TriggerBy) -> Self {
+        if self.trigger_price.is_some() {
+            self.trigger_by = Some(trigger_by);
+        }
+        if self.take_profit.is_some() {
+            self.tp_trigger_by = Some(trigger_by);
+        }
+        if self.stop_loss.is_some() {
+            self.sl_trigger_by = Some(trigger_by);
+        }
+        self
+    }
+
+    pub fn reduce_only(mut self) -> Self {
+        self.reduce_only = true;
+        self
+    }
+
+    /// One-line human summary, used for confirmations and status messages.
+    pub fn describe(&self) -> String {
+        let side = match self.side {
+            Side::Buy => "BUY",
+            Side::Sell => "SELL",
+        };
+        let mut text = format!("{} {} {}", side, self.qty, self.symbol);
+        match (&self.trigger_price, &self.price) {
+            (Some(trigger), Some(price)) => {
+                text.push_str(&format!(" stop-limit {} @ {}", trigger, price))
+            }
+            (Some(trigger), None) => text.push_str(&format!(" stop-market {}", trigger)),
+            (None, Some(price)) => text.push_str(&format!(" limit @ {}", price)),
+            (None, None) => text.push_str(" market"),
+        }
+        if let Some(tp) = &self.take_profit {
+            text.push_str(&format!(" TP {}", tp));
+        }
+        if let Some(sl) = &self.stop_loss {
+            text.push_str(&format!(" SL {}", sl));
+        }
+        if self.reduce_only {
+            text.push_str(" reduce-only");
+        }
+        text
+    }
+}
+
+fn parse_number(token: Option<&str>, what: &str) -> Result<f64, String> {
+    token
+        .ok_or_else(|| format!("missing {}", what))?
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite() && *v > 0.0)
+        .ok_or_else(|| format!("invalid {}", what))
+}
+
+/// Parses an order typed on the command line:
+///
+/// `buy|sell <qty> [market | limit <price> | stop <trigger> | stoplimit <trigger> <price>]
+///  [tp <price>] [sl <price>] [by last|mark|index] [reduce]`
+///
+/// Stop orders trigger on a cross of `last_price`, so they need one.
+pub fn parse_order_command(
+    input: &str,
+    symbol: &str,
+    last_price: Option<f64>,
+) -> Result<OrderRequest, String> {
+    let lowered = input.to_lowercase();
+    let mut tokens = lowered.split_whitespace();
+
+    let side = match tokens.next() {
+        Some("buy") | Some("b") => Side::Buy,
+        Some("sell") | Some("s") => Side::Sell,
+        _ => return Err(String::from("order must start with buy or sell")),
+    };
+    let qty = parse_number(tokens.next(), "quantity")?;
+
+    let mut order = OrderRequest::market(symbol, side, qty);
+    let (mut take_profit, mut stop_loss, mut trigger_by, mut reduce_only) =
+        (None, None, None, false);
+    while let Some(token) = tokens.next() {
+        match token {
+            "market" | "mkt" => order = OrderRequest::market(symbol, side, qty),
+            "limit" => {
+                let price = parse_number(tokens.next(), "limit price")?;
+                order = OrderRequest::limit(symbol, side, qty, price);
+            }
+            "stop" => {
+                let trigger = parse_number(tokens.next(), "trigger price")?;
+                let last_price = last_price.ok_or("no price yet")?;
+                order = OrderRequest::stop_market(symbol, side, qty, trigger, last_price);
+            }
+            "stoplimit" => {
+                let trigger = parse_number(tokens.next(), "trigger price")?;
+                let price = parse_number(tokens.next(), "limit price")?;
+                let last_price = last_price.ok_or("no price yet")?;
+                order = OrderRequest::stop_limit(symbol, side, qty, trigger, price, last_price);
+            }
+            "tp" => take_profit = Some(parse_number(tokens.next(), "take-profit price")?),
+            "sl" => stop_loss = Some(parse_number(tokens.next(), "stop-loss price")?),
+            "by" => {
+                trigger_by = Some(match tokens.next() {
+                    Some("last") => TriggerBy::LastPrice,
+                    Some("mark") => TriggerBy::MarkPrice,
+                    Some("index") => TriggerBy::IndexPrice,
+                    _ => return Err(String::from("trigger source must be last, mark or index")),
+                });
+            }
+            "reduce" => reduce_only = true,
+            other => return Err(format!("unknown word '{}'", other)),
+        }
+    }
+
+    if let Some(price) = take_profit {
+        order = order.with_take_profit(price);
+    }
+    if let Some(price) = stop_loss {
+        order = order.with_stop_loss(price);
+    }
+    if let Some(trigger_by) = trigger_by {
+        order = order.with_trigger_by(trigger_by);
+    }
+    if reduce_only {
+        order = order.reduce_only();
+    }
+    Ok(order)
+}
+
+/// Parses an alert with an attached order: `<price> <order command>`, e.g.
+/// `3200 sell 0.5`. Returns the alert price and the order.
+pub fn parse_alert_order(
+    input: &str,
+    symbol: &str,
+    last_price: Option<f64>,
+) -> Result<(f64, OrderRequest), String> {
+    let input = input.trim_start();
+    let (price, command) = input
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| String::from("expected an alert price and an order"))?;
+    let price = parse_number(Some(price), "alert price")?;
+    Ok((price, parse_order_command(command, symbol, last_price)?))
+}
+
+/// Sends the order and returns the exchange order id.
+pub async fn place_order(client: &RestClient, order: &OrderRequest) -> RestResult<String> {
+    let response: OrderResponse = client.post("/v5/order/create", order).await?;
+    Ok(response.order_id)
+}
+
+#[derive(Debug, Serialize)]
+struct CancelAllRequest<'a> {
+    category: &'static str,
+    symbol: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct CancelAllResponse {
+    list: Vec<OrderResponse>,
+}
+
+/// Cancels every open order in `symbol` and returns how many were cancelled.
+pub async fn cancel_all_orders(client: &RestClient, symbol: &str) -> RestResult<usize> {
+    let request = CancelAllRequest {
+        category: "linear",
+        symbol,
+    };
+    let response: CancelAllResponse = client.post("/v5/order/cancel-all", &request).await?;
+    Ok(response.list.len())
+}
+
+/// Body of `POST /v5/order/amend`; fields left `None` keep their value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendRequest {
+    pub category: &'static str,
+    pub symbol: String,
+    pub order_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qty: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+}
+
+impl AmendRequest {
+    pub fn new(symbol: &str, order_id: &str, qty: Option<f64>, price: Option<f64>) -> Self {
+        Self {
+            category: "linear",
+            symbol: symbol.to_string(),
+            order_id: order_id.to_string(),
+            qty: qty.map(decimal),
+            price: price.map(decimal),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let mut text = format!("amend {}", self.order_id);
+        if let Some(qty) = &self.qty {
+            text.push_str(&format!(" qty {}", qty));
+        }
+        if let Some(price) = &self.price {
+            text.push_str(&format!(" price {}", price));
+        }
+        text
+    }
+}
+
+/// Parses the amend prompt: `[qty] [@price]`, e.g. `0.5`, `@3200` or `0.5 @3200`.
+pub fn parse_amend(input: &str) -> Result<(Option<f64>, Option<f64>), String> {
+    let (mut qty, mut price) = (None, None);
+    for token in input.split_whitespace() {
+        match token.strip_prefix('@') {
+            Some(value) => price = Some(parse_number(Some(value), "price")?),
+            None => qty = Some(parse_number(Some(token), "quantity")?),
+        }
+    }
+    if qty.is_none() && price.is_none() {
+        return Err(String::from("nothing to amend"));
+    }
+    Ok((qty, price))
+}
+
+/// Changes the quantity and/or price of a resting order.
+pub async fn amend_order(client: &RestClient, request: &AmendRequest) -> RestResult<String> {
+    let response: OrderResponse = client.post("/v5/order/amend", request).await?;
+    Ok(response.order_id)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CancelRequest<'a> {
+    category: &'static str,
+    symbol: &'a str,
+    order_id: &'a str,
+}
+
+pub async fn cancel_order(client: &RestClient, symbol: &str, order_id: &str) -> RestResult<String> {
+    let request = CancelRequest {
+        category: "linear",
+        symbol,
+        order_id,
+    };
+    let response: OrderResponse = client.post("/v5/order/cancel", &request).await?;
+    Ok(response.order_id)
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenOrdersResponse {
+    list: Vec<OrderData>,
+}
+
+/// Orders still working in `symbol`, to fill the orders panel before the
+/// first push on the private `order` topic.
+pub async fn fetch_open_orders(client: &RestClient, symbol: &str) -> RestResult<Vec<OrderData>> {
+    let query = format!("category=linear&symbol={}&openOnly=0", symbol);
+    let response: OpenOrdersResponse = client.get("/v5/order/realtime", &query).await?;
+    Ok(response.list)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stop_orders_need_a_price() {
+        for command in ["buy 1 stop 3000", "sell 1 stoplimit 3000 2990"] {
+            assert_eq!(
+                parse_order_command(command, "ETHUSDT", None),
+                Err(String::from("no price yet"))
+            );
+        }
+        assert!(parse_order_command("buy 1 limit 2900", "ETHUSDT", None).is_ok());
+        assert!(parse_alert_order("3200 sell 1 stop 3100", "ETHUSDT", None).is_err());
+    }
+
+    #[test]
+    fn stop_direction_follows_the_last_price() {
+        let above = parse_order_command("buy 1 stop 3000", "ETHUSDT", Some(2900.0)).unwrap();
+        let below = parse_order_command("sell 1 stop 2800", "ETHUSDT", Some(2900.0)).unwrap();
+
+        assert_eq!(above.trigger_direction, Some(1));
+        assert_eq!(below.trigger_direction, Some(2));
+    }
+}
@@ -0,0 +1,530 @@
+use crate::{
+    fees::FeeModel,
+    models::{CoinBalance, Execution, MarginMode, OpenOrder, Position, Side, WalletBalance},
+    orders::{OrderRequest, OrderType},
+};
+use chrono::Utc;
+use std::collections::BTreeMap;
+
+/// Quote currency the paper account is kept in.
+const PAPER_COIN: &str = "USDT";
+
+/// Paper trading has no leverage settings, so every position is margined
+/// in full.
+const PAPER_LEVERAGE: f64 = 1.0;
+
+#[derive(Debug, Clone)]
+struct PaperOrder {
+    id: u64,
+    symbol: String,
+    side: Side,
+    order_type: OrderType,
+    qty: f64,
+    price: Option<f64>,
+    /// Still waiting for price to reach this trigger (1 = rise to, 2 = fall to).
+    trigger: Option<(f64, u8)>,
+    take_profit: Option<f64>,
+    stop_loss: Option<f64>,
+    reduce_only: bool,
+    /// Entry order this TP/SL leg belongs to; filling one leg cancels the other.
+    parent: Option<u64>,
+    /// A limit that has sat on the book through a price without filling; only
+    /// these fill at their limit as maker.
+    rested: bool,
+}
+
+impl PaperOrder {
+    fn from_request(id: u64, order: &OrderRequest) -> Option<Self> {
+        let number = |value: &Option<String>| value.as_deref().and_then(|v| v.parse().ok());
+        Some(Self {
+            id,
+            symbol: order.symbol.clone(),
+            side: order.side,
+            order_type: order.order_type,
+            qty: order.qty.parse().ok()?,
+            price: number(&order.price),
+            trigger: number(&order.trigger_price)
+                .map(|trigger| (trigger, order.trigger_direction.unwrap_or(1))),
+            take_profit: number(&order.take_profit),
+            stop_loss: number(&order.stop_loss),
+            reduce_only: order.reduce_only,
+            parent: None,
+            rested: false,
+        })
+    }
+
+    /// Reduce-only stop closing `parent`'s position, for a bracket leg.
+    fn exit(id: u64, parent: &PaperOrder, qty: f64, trigger: f64, direction: u8) -> Self {
+        let side = match parent.side {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        };
+        Self {
+            id,
+            symbol: parent.symbol.clone(),
+            side,
+            order_type: OrderType::Market,
+            qty,
+            price: None,
+            trigger: Some((trigger, direction)),
+            take_profit: None,
+            stop_loss: None,
+            reduce_only: true,
+            parent: Some(parent.id),
+            rested: false,
+        }
+    }
+
+    /// Fill price at `price`, or `None` if the order keeps resting.
+    fn fill_price(&mut self, price: f64) -> Option<f64> {
+        if let Some((trigger, direction)) = self.trigger {
+            let reached = match direction {
+                1 => price >= trigger,
+                _ => price <= trigger,
+            };
+            if !reached {
+                return None;
+            }
+            self.trigger = None;
+        }
+        match (self.order_type, self.price) {
+            (OrderType::Limit, Some(limit)) => {
+                let crossed = match self.side {
+                    Side::Buy => price <= limit,
+                    Side::Sell => price >= limit,
+                };
+                if !crossed {
+                    self.rested = true;
+                    None
+                } else if self.rested {
+                    Some(limit)
+                } else {
+                    // Marketable when placed: takes the better of the two
+                    Some(match self.side {
+                        Side::Buy => price.min(limit),
+                        Side::Sell => price.max(limit),
+                    })
+                }
+            }
+            _ => Some(price),
+        }
+    }
+}
+
+/// Net position in one symbol; `qty` is negative when short.
+#[derive(Debug, Clone, Copy, Default)]
+struct PaperPosition {
+    qty: f64,
+    entry_price: f64,
+}
+
+/// Simulated exchange that fills orders against the live trade stream, so the
+/// order UI can be practiced without touching the account. Market orders fill
+/// at the last trade, limit orders once price trades through them (at once
+/// and as takers if it already has), and
+/// triggers and TP/SL behave like their exchange counterparts on last price.
+/// Fills pay fees and slippage from the [`FeeModel`].
+pub struct PaperBroker {
+    /// Cash balance including realized PnL.
+    balance: f64,
+    realized_pnl: f64,
+    positions: BTreeMap<String, PaperPosition>,
+    marks: BTreeMap<String, f64>,
+    orders: Vec<PaperOrder>,
+    next_id: u64,
+    fees: FeeModel,
+    tick_size: f64,
+    /// Volume of the current candle, for volume-based slippage.
+    volume: f64,
+}
+
+impl PaperBroker {
+    pub fn new(balance: f64, fees: FeeModel, tick_size: f64) -> Self {
+        Self {
+            balance,
+            realized_pnl: 0.0,
+            positions: BTreeMap::new(),
+            marks: BTreeMap::new(),
+            orders: Vec::new(),
+            next_id: 1,
+            fees,
+            tick_size,
+            volume: 0.0,
+        }
+    }
+
+    /// Accepts an order and fills it right away if `last_price` allows.
+    /// Returns the paper order id and any fills.
+    pub fn submit(&mut self, order: &OrderRequest, last_price: f64) -> (String, Vec<Execution>) {
+        let id = self.next_id;
+        self.next_id += 1;
+        let Some(order) = PaperOrder::from_request(id, order) else {
+            return (id.to_string(), Vec::new());
+        };
+        let symbol = order.symbol.clone();
+        self.orders.push(order);
+        (id.to_string(), self.on_price(&symbol, last_price))
+    }
+
+    pub fn set_volume(&mut self, volume: f64) {
+        self.volume = volume;
+    }
+
+    /// Cancels the resting orders in `symbol` and returns how many there were.
+    pub fn cancel_all(&mut self, symbol: &str) -> usize {
+        let before = self.orders.len();
+        self.orders.retain(|order| order.symbol != symbol);
+        before - self.orders.len()
+    }
+
+    /// Number of orders still resting.
+    pub fn open_orders(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// The resting orders in `symbol`, shaped like the exchange's.
+    pub fn orders(&self, symbol: &str) -> Vec<OpenOrder> {
+        self.orders
+            .iter()
+            .filter(|order| order.symbol == symbol)
+            .map(|order| OpenOrder {
+                order_id: order.id.to_string(),
+                symbol: order.symbol.clone(),
+                side: order.side,
+                order_type: format!("{:?}", order.order_type),
+                price: order.price,
+                qty: order.qty,
+                leaves_qty: order.qty,
+                trigger_price: order.trigger.map(|(trigger, _)| trigger),
+                reduce_only: order.reduce_only,
+                created_time: 0,
+            })
+            .collect()
+    }
+
+    /// Changes a resting order; the new price is a limit price, or the
+    /// trigger of a stop-market order. Returns whether the order exists.
+    pub fn amend(&mut self, order_id: &str, qty: Option<f64>, price: Option<f64>) -> bool {
+        let Some(order) = self
+            .orders
+            .iter_mut()
+            .find(|o| o.id.to_string() == order_id)
+        else {
+            return false;
+        };
+        if let Some(qty) = qty {
+            order.qty = qty;
+        }
+        match (price, order.order_type, &mut order.trigger) {
+            (Some(price), OrderType::Limit, _) => order.price = Some(price),
+            (Some(price), _, Some(trigger)) => trigger.0 = price,
+            _ => {}
+        }
+        true
+    }
+
+    /// Cancels one resting order. Returns whether it existed.
+    pub fn cancel(&mut self, order_id: &str) -> bool {
+        let before = self.orders.len();
+        self.orders.retain(|order| order.id.to_string() != order_id);
+        before != self.orders.len()
+    }
+
+    pub fn realized_pnl(&self) -> f64 {
+        self.realized_pnl
+    }
+
+    /// Feeds a trade price for `symbol` and returns the fills it caused.
+    pub fn on_price(&mut self, symbol: &str, price: f64) -> Vec<Execution> {
+        self.marks.insert(symbol.to_string(), price);
+        let mut fills = Vec::new();
+        while let Some((index, fill_price)) = self.next_fill(symbol, price) {
+            let order = self.orders.remove(index);
+            if let Some(parent) = order.parent {
+                self.orders.retain(|o| o.parent != Some(parent));
+            }
+            if let Some(fill) = self.fill(&order, fill_price) {
+                fills.push(fill);
+            }
+        }
+        fills
+    }
+
+    /// First resting order in `symbol` that `price` fills. Bracket legs added
+    /// by a fill are appended, so they get checked against the same price.
+    fn next_fill(&mut self, symbol: &str, price: f64) -> Option<(usize, f64)> {
+        self.orders
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, order)| order.symbol == symbol)
+            .find_map(|(index, order)| order.fill_price(price).map(|p| (index, p)))
+    }
+
+    fn fill(&mut self, order: &PaperOrder, price: f64) -> Option<Execution> {
+        let position = self.positions.entry(order.symbol.clone()).or_default();
+        let signed = match order.side {
+            Side::Buy => order.qty,
+            Side::Sell => -order.qty,
+        };
+        let mut qty = signed;
+        if order.reduce_only {
+            // Never flips or opens: clamp to what is left of the position
+            if position.qty == 0.0 || position.qty.signum() == signed.signum() {
+                return None;
+            }
+            qty = signed.signum() * signed.abs().min(position.qty.abs());
+        }
+
+        // Only a limit that rested on the book pays maker; the rest took
+        // liquidity and slip, though a limit never past its price
+        let maker = order.rested;
+        let price = if maker {
+            price
+        } else {
+            let slipped =
+                self.fees
+                    .taker_price(order.side, price, qty.abs(), self.tick_size, self.volume);
+            match (order.order_type, order.price, order.side) {
+                (OrderType::Limit, Some(limit), Side::Buy) => slipped.min(limit),
+                (OrderType::Limit, Some(limit), Side::Sell) => slipped.max(limit),
+                _ => slipped,
+            }
+        };
+        let fee = self.fees.fee(price * qty, maker);
+        self.balance -= fee;
+        self.realized_pnl -= fee;
+
+        if position.qty != 0.0 && position.qty.signum() != qty.signum() {
+            let closed = qty.abs().min(position.qty.abs());
+            let pnl = (price - position.entry_price) * closed * position.qty.signum();
+            self.realized_pnl += pnl;
+            self.balance += pnl;
+        }
+        let new_qty = position.qty + qty;
+        if new_qty == 0.0 || new_qty.signum() != position.qty.signum() {
+            // Flat, or flipped: what remains was opened at this price
+            position.entry_price = price;
+        } else if new_qty.abs() > position.qty.abs() {
+            position.entry_price = (position.entry_price * position.qty + price * qty) / new_qty;
+        }
+        position.qty = new_qty;
+        if position.qty == 0.0 {
+            self.positions.remove(&order.symbol);
+        }
+
+        if !order.reduce_only {
+            self.attach_bracket(order, qty.abs(), price);
+        }
+
+        Some(Execution {
+            exec_id: format!("paper-{}-{}", order.id, Utc::now().timestamp_millis()),
+            order_id: order.id.to_string(),
+            symbol: order.symbol.clone(),
+            timestamp: Utc::now().timestamp_millis(),
+            side: order.side,
+            price,
+            qty: qty.abs(),
+            fee,
+        })
+    }
+
+    /// Places the TP/SL of a filled entry as reduce-only exit orders.
+    fn attach_bracket(&mut self, order: &PaperOrder, qty: f64, entry: f64) {
+        for target in [order.take_profit, order.stop_loss].into_iter().flatten() {
+            let direction = if target >= entry { 1 } else { 2 };
+            let id = self.next_id;
+            self.next_id += 1;
+            self.orders
+                .push(PaperOrder::exit(id, order, qty, target, direction));
+        }
+    }
+
+    /// Open positions marked at the latest trade prices.
+    pub fn positions(&self) -> Vec<Position> {
+        self.positions
+            .iter()
+            .map(|(symbol, position)| {
+                let side = if position.qty > 0.0 {
+                    Side::Buy
+                } else {
+                    Side::Sell
+                };
+                let mut marked = Position {
+                    symbol: symbol.clone(),
+                    side,
+                    size: position.qty.abs(),
+                    entry_price: position.entry_price,
+                    mark_price: position.entry_price,
+                    unrealised_pnl: 0.0,
+                    liq_price: None,
+                    leverage: None,
+                    margin_mode: MarginMode::Cross,
+                };
+                if let Some(mark) = self.marks.get(symbol) {
+                    marked.mark_at(*mark);
+                }
+                marked
+            })
+            .collect()
+    }
+
+    pub fn wallet(&self) -> WalletBalance {
+        let unrealised_pnl: f64 = self.positions().iter().map(|p| p.unrealised_pnl).sum();
+        let equity = self.balance + unrealised_pnl;
+        let margin: f64 = self
+            .positions
+            .values()
+            .map(|p| p.qty.abs() * p.entry_price / PAPER_LEVERAGE)
+            .sum();
+        WalletBalance {
+            account_type: String::from("PAPER"),
+            total_equity: equity,
+            available_balance: (self.balance.min(equity) - margin).max(0.0),
+            initial_margin: margin,
+            coins: vec![CoinBalance {
+                coin: PAPER_COIN.to_string(),
+                wallet_balance: self.balance,
+                equity,
+                unrealised_pnl,
+                cum_realised_pnl: self.realized_pnl,
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYMBOL: &str = "BTCUSDT";
+
+    fn broker() -> PaperBroker {
+        PaperBroker::new(10_000.0, FeeModel::zero(), 0.5)
+    }
+
+    fn position(broker: &PaperBroker) -> Option<(Side, f64, f64)> {
+        broker
+            .positions()
+            .first()
+            .map(|p| (p.side, p.size, p.entry_price))
+    }
+
+    #[test]
+    fn limit_fills_at_its_price_once_crossed() {
+        let mut broker = broker();
+        let (_, fills) = broker.submit(&OrderRequest::limit(SYMBOL, Side::Buy, 1.0, 100.0), 105.0);
+        assert!(fills.is_empty());
+
+        assert!(broker.on_price(SYMBOL, 101.0).is_empty());
+        let fills = broker.on_price(SYMBOL, 99.5);
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 100.0);
+        assert_eq!(broker.open_orders(), 0);
+        assert_eq!(position(&broker), Some((Side::Buy, 1.0, 100.0)));
+    }
+
+    #[test]
+    fn marketable_limit_takes_the_better_price_as_taker() {
+        let fees = FeeModel {
+            maker: 0.0002,
+            taker: 0.001,
+            slippage: crate::fees::Slippage::Ticks(0.0),
+        };
+        let mut broker = PaperBroker::new(10_000.0, fees, 0.5);
+
+        let (_, fills) = broker.submit(&OrderRequest::limit(SYMBOL, Side::Buy, 1.0, 100.0), 95.0);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 95.0);
+        assert_eq!(fills[0].fee, 95.0 * 0.001);
+
+        // A limit that waited for price fills at its limit as maker
+        broker.submit(&OrderRequest::limit(SYMBOL, Side::Sell, 1.0, 100.0), 98.0);
+        let fills = broker.on_price(SYMBOL, 101.0);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 100.0);
+        assert_eq!(fills[0].fee, 100.0 * 0.0002);
+    }
+
+    #[test]
+    fn triggered_stop_limit_takes_liquidity() {
+        let fees = FeeModel {
+            maker: 0.0002,
+            taker: 0.001,
+            slippage: crate::fees::Slippage::Ticks(2.0),
+        };
+        let mut broker = PaperBroker::new(10_000.0, fees, 0.5);
+        let order = OrderRequest::stop_limit(SYMBOL, Side::Buy, 1.0, 105.0, 106.0, 100.0);
+        assert!(broker.submit(&order, 100.0).1.is_empty());
+
+        let fills = broker.on_price(SYMBOL, 105.2);
+
+        // Marketable on trigger, so a taker: slippage would take it to 106.2,
+        // the limit caps it at 106
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].price, 106.0);
+        assert_eq!(fills[0].fee, 106.0 * 0.001);
+    }
+
+    #[test]
+    fn take_profit_cancels_stop_loss() {
+        let mut broker = broker();
+        let order = OrderRequest::market(SYMBOL, Side::Buy, 2.0)
+            .with_take_profit(110.0)
+            .with_stop_loss(90.0);
+        let (_, fills) = broker.submit(&order, 100.0);
+        assert_eq!(fills.len(), 1);
+        assert_eq!(broker.open_orders(), 2);
+
+        let fills = broker.on_price(SYMBOL, 111.0);
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].side, Side::Sell);
+        assert_eq!(broker.open_orders(), 0);
+        assert_eq!(position(&broker), None);
+        assert_eq!(broker.realized_pnl(), 22.0);
+        assert!(broker.on_price(SYMBOL, 89.0).is_empty());
+    }
+
+    #[test]
+    fn reduce_only_is_clamped_to_the_position() {
+        let mut broker = broker();
+        let close = OrderRequest::market(SYMBOL, Side::Sell, 3.0).reduce_only();
+        let (_, fills) = broker.submit(&close, 100.0);
+        assert!(fills.is_empty(), "nothing to reduce");
+
+        broker.submit(&OrderRequest::market(SYMBOL, Side::Buy, 1.0), 100.0);
+        let (_, fills) = broker.submit(&close, 105.0);
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].qty, 1.0);
+        assert_eq!(position(&broker), None);
+        assert_eq!(broker.realized_pnl(), 5.0);
+    }
+
+    #[test]
+    fn flip_reprices_the_remainder_at_the_fill() {
+        let mut broker = broker();
+        broker.submit(&OrderRequest::market(SYMBOL, Side::Buy, 1.0), 100.0);
+        broker.submit(&OrderRequest::market(SYMBOL, Side::Buy, 1.0), 110.0);
+        assert_eq!(position(&broker), Some((Side::Buy, 2.0, 105.0)));
+
+        broker.submit(&OrderRequest::market(SYMBOL, Side::Sell, 5.0), 120.0);
+
+        assert_eq!(position(&broker), Some((Side::Sell, 3.0, 120.0)));
+        assert_eq!(broker.realized_pnl(), 30.0);
+        assert_eq!(broker.wallet().coins[0].wallet_balance, 10_030.0);
+    }
+
+    #[test]
+    fn margin_of_open_positions_is_not_available() {
+        let mut broker = broker();
+        broker.submit(&OrderRequest::market(SYMBOL, Side::Buy, 2.0), 100.0);
+        let wallet = broker.wallet();
+        assert_eq!(wallet.initial_margin, 200.0);
+        assert_eq!(wallet.available_balance, 9_800.0);
+
+        broker.on_price(SYMBOL, 90.0);
+        assert_eq!(broker.wallet().available_balance, 9_780.0);
+    }
+}
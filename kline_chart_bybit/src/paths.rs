@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+
+/// Per-user directory for everything the app persists between sessions.
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("kline_chart_bybit")
+}
+
+/// Per-user directory for configuration files.
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("kline_chart_bybit")
+}
@@ -0,0 +1,56 @@
+use crate::{
+    history::{fetch_candles, interval_ms},
+    paths::data_dir,
+    rest::RestResult,
+    store::CandleStore,
+};
+use chrono::Utc;
+use std::{fs, io, path::PathBuf};
+
+/// Longest gap backfilled after a crash; older history is left to `export`.
+const MAX_BACKFILL_MS: i64 = 24 * 60 * 60 * 1000;
+
+/// File that exists while the chart runs, so a leftover one on startup
+/// means the previous session did not exit cleanly.
+pub struct RunMarker {
+    path: PathBuf,
+}
+
+impl RunMarker {
+    /// Creates the marker and reports whether the last session crashed.
+    pub fn acquire() -> io::Result<(Self, bool)> {
+        let path = data_dir().join("running");
+        let unclean = path.exists();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, std::process::id().to_string())?;
+        Ok((Self { path }, unclean))
+    }
+
+    /// Removes the marker on a clean exit.
+    pub fn release(self) -> io::Result<()> {
+        fs::remove_file(&self.path)
+    }
+}
+
+/// Fills the store from each series' last candle up to now over REST, so the
+/// chart resumes without a hole. Returns how many candles were added.
+pub async fn backfill(store: &CandleStore) -> RestResult<usize> {
+    let now = Utc::now().timestamp_millis();
+    let mut added = 0;
+    for (symbol, interval, last) in store.last_starts()? {
+        let Some(step) = interval_ms(&interval) else {
+            continue;
+        };
+        let start = (last + step).max(now - MAX_BACKFILL_MS);
+        if start + step > now {
+            continue;
+        }
+        for candle in fetch_candles(&symbol, &interval, start, now).await? {
+            store.insert(&symbol, &interval, &candle)?;
+            added += 1;
+        }
+    }
+    Ok(added)
+}
@@ -0,0 +1,160 @@
+use crate::models::Candle;
+use chrono::Utc;
+use futures_util::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, LineWriter, Write},
+    path::Path,
+    time::Duration,
+};
+use tokio::{task::yield_now, time::sleep};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+
+/// One line of a recording: a raw inbound frame and when it arrived.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// Receive time in milliseconds since the epoch.
+    pub received: i64,
+    /// "public" or "private".
+    pub stream: String,
+    pub frame: String,
+}
+
+/// Appends every inbound frame to a JSON-lines file, for `--replay`.
+pub struct Recorder {
+    file: LineWriter<File>,
+}
+
+impl Recorder {
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: LineWriter::new(file),
+        })
+    }
+
+    /// Writes one frame received now on `stream` ("public" or "private").
+    pub fn record(&mut self, stream: &str, frame: &str) -> io::Result<()> {
+        let line = RecordedFrame {
+            received: Utc::now().timestamp_millis(),
+            stream: stream.to_string(),
+            frame: frame.to_string(),
+        };
+        serde_json::to_writer(&mut self.file, &line)?;
+        self.file.write_all(b"\n")
+    }
+}
+
+/// A public frame to feed the app and its original time.
+#[derive(Debug, Clone)]
+pub struct ReplayEvent {
+    pub at: i64,
+    pub frame: String,
+}
+
+/// Reads a JSON-lines file of recorded frames (see `--record`), raw public
+/// frames or candles, in file order.
+///
+/// Candles are wrapped into confirmed kline frames of `symbol` at `interval`. Private
+/// frames are skipped: a replay reviews the market, not an account.
+pub fn load(path: &Path, symbol: &str, interval: &str) -> io::Result<Vec<ReplayEvent>> {
+    let mut events = Vec::new();
+    for (number, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let invalid = |e: serde_json::Error| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}", number + 1, e),
+            )
+        };
+        let value: Value = serde_json::from_str(&line).map_err(invalid)?;
+        if value.get("frame").is_some() {
+            let recorded: RecordedFrame = serde_json::from_value(value).map_err(invalid)?;
+            if recorded.stream == "public" {
+                events.push(ReplayEvent {
+                    at: recorded.received,
+                    frame: recorded.frame,
+                });
+            }
+        } else if value.get("topic").is_some() {
+            let at = value.get("ts").and_then(Value::as_i64).unwrap_or_default();
+            events.push(ReplayEvent { at, frame: line });
+        } else {
+            let candle: Candle = serde_json::from_value(value).map_err(invalid)?;
+            events.push(ReplayEvent {
+                at: candle.start,
+                frame: kline_frame(&candle, symbol, interval),
+            });
+        }
+    }
+    Ok(events)
+}
+
+/// A confirmed kline frame as the public stream would send it.
+fn kline_frame(candle: &Candle, symbol: &str, interval: &str) -> String {
+    json!({
+        "topic": format!("kline.{}.{}", interval, symbol),
+        "type": "snapshot",
+        "ts": candle.start,
+        "data": [{
+            "start": candle.start,
+            "end": candle.start,
+            "interval": interval,
+            "open": candle.open.to_string(),
+            "close": candle.close.to_string(),
+            "high": candle.high.to_string(),
+            "low": candle.low.to_string(),
+            "volume": candle.volume.to_string(),
+            "turnover": candle.turnover.to_string(),
+            "confirm": true,
+            "timestamp": candle.start,
+        }],
+    })
+    .to_string()
+}
+
+/// Frames fed at `max` speed before letting the UI tick run.
+const MAX_SPEED_BATCH: usize = 64;
+
+/// Parses a replay speed such as `10x`, `0.5` or `max` (no waiting).
+pub fn parse_speed(text: &str) -> Result<f64, String> {
+    if text == "max" {
+        return Ok(f64::INFINITY);
+    }
+    text.trim_end_matches('x')
+        .parse::<f64>()
+        .ok()
+        .filter(|speed| *speed > 0.0)
+        .ok_or_else(|| format!("invalid speed '{}', expected e.g. 10x or max", text))
+}
+
+/// Emits the events as websocket messages, keeping their original spacing
+/// divided by `speed`, and ends after the last one.
+pub fn replay_stream(
+    events: Vec<ReplayEvent>,
+    speed: f64,
+) -> impl Stream<Item = Result<Message, WsError>> {
+    let mut previous: Option<i64> = None;
+    stream::iter(events)
+        .enumerate()
+        .then(move |(index, event)| {
+            let gap = previous.map_or(0, |at| (event.at - at).max(0));
+            previous = Some(event.at);
+            async move {
+                if speed.is_finite() {
+                    if gap > 0 {
+                        sleep(Duration::from_secs_f64(gap as f64 / 1000.0 / speed)).await;
+                    }
+                } else if index > 0 && index % MAX_SPEED_BATCH == 0 {
+                    // Every frame is ready at once; give input and drawing a turn
+                    yield_now().await;
+                }
+                Ok(Message::Text(event.frame))
+            }
+        })
+}
@@ -0,0 +1,77 @@
+use crate::{auth::Signer, config::endpoints, constants::USER_AGENT};
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub type RestResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Envelope of every v5 REST response.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiResponse<T> {
+    ret_code: i64,
+    ret_msg: String,
+    result: Option<T>,
+}
+
+/// Signed client for Bybit's private v5 REST endpoints.
+#[derive(Clone)]
+pub struct RestClient {
+    http: reqwest::Client,
+    signer: Signer,
+    base_url: &'static str,
+}
+
+impl RestClient {
+    pub fn new(signer: Signer) -> Self {
+        let base_url = if signer.testnet {
+            &endpoints().testnet_rest
+        } else {
+            &endpoints().rest
+        };
+        Self {
+            http: reqwest::Client::new(),
+            signer,
+            base_url,
+        }
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, path: &str, query: &str) -> RestResult<T> {
+        let timestamp = Utc::now().timestamp_millis();
+        let mut request = self
+            .http
+            .get(format!("{}{}?{}", self.base_url, path, query))
+            .header("User-Agent", USER_AGENT);
+        for (name, value) in self.signer.rest_headers(timestamp, query) {
+            request = request.header(name, value);
+        }
+        Self::unwrap_response(request.send().await?.json().await?)
+    }
+
+    pub async fn post<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> RestResult<T> {
+        let timestamp = Utc::now().timestamp_millis();
+        // The signature covers the exact body bytes, so serialize once and send those
+        let body = serde_json::to_string(body)?;
+        let mut request = self
+            .http
+            .post(format!("{}{}", self.base_url, path))
+            .header("User-Agent", USER_AGENT)
+            .header("Content-Type", "application/json");
+        for (name, value) in self.signer.rest_headers(timestamp, &body) {
+            request = request.header(name, value);
+        }
+        Self::unwrap_response(request.body(body).send().await?.json().await?)
+    }
+
+    fn unwrap_response<T>(response: ApiResponse<T>) -> RestResult<T> {
+        if response.ret_code != 0 {
+            return Err(format!("{} (code {})", response.ret_msg, response.ret_code).into());
+        }
+        response
+            .result
+            .ok_or_else(|| "response without result".into())
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{credentials::prompt_keys, paths::config_dir};
+use age::secrecy::SecretString;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{Read, Write},
+    iter,
+    path::PathBuf,
+};
+
+type SecretsResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredKeys {
+    pub key: String,
+    pub secret: String,
+}
+
+/// Passphrase-encrypted credentials for machines without an OS keyring.
+/// The file is an age container (scrypt passphrase recipient) around TOML
+/// with one `[profiles.<name>]` table per API key.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SecretsFile {
+    #[serde(default)]
+    pub profiles: BTreeMap<String, StoredKeys>,
+}
+
+impl SecretsFile {
+    pub fn path() -> PathBuf {
+        config_dir().join("credentials.age")
+    }
+
+    pub fn exists() -> bool {
+        Self::path().exists()
+    }
+
+    pub fn unlock(passphrase: &str) -> SecretsResult<Self> {
+        let encrypted = fs::read(Self::path())?;
+        let decryptor = age::Decryptor::new(&encrypted[..])?;
+        let identity = age::scrypt::Identity::new(SecretString::from(passphrase.to_string()));
+        let mut reader = decryptor.decrypt(iter::once(&identity as &dyn age::Identity))?;
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        Ok(toml::from_str(&text)?)
+    }
+
+    pub fn save(&self, passphrase: &str) -> SecretsResult<()> {
+        let text = toml::to_string(self)?;
+        let encryptor =
+            age::Encryptor::with_user_passphrase(SecretString::from(passphrase.to_string()));
+        let mut encrypted = Vec::new();
+        let mut writer = encryptor.wrap_output(&mut encrypted)?;
+        writer.write_all(text.as_bytes())?;
+        writer.finish()?;
+
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, encrypted)?;
+        Ok(())
+    }
+}
+
+/// Prompts for the passphrase and decrypts the credentials file, if there is one.
+pub fn unlock_interactive() -> SecretsResult<Option<SecretsFile>> {
+    if !SecretsFile::exists() {
+        return Ok(None);
+    }
+    let passphrase = rpassword::prompt_password("Passphrase for credentials file: ")?;
+    SecretsFile::unlock(&passphrase)
+        .map(Some)
+        .map_err(|e| format!("cannot unlock {}: {}", SecretsFile::path().display(), e).into())
+}
+
+/// One-time `--encrypt-keys` setup: adds a profile's key and secret to the
+/// encrypted credentials file, creating it on first use.
+pub fn encrypt_keys_interactive(profile: &str) -> SecretsResult<()> {
+    let (key, secret) = prompt_keys(profile)?;
+
+    let (mut file, passphrase) = if SecretsFile::exists() {
+        let passphrase = rpassword::prompt_password("Passphrase for credentials file: ")?;
+        (SecretsFile::unlock(&passphrase)?, passphrase)
+    } else {
+        let passphrase = rpassword::prompt_password("New passphrase: ")?;
+        if passphrase.is_empty() {
+            return Err("passphrase must not be empty".into());
+        }
+        if rpassword::prompt_password("Repeat passphrase: ")? != passphrase {
+            return Err("passphrases do not match".into());
+        }
+        (SecretsFile::default(), passphrase)
+    };
+
+    file.profiles
+        .insert(profile.to_string(), StoredKeys { key, secret });
+    file.save(&passphrase)?;
+    println!(
+        "Stored credentials for '{profile}' in {}.",
+        SecretsFile::path().display()
+    );
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::paths::data_dir;
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+/// Which panels are open.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Panels {
+    pub trades: bool,
+    pub ladder: bool,
+    pub positions: bool,
+    pub wallet: bool,
+    pub executions: bool,
+    pub orders: bool,
+    pub equity: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Indicators {
+    pub ma50: bool,
+}
+
+impl Default for Indicators {
+    fn default() -> Self {
+        Self { ma50: true }
+    }
+}
+
+/// UI state saved on exit and restored on the next launch. Drawings and the
+/// fib toggle are saved on their own as they change, see
+/// [`crate::drawings::Drawings`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    pub symbol: String,
+    pub interval: String,
+    /// Candles shown on the chart.
+    pub zoom: Option<usize>,
+    pub indicators: Indicators,
+    pub panels: Panels,
+}
+
+impl SessionState {
+    pub fn default_path() -> PathBuf {
+        data_dir().join("session.json")
+    }
+
+    /// The saved state, or `None` on the first launch or if it is unreadable.
+    pub fn load() -> Option<Self> {
+        let text = fs::read_to_string(Self::default_path()).ok()?;
+        serde_json::from_str(&text).ok()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::default_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
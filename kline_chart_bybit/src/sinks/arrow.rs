@@ -0,0 +1,77 @@
+use super::SinkEvent;
+use crate::export::{candles_batch, ExportResult};
+use arrow_ipc::writer::StreamWriter;
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::PathBuf,
+    thread,
+};
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+/// Where `--arrow-stream` writes to.
+#[derive(Debug, Clone)]
+pub enum ArrowTarget {
+    File(PathBuf),
+    /// A listening unix socket, given as `unix:<path>`.
+    Socket(PathBuf),
+}
+
+impl ArrowTarget {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        match spec.strip_prefix("unix:") {
+            Some("") => Err(String::from("missing socket path after unix:")),
+            Some(path) => Ok(Self::Socket(PathBuf::from(path))),
+            None => Ok(Self::File(PathBuf::from(spec))),
+        }
+    }
+
+    fn open(&self) -> io::Result<Box<dyn Write + Send>> {
+        match self {
+            Self::File(path) => Ok(Box::new(File::create(path)?)),
+            #[cfg(unix)]
+            Self::Socket(path) => Ok(Box::new(UnixStream::connect(path)?)),
+            #[cfg(not(unix))]
+            Self::Socket(_) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "unix sockets are not available on this platform",
+            )),
+        }
+    }
+}
+
+/// Writes `candles` to `writer` as one IPC stream, one batch per candle.
+fn write_stream(
+    writer: Box<dyn Write + Send>,
+    candles: &mut mpsc::UnboundedReceiver<SinkEvent>,
+) -> ExportResult<()> {
+    let schema = candles_batch(&[])?.schema();
+    let mut stream = StreamWriter::try_new(writer, &schema)?;
+    while let Some(event) = candles.blocking_recv() {
+        if let SinkEvent::Candle { candle, .. } = event {
+            stream.write(&candles_batch(&[candle])?)?;
+            stream.flush()?;
+        }
+    }
+    stream.finish()?;
+    Ok(())
+}
+
+/// Opens `target` and streams every confirmed candle to it as Arrow IPC
+/// from a background thread, so readers can ingest it while the chart runs.
+pub fn spawn(
+    target: &ArrowTarget,
+    errors: UnboundedSender<String>,
+) -> io::Result<UnboundedSender<SinkEvent>> {
+    let writer = target.open()?;
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    thread::spawn(move || {
+        if let Err(e) = write_stream(writer, &mut rx) {
+            let _ = errors.send(format!("Arrow stream stopped: {}", e));
+        }
+    });
+    Ok(tx)
+}
@@ -0,0 +1,64 @@
+//! Avro binary encoding of the sink events, without a container or schema
+//! registry header: each payload is one datum of the schema below.
+
+use crate::models::{Candle, Side, Trade};
+
+pub const CANDLE_SCHEMA: &str = r#"{"type":"record","name":"Candle","namespace":"kline_chart","fields":[{"name":"symbol","type":"string"},{"name":"interval","type":"string"},{"name":"start","type":"long"},{"name":"open","type":"double"},{"name":"high","type":"double"},{"name":"low","type":"double"},{"name":"close","type":"double"},{"name":"volume","type":"double"},{"name":"turnover","type":"double"}]}"#;
+
+pub const TRADE_SCHEMA: &str = r#"{"type":"record","name":"Trade","namespace":"kline_chart","fields":[{"name":"symbol","type":"string"},{"name":"timestamp","type":"long"},{"name":"price","type":"double"},{"name":"size","type":"double"},{"name":"side","type":{"type":"enum","name":"Side","symbols":["Buy","Sell"]}},{"name":"block_trade","type":"boolean"}]}"#;
+
+fn long(out: &mut Vec<u8>, value: i64) {
+    let mut zigzag = ((value << 1) ^ (value >> 63)) as u64;
+    while zigzag >= 0x80 {
+        out.push((zigzag as u8) | 0x80);
+        zigzag >>= 7;
+    }
+    out.push(zigzag as u8);
+}
+
+fn double(out: &mut Vec<u8>, value: f64) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn string(out: &mut Vec<u8>, value: &str) {
+    long(out, value.len() as i64);
+    out.extend_from_slice(value.as_bytes());
+}
+
+/// A datum of [`CANDLE_SCHEMA`].
+pub fn candle(symbol: &str, interval: &str, candle: &Candle) -> Vec<u8> {
+    let mut out = Vec::new();
+    string(&mut out, symbol);
+    string(&mut out, interval);
+    long(&mut out, candle.start);
+    for value in [
+        candle.open,
+        candle.high,
+        candle.low,
+        candle.close,
+        candle.volume,
+        candle.turnover,
+    ] {
+        double(&mut out, value);
+    }
+    out
+}
+
+/// A datum of [`TRADE_SCHEMA`].
+pub fn trade(symbol: &str, trade: &Trade) -> Vec<u8> {
+    let mut out = Vec::new();
+    string(&mut out, symbol);
+    long(&mut out, trade.timestamp);
+    double(&mut out, trade.price);
+    double(&mut out, trade.size);
+    // Enum symbols are encoded by index
+    long(
+        &mut out,
+        match trade.side {
+            Side::Buy => 0,
+            Side::Sell => 1,
+        },
+    );
+    out.push(trade.block_trade as u8);
+    out
+}
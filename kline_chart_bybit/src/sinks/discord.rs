@@ -0,0 +1,84 @@
+use super::{template, SinkEvent};
+use crate::{alerts::Channel, constants::USER_AGENT};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+/// Embed colour of alerts, Discord's blurple.
+const EMBED_COLOR: u32 = 0x5865F2;
+
+/// `[discord]` in the config file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DiscordConfig {
+    /// Channel webhook URL from Server Settings > Integrations.
+    pub webhook_url: String,
+    /// Name the messages are posted under instead of the webhook's.
+    #[serde(default)]
+    pub username: Option<String>,
+}
+
+/// The webhook body for an alert: one embed with the symbol, prices and rule.
+fn alert_message(config: &DiscordConfig, event: &SinkEvent) -> Option<Value> {
+    let SinkEvent::Alert {
+        symbol, time, rule, ..
+    } = event
+    else {
+        return None;
+    };
+    let fields = template::alert_fields(event)?;
+    let field = |name: &str| {
+        fields
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.clone())
+            .unwrap_or_default()
+    };
+    let timestamp = chrono::DateTime::from_timestamp_millis(*time)
+        .map(|t| t.to_rfc3339())
+        .unwrap_or_default();
+    let mut message = json!({
+        "embeds": [{
+            "title": format!("{} alert", symbol),
+            "description": rule,
+            "color": EMBED_COLOR,
+            "timestamp": timestamp,
+            "fields": [
+                { "name": "Symbol", "value": symbol, "inline": true },
+                { "name": "Level", "value": field("price"), "inline": true },
+                { "name": "Last price", "value": field("last_price"), "inline": true },
+            ],
+        }],
+    });
+    if let Some(username) = &config.username {
+        message["username"] = json!(username);
+    }
+    Some(message)
+}
+
+/// Starts a task posting each alert routed to Discord to the webhook as an embed.
+pub fn spawn(config: DiscordConfig, errors: UnboundedSender<String>) -> UnboundedSender<SinkEvent> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<SinkEvent>();
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        while let Some(event) = rx.recv().await {
+            if !event.notifies(Channel::Discord) {
+                continue;
+            }
+            let Some(message) = alert_message(&config, &event) else {
+                continue;
+            };
+            let result = client
+                .post(&config.webhook_url)
+                .header("User-Agent", USER_AGENT)
+                .json(&message)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            if let Err(e) = result {
+                // Drop the URL, it contains the webhook token
+                let _ = errors.send(format!("Discord alert failed: {}", e.without_url()));
+            }
+        }
+    });
+    tx
+}
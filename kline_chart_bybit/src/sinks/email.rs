@@ -0,0 +1,116 @@
+use super::{template, SinkEvent};
+use crate::alerts::Channel;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use serde::Deserialize;
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+/// How the SMTP connection is secured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Security {
+    /// Plain connection upgraded with STARTTLS, usually port 587.
+    #[default]
+    Starttls,
+    /// TLS from the start, usually port 465.
+    Tls,
+    /// No encryption, for a local relay only.
+    None,
+}
+
+/// `[email]` in the config file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailConfig {
+    pub host: String,
+    /// Defaults to the usual port of `security`.
+    #[serde(default)]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub security: Security,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    /// Subject and body, filled in like the Telegram template.
+    #[serde(default = "default_subject")]
+    pub subject: String,
+    #[serde(default = "default_body")]
+    pub body: String,
+}
+
+fn default_subject() -> String {
+    String::from("[{symbol}] {rule}")
+}
+
+fn default_body() -> String {
+    String::from("{symbol} hit the alert {rule}.\n\nLast price: {last_price}\nTime: {time}\n")
+}
+
+type Mailer = AsyncSmtpTransport<Tokio1Executor>;
+
+fn mailer(config: &EmailConfig) -> Result<Mailer, lettre::transport::smtp::Error> {
+    let mut builder = match config.security {
+        Security::Starttls => Mailer::starttls_relay(&config.host)?,
+        Security::Tls => Mailer::relay(&config.host)?,
+        Security::None => Mailer::builder_dangerous(&config.host),
+    };
+    if let Some(port) = config.port {
+        builder = builder.port(port);
+    }
+    if let (Some(username), Some(password)) = (&config.username, &config.password) {
+        builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+    Ok(builder.build())
+}
+
+fn message(config: &EmailConfig, event: &SinkEvent) -> Result<Option<Message>, String> {
+    let (Some(subject), Some(body)) = (
+        template::render(&config.subject, event),
+        template::render(&config.body, event),
+    ) else {
+        return Ok(None);
+    };
+    let mailbox = |address: &str| {
+        address
+            .parse::<Mailbox>()
+            .map_err(|e| format!("bad address '{}': {}", address, e))
+    };
+    let mut builder = Message::builder()
+        .from(mailbox(&config.from)?)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN);
+    for to in &config.to {
+        builder = builder.to(mailbox(to)?);
+    }
+    builder.body(body).map(Some).map_err(|e| e.to_string())
+}
+
+/// Starts a task emailing each alert routed to email to the `to` addresses.
+pub fn spawn(
+    config: EmailConfig,
+    errors: UnboundedSender<String>,
+) -> Result<UnboundedSender<SinkEvent>, lettre::transport::smtp::Error> {
+    let mailer = mailer(&config)?;
+    let (tx, mut rx) = mpsc::unbounded_channel::<SinkEvent>();
+    tokio::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            if !event.notifies(Channel::Email) {
+                continue;
+            }
+            let result = match message(&config, &event) {
+                Ok(Some(message)) => mailer.send(message).await.map_err(|e| e.to_string()),
+                Ok(None) => continue,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                let _ = errors.send(format!("Email alert failed: {}", e));
+            }
+        }
+    });
+    Ok(tx)
+}
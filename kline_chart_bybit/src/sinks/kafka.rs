@@ -0,0 +1,90 @@
+use super::{avro, SinkEvent};
+use rdkafka::{
+    producer::{FutureProducer, FutureRecord},
+    ClientConfig,
+};
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+/// Encoding of the Kafka message values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Payload {
+    #[default]
+    Json,
+    /// Bare Avro datums, see [`avro::CANDLE_SCHEMA`] and [`avro::TRADE_SCHEMA`].
+    Avro,
+}
+
+/// `[kafka]` in the config file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct KafkaConfig {
+    /// Comma-separated `host:port` list.
+    pub brokers: String,
+    pub candles_topic: String,
+    pub trades_topic: String,
+    pub payload: Payload,
+}
+
+impl Default for KafkaConfig {
+    fn default() -> Self {
+        Self {
+            brokers: String::from("localhost:9092"),
+            candles_topic: String::from("kline_chart.candles"),
+            trades_topic: String::from("kline_chart.trades"),
+            payload: Payload::Json,
+        }
+    }
+}
+
+/// The topic, key and value an event is produced as; alerts are not sent.
+fn record(config: &KafkaConfig, event: &SinkEvent) -> Option<(String, String, Vec<u8>)> {
+    let json = || serde_json::to_vec(event).ok();
+    match event {
+        SinkEvent::Candle {
+            symbol,
+            interval,
+            candle,
+        } => {
+            let value = match config.payload {
+                Payload::Json => json()?,
+                Payload::Avro => avro::candle(symbol, interval, candle),
+            };
+            Some((config.candles_topic.clone(), symbol.clone(), value))
+        }
+        SinkEvent::Trade { symbol, trade } => {
+            let value = match config.payload {
+                Payload::Json => json()?,
+                Payload::Avro => avro::trade(symbol, trade),
+            };
+            Some((config.trades_topic.clone(), symbol.clone(), value))
+        }
+        SinkEvent::Alert { .. } => None,
+    }
+}
+
+/// Starts a task producing candles and trades to their topics, keyed by symbol.
+pub fn spawn(
+    config: KafkaConfig,
+    errors: UnboundedSender<String>,
+) -> rdkafka::error::KafkaResult<UnboundedSender<SinkEvent>> {
+    let producer: FutureProducer = ClientConfig::new()
+        .set("bootstrap.servers", &config.brokers)
+        .set("message.timeout.ms", "5000")
+        .create()?;
+    let (tx, mut rx) = mpsc::unbounded_channel::<SinkEvent>();
+    tokio::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            let Some((topic, key, value)) = record(&config, &event) else {
+                continue;
+            };
+            let record = FutureRecord::to(&topic).key(&key).payload(&value);
+            if let Err((e, _)) = producer.send(record, Duration::ZERO).await {
+                let _ = errors.send(format!("Kafka sink: {}", e));
+            }
+        }
+    });
+    Ok(tx)
+}
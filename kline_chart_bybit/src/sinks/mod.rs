@@ -0,0 +1,102 @@
+pub mod arrow;
+pub mod avro;
+pub mod discord;
+pub mod email;
+pub mod kafka;
+pub mod mqtt;
+pub mod redis;
+pub mod telegram;
+pub mod template;
+pub mod webhook;
+
+use crate::{
+    alerts::Channel,
+    models::{Candle, Trade},
+};
+use serde::Serialize;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+/// Something worth telling downstream services about.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkEvent {
+    /// A candle that just confirmed.
+    Candle {
+        symbol: String,
+        interval: String,
+        #[serde(flatten)]
+        candle: Candle,
+    },
+    /// A public trade in the chart symbol.
+    Trade {
+        symbol: String,
+        #[serde(flatten)]
+        trade: Trade,
+    },
+    /// A price alert that fired.
+    Alert {
+        symbol: String,
+        price: f64,
+        last_price: f64,
+        /// What the alert was waiting for, e.g. `price above 3200.00`.
+        rule: String,
+        /// Notification channels the alert asked for.
+        channels: Vec<Channel>,
+        /// Milliseconds since the epoch.
+        time: i64,
+    },
+}
+
+impl SinkEvent {
+    /// Whether a notifier for `channel` should deliver this event: only
+    /// alerts, and only those routed to it.
+    pub fn notifies(&self, channel: Channel) -> bool {
+        matches!(self, SinkEvent::Alert { channels, .. } if channels.contains(&channel))
+    }
+}
+
+/// Fan-out to the configured sinks. Each sink runs as its own task and
+/// reports failures back here instead of printing over the TUI.
+pub struct Sinks {
+    senders: Vec<UnboundedSender<SinkEvent>>,
+    errors_tx: UnboundedSender<String>,
+    errors_rx: UnboundedReceiver<String>,
+}
+
+impl Sinks {
+    pub fn new() -> Self {
+        let (errors_tx, errors_rx) = mpsc::unbounded_channel();
+        Self {
+            senders: Vec::new(),
+            errors_tx,
+            errors_rx,
+        }
+    }
+
+    /// Where a sink task, or the `--serve` and `--grpc` servers, send their
+    /// error messages.
+    pub fn error_sender(&self) -> UnboundedSender<String> {
+        self.errors_tx.clone()
+    }
+
+    pub fn add(&mut self, sender: UnboundedSender<SinkEvent>) {
+        self.senders.push(sender);
+    }
+
+    pub fn publish(&mut self, event: SinkEvent) {
+        // A sink whose task ended is dropped
+        self.senders
+            .retain(|sender| sender.send(event.clone()).is_ok());
+    }
+
+    /// The oldest unreported sink failure.
+    pub fn take_error(&mut self) -> Option<String> {
+        self.errors_rx.try_recv().ok()
+    }
+}
+
+impl Default for Sinks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,98 @@
+use super::SinkEvent;
+use rumqttc::{AsyncClient, MqttOptions, QoS};
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+/// `[mqtt]` in the config file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MqttConfig {
+    pub host: String,
+    pub port: u16,
+    pub client_id: String,
+    /// Last trade price as a plain number, retained so new subscribers get it.
+    pub price_topic: String,
+    pub candles_topic: String,
+    pub alerts_topic: String,
+    /// 0 (at most once), 1 (at least once) or 2 (exactly once).
+    pub qos: u8,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            host: String::from("localhost"),
+            port: 1883,
+            client_id: String::from("kline_chart_bybit"),
+            price_topic: String::from("kline_chart/price"),
+            candles_topic: String::from("kline_chart/candles"),
+            alerts_topic: String::from("kline_chart/alerts"),
+            qos: 0,
+        }
+    }
+}
+
+impl MqttConfig {
+    fn qos(&self) -> QoS {
+        match self.qos {
+            0 => QoS::AtMostOnce,
+            1 => QoS::AtLeastOnce,
+            _ => QoS::ExactlyOnce,
+        }
+    }
+}
+
+/// Topic, payload and retain flag an event is published with.
+fn message(config: &MqttConfig, event: &SinkEvent) -> Option<(String, Vec<u8>, bool)> {
+    match event {
+        SinkEvent::Trade { symbol, trade } => Some((
+            format!("{}/{}", config.price_topic, symbol),
+            trade.price.to_string().into_bytes(),
+            true,
+        )),
+        SinkEvent::Candle { symbol, .. } => Some((
+            format!("{}/{}", config.candles_topic, symbol),
+            serde_json::to_vec(event).ok()?,
+            false,
+        )),
+        SinkEvent::Alert { symbol, .. } => Some((
+            format!("{}/{}", config.alerts_topic, symbol),
+            serde_json::to_vec(event).ok()?,
+            false,
+        )),
+    }
+}
+
+/// Starts the MQTT client and a task publishing prices, candles and alerts
+/// under their topics, suffixed with the symbol.
+pub fn spawn(config: MqttConfig, errors: UnboundedSender<String>) -> UnboundedSender<SinkEvent> {
+    let mut options = MqttOptions::new(&config.client_id, &config.host, config.port);
+    options.set_keep_alive(Duration::from_secs(30));
+    let (client, mut eventloop) = AsyncClient::new(options, 64);
+
+    // The event loop does the network work and reconnects when polled again
+    let loop_errors = errors.clone();
+    tokio::spawn(async move {
+        loop {
+            if let Err(e) = eventloop.poll().await {
+                let _ = loop_errors.send(format!("MQTT sink: {}", e));
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+        }
+    });
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<SinkEvent>();
+    tokio::spawn(async move {
+        let qos = config.qos();
+        while let Some(event) = rx.recv().await {
+            let Some((topic, payload, retain)) = message(&config, &event) else {
+                continue;
+            };
+            if let Err(e) = client.publish(topic, qos, retain, payload).await {
+                let _ = errors.send(format!("MQTT sink: {}", e));
+            }
+        }
+    });
+    tx
+}
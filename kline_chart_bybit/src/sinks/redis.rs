@@ -0,0 +1,62 @@
+use super::SinkEvent;
+use redis::{aio::MultiplexedConnection, AsyncCommands, Client};
+use serde::Deserialize;
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+/// `[redis]` in the config file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RedisConfig {
+    pub url: String,
+    pub candles_channel: String,
+    pub alerts_channel: String,
+}
+
+impl Default for RedisConfig {
+    fn default() -> Self {
+        Self {
+            url: String::from("redis://127.0.0.1/"),
+            candles_channel: String::from("kline_chart:candles"),
+            alerts_channel: String::from("kline_chart:alerts"),
+        }
+    }
+}
+
+/// Starts a task publishing each event as JSON to its Redis channel. The
+/// connection is made on the first event and remade after a failure.
+pub fn spawn(
+    config: RedisConfig,
+    errors: UnboundedSender<String>,
+) -> redis::RedisResult<UnboundedSender<SinkEvent>> {
+    let client = Client::open(config.url.as_str())?;
+    let (tx, mut rx) = mpsc::unbounded_channel::<SinkEvent>();
+    tokio::spawn(async move {
+        let mut connection: Option<MultiplexedConnection> = None;
+        while let Some(event) = rx.recv().await {
+            let channel = match event {
+                SinkEvent::Candle { .. } => &config.candles_channel,
+                SinkEvent::Alert { .. } => &config.alerts_channel,
+                SinkEvent::Trade { .. } => continue,
+            };
+            let Ok(payload) = serde_json::to_string(&event) else {
+                continue;
+            };
+            let result = match connection.as_mut() {
+                Some(conn) => conn.publish::<_, _, ()>(channel, payload).await,
+                None => match client.get_multiplexed_async_connection().await {
+                    Ok(mut conn) => {
+                        let sent = conn.publish::<_, _, ()>(channel, payload).await;
+                        connection = Some(conn);
+                        sent
+                    }
+                    Err(e) => Err(e),
+                },
+            };
+            if let Err(e) = result {
+                connection = None;
+                let _ = errors.send(format!("Redis sink: {}", e));
+            }
+        }
+    });
+    Ok(tx)
+}
@@ -0,0 +1,76 @@
+use super::{template, SinkEvent};
+use crate::{alerts::Channel, constants::USER_AGENT};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+/// `[telegram]` in the config file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramConfig {
+    /// Bot token from @BotFather.
+    pub token: String,
+    /// Chat, group or channel the bot posts to.
+    pub chat_id: String,
+    /// Message text; `{symbol}`, `{price}`, `{last_price}`, `{rule}` and
+    /// `{time}` are filled in from the alert.
+    #[serde(default = "default_template")]
+    pub template: String,
+    #[serde(default = "default_api_url")]
+    pub api_url: String,
+}
+
+fn default_template() -> String {
+    String::from("{symbol}: {rule} hit, last {last_price} at {time}")
+}
+
+fn default_api_url() -> String {
+    String::from("https://api.telegram.org")
+}
+
+#[derive(Debug, Deserialize)]
+struct BotResponse {
+    ok: bool,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// Starts a task sending each alert routed to Telegram to the chat through the Bot API.
+pub fn spawn(
+    config: TelegramConfig,
+    errors: UnboundedSender<String>,
+) -> UnboundedSender<SinkEvent> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<SinkEvent>();
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        let url = format!("{}/bot{}/sendMessage", config.api_url, config.token);
+        while let Some(event) = rx.recv().await {
+            if !event.notifies(Channel::Telegram) {
+                continue;
+            }
+            let Some(text) = template::render(&config.template, &event) else {
+                continue;
+            };
+            let body = json!({ "chat_id": config.chat_id, "text": text });
+            let result = async {
+                let response: BotResponse = client
+                    .post(&url)
+                    .header("User-Agent", USER_AGENT)
+                    .json(&body)
+                    .send()
+                    .await?
+                    .json()
+                    .await?;
+                Ok::<_, reqwest::Error>(response)
+            }
+            .await;
+            let error = match result {
+                Ok(response) if response.ok => continue,
+                Ok(response) => response.description.unwrap_or_default(),
+                // Drop the URL, it contains the token
+                Err(e) => e.without_url().to_string(),
+            };
+            let _ = errors.send(format!("Telegram alert failed: {}", error));
+        }
+    });
+    tx
+}
@@ -0,0 +1,45 @@
+use super::SinkEvent;
+
+/// The placeholder values of a fired alert, or `None` for other events.
+pub fn alert_fields(event: &SinkEvent) -> Option<Vec<(&'static str, String)>> {
+    let SinkEvent::Alert {
+        symbol,
+        price,
+        last_price,
+        rule,
+        time,
+        ..
+    } = event
+    else {
+        return None;
+    };
+    let time = chrono::DateTime::from_timestamp_millis(*time)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_default();
+    Some(vec![
+        ("symbol", symbol.clone()),
+        ("price", format!("{:.2}", price)),
+        ("last_price", format!("{:.2}", last_price)),
+        ("rule", rule.clone()),
+        ("time", time),
+    ])
+}
+
+/// Replaces every `{name}` in `template` with its value, passed through
+/// `escape`; unknown placeholders are left as they are.
+pub fn render_with(
+    template: &str,
+    fields: &[(&str, String)],
+    escape: impl Fn(&str) -> String,
+) -> String {
+    let mut text = template.to_string();
+    for (name, value) in fields {
+        text = text.replace(&format!("{{{}}}", name), &escape(value));
+    }
+    text
+}
+
+/// Renders an alert into `template`, or `None` for other events.
+pub fn render(template: &str, event: &SinkEvent) -> Option<String> {
+    alert_fields(event).map(|fields| render_with(template, &fields, str::to_string))
+}
@@ -0,0 +1,70 @@
+use super::{template, SinkEvent};
+use crate::{alerts::Channel, constants::USER_AGENT};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+/// `[webhook]` in the config file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Extra request headers, e.g. an `Authorization` token.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// JSON body; placeholders are filled in JSON-escaped, so put string
+    /// ones inside quotes: `{"text": "{symbol} {rule}"}`.
+    #[serde(default = "default_body")]
+    pub body: String,
+}
+
+fn default_body() -> String {
+    String::from(
+        r#"{"symbol": "{symbol}", "price": {price}, "last_price": {last_price}, "rule": "{rule}", "time": "{time}"}"#,
+    )
+}
+
+/// `value` escaped for use inside a JSON string.
+fn json_escape(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap_or_default();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// Starts a task posting the rendered body for each alert routed to the webhook.
+pub fn spawn(config: WebhookConfig, errors: UnboundedSender<String>) -> UnboundedSender<SinkEvent> {
+    let (tx, mut rx) = mpsc::unbounded_channel::<SinkEvent>();
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        while let Some(event) = rx.recv().await {
+            if !event.notifies(Channel::Webhook) {
+                continue;
+            }
+            let Some(fields) = template::alert_fields(&event) else {
+                continue;
+            };
+            let body = template::render_with(&config.body, &fields, json_escape);
+            let body: Value = match serde_json::from_str(&body) {
+                Ok(body) => body,
+                Err(e) => {
+                    let _ = errors.send(format!("Webhook body is not valid JSON: {}", e));
+                    continue;
+                }
+            };
+            let mut request = client
+                .post(&config.url)
+                .header("User-Agent", USER_AGENT)
+                .json(&body);
+            for (name, value) in &config.headers {
+                request = request.header(name, value);
+            }
+            let result = request
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            if let Err(e) = result {
+                let _ = errors.send(format!("Webhook alert failed: {}", e));
+            }
+        }
+    });
+    tx
+}
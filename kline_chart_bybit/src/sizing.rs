@@ -0,0 +1,63 @@
+use crate::{instrument::Instrument, models::Side};
+
+/// Result of the risk calculator.
+#[derive(Debug, Clone)]
+pub struct PositionSize {
+    pub side: Side,
+    pub qty: f64,
+    pub stop_price: f64,
+    /// Money lost if the stop is hit with `qty`.
+    pub risk: f64,
+}
+
+/// Parses `<risk %> <stop distance | @stop price>`, e.g. `1 15` or `0.5 @2950`.
+fn parse_input(input: &str) -> Result<(f64, &str), String> {
+    let mut tokens = input.split_whitespace();
+    let risk_percent = tokens
+        .next()
+        .ok_or("enter risk %")?
+        .trim_end_matches('%')
+        .parse::<f64>()
+        .ok()
+        .filter(|r| *r > 0.0 && *r <= 100.0)
+        .ok_or("risk % must be between 0 and 100")?;
+    let stop = tokens.next().ok_or("enter stop distance or @price")?;
+    Ok((risk_percent, stop))
+}
+
+/// Sizes a position so that hitting the stop loses `risk %` of `balance`.
+/// A stop below `entry` means a long, above it a short.
+pub fn size_position(
+    input: &str,
+    balance: f64,
+    entry: f64,
+    instrument: &Instrument,
+) -> Result<PositionSize, String> {
+    let (risk_percent, stop) = parse_input(input)?;
+    let stop_price = match stop.strip_prefix('@') {
+        Some(price) => price.parse::<f64>().map_err(|_| "invalid stop price")?,
+        // A bare distance puts the stop under the entry, i.e. a long
+        None => entry - stop.parse::<f64>().map_err(|_| "invalid stop distance")?,
+    };
+    let stop_price = instrument.round_price(stop_price);
+    let distance = (entry - stop_price).abs();
+    if distance < instrument.tick_size {
+        return Err(String::from("stop is at the entry price"));
+    }
+    let side = if stop_price < entry {
+        Side::Buy
+    } else {
+        Side::Sell
+    };
+
+    let budget = balance * risk_percent / 100.0;
+    let qty = instrument
+        .round_qty(budget / distance)
+        .ok_or_else(|| format!("risk {:.2} is below the minimum order size", budget))?;
+    Ok(PositionSize {
+        side,
+        qty,
+        stop_price,
+        risk: qty * distance,
+    })
+}
@@ -0,0 +1,145 @@
+use crate::{models::Candle, paths::data_dir};
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+
+/// Exchange column of every row written by this app.
+const EXCHANGE: &str = "bybit";
+
+/// Local SQLite database of confirmed candles, so history accumulates across
+/// sessions instead of being refetched.
+pub struct CandleStore {
+    conn: Connection,
+}
+
+/// `market.sqlite` in the data directory.
+pub fn default_path() -> PathBuf {
+    data_dir().join("market.sqlite")
+}
+
+impl CandleStore {
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        if let Some(dir) = path.parent() {
+            // A missing directory surfaces as the open error below
+            let _ = std::fs::create_dir_all(dir);
+        }
+        let conn = Connection::open(path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS candles (
+                exchange TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                interval TEXT NOT NULL,
+                start INTEGER NOT NULL,
+                open REAL NOT NULL,
+                high REAL NOT NULL,
+                low REAL NOT NULL,
+                close REAL NOT NULL,
+                volume REAL NOT NULL,
+                turnover REAL NOT NULL,
+                PRIMARY KEY (exchange, symbol, interval, start)
+            )",
+        )?;
+        Ok(Self { conn })
+    }
+
+    /// Stores a confirmed candle, replacing any earlier copy of it.
+    pub fn insert(&self, symbol: &str, interval: &str, candle: &Candle) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO candles
+                (exchange, symbol, interval, start, open, high, low, close, volume, turnover)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                EXCHANGE,
+                symbol,
+                interval,
+                candle.start,
+                candle.open,
+                candle.high,
+                candle.low,
+                candle.close,
+                candle.volume,
+                candle.turnover
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Stores a batch of downloaded candles in one transaction.
+    pub fn insert_all(
+        &self,
+        symbol: &str,
+        interval: &str,
+        candles: &[Candle],
+    ) -> rusqlite::Result<()> {
+        let transaction = self.conn.unchecked_transaction()?;
+        for candle in candles {
+            self.insert(symbol, interval, candle)?;
+        }
+        transaction.commit()
+    }
+
+    /// The newest `limit` candles, oldest first.
+    pub fn latest(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: usize,
+    ) -> rusqlite::Result<Vec<Candle>> {
+        let mut statement = self.conn.prepare(
+            "SELECT start, open, high, low, close, volume, turnover FROM candles
+             WHERE exchange = ?1 AND symbol = ?2 AND interval = ?3
+             ORDER BY start DESC LIMIT ?4",
+        )?;
+        let mut candles = statement
+            .query_map(params![EXCHANGE, symbol, interval, limit as i64], |row| {
+                Ok(Candle {
+                    start: row.get(0)?,
+                    open: row.get(1)?,
+                    high: row.get(2)?,
+                    low: row.get(3)?,
+                    close: row.get(4)?,
+                    volume: row.get(5)?,
+                    turnover: row.get(6)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        candles.reverse();
+        Ok(candles)
+    }
+
+    /// Every stored candle of one series, oldest first.
+    pub fn series(&self, symbol: &str, interval: &str) -> rusqlite::Result<Vec<Candle>> {
+        let mut statement = self.conn.prepare(
+            "SELECT start, open, high, low, close, volume, turnover FROM candles
+             WHERE exchange = ?1 AND symbol = ?2 AND interval = ?3
+             ORDER BY start",
+        )?;
+        let candles = statement
+            .query_map(params![EXCHANGE, symbol, interval], |row| {
+                Ok(Candle {
+                    start: row.get(0)?,
+                    open: row.get(1)?,
+                    high: row.get(2)?,
+                    low: row.get(3)?,
+                    close: row.get(4)?,
+                    volume: row.get(5)?,
+                    turnover: row.get(6)?,
+                })
+            })?
+            .collect();
+        candles
+    }
+
+    /// Start of the newest stored candle of every symbol and interval.
+    pub fn last_starts(&self) -> rusqlite::Result<Vec<(String, String, i64)>> {
+        let mut statement = self.conn.prepare(
+            "SELECT symbol, interval, MAX(start) FROM candles
+             WHERE exchange = ?1 GROUP BY symbol, interval",
+        )?;
+        let rows = statement
+            .query_map(params![EXCHANGE], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?
+            .collect();
+        rows
+    }
+}
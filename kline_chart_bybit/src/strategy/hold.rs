@@ -0,0 +1,32 @@
+use super::{Signal, Strategy};
+use crate::models::Candle;
+
+pub struct BuyAndHold {
+    entered: bool,
+}
+
+impl BuyAndHold {
+    pub fn new() -> Self {
+        Self { entered: false }
+    }
+}
+
+impl Default for BuyAndHold {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Strategy for BuyAndHold {
+    fn name(&self) -> &str {
+        "buy & hold"
+    }
+
+    fn on_candle(&mut self, _candle: &Candle) -> Option<Signal> {
+        if self.entered {
+            return None;
+        }
+        self.entered = true;
+        Some(Signal::EnterLong)
+    }
+}
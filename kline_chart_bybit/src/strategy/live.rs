@@ -0,0 +1,68 @@
+use super::Signal;
+use crate::{models::Side, orders::OrderRequest};
+use clap::ValueEnum;
+
+/// What the live chart does with a strategy's signals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum LiveMode {
+    /// Only draw the signals and show them in the status line
+    #[default]
+    DryRun,
+    /// Also send orders for them through the order module
+    Trade,
+}
+
+/// Limits on what a live strategy may hold.
+#[derive(Debug, Clone, Copy)]
+pub struct PositionCaps {
+    /// Size of each entry.
+    pub qty: f64,
+    /// Largest absolute position, whatever the signals say.
+    pub max_position: f64,
+}
+
+/// Market orders that take the position from `current` (signed, negative
+/// when short) to where `signal` wants it, within `caps`.
+///
+/// Turning from long to short closes with a reduce-only order first, so a
+/// rejected entry never leaves a larger position than before.
+pub fn orders_for(
+    symbol: &str,
+    signal: Signal,
+    current: f64,
+    caps: PositionCaps,
+) -> Vec<OrderRequest> {
+    let size = caps.qty.min(caps.max_position);
+    let target = match signal {
+        Signal::EnterLong => size,
+        Signal::EnterShort => -size,
+        Signal::Exit => 0.0,
+    };
+    if (target - current).abs() <= f64::EPSILON {
+        return Vec::new();
+    }
+
+    let mut orders = Vec::new();
+    let mut current = current;
+    if current != 0.0 && (target == 0.0 || target.signum() != current.signum()) {
+        orders.push(OrderRequest::market(symbol, side_of(-current), current.abs()).reduce_only());
+        current = 0.0;
+    }
+    let delta = target - current;
+    if delta.abs() > f64::EPSILON {
+        let mut order = OrderRequest::market(symbol, side_of(delta), delta.abs());
+        if target.abs() < current.abs() {
+            order = order.reduce_only();
+        }
+        orders.push(order);
+    }
+    orders
+}
+
+fn side_of(delta: f64) -> Side {
+    if delta > 0.0 {
+        Side::Buy
+    } else {
+        Side::Sell
+    }
+}
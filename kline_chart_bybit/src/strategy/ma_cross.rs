@@ -0,0 +1,60 @@
+use super::{Signal, Strategy};
+use crate::models::Candle;
+use std::collections::VecDeque;
+
+/// Simple-moving-average crossover: long when the fast average closes above
+/// the slow one, flat when it closes back below.
+///
+/// This is the reference implementation of [`Strategy`]: it keeps only the
+/// closes it needs and emits a signal on the candle where the cross happens.
+pub struct MaCrossover {
+    fast: usize,
+    slow: usize,
+    closes: VecDeque<f64>,
+    /// Whether fast was above slow on the previous candle.
+    fast_above: Option<bool>,
+    name: String,
+}
+
+impl MaCrossover {
+    pub fn new(fast: usize, slow: usize) -> Self {
+        let fast = fast.max(1);
+        let slow = slow.max(fast + 1);
+        Self {
+            fast,
+            slow,
+            closes: VecDeque::with_capacity(slow + 1),
+            fast_above: None,
+            name: format!("MA cross {}/{}", fast, slow),
+        }
+    }
+
+    fn average(&self, period: usize) -> f64 {
+        self.closes.iter().rev().take(period).sum::<f64>() / period as f64
+    }
+}
+
+impl Strategy for MaCrossover {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn on_candle(&mut self, candle: &Candle) -> Option<Signal> {
+        self.closes.push_back(candle.close);
+        if self.closes.len() > self.slow {
+            self.closes.pop_front();
+        }
+        if self.closes.len() < self.slow {
+            return None;
+        }
+
+        let above = self.average(self.fast) > self.average(self.slow);
+        let crossed = self.fast_above.is_some_and(|was_above| was_above != above);
+        self.fast_above = Some(above);
+        match (crossed, above) {
+            (true, true) => Some(Signal::EnterLong),
+            (true, false) => Some(Signal::Exit),
+            _ => None,
+        }
+    }
+}
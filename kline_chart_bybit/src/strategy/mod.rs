@@ -0,0 +1,78 @@
+pub mod hold;
+pub mod live;
+pub mod ma_cross;
+
+pub use hold::BuyAndHold;
+pub use live::{LiveMode, PositionCaps};
+pub use ma_cross::MaCrossover;
+
+use crate::models::Candle;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+/// What a strategy wants its position to become.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Signal {
+    EnterLong,
+    EnterShort,
+    Exit,
+}
+
+impl Signal {
+    /// Arrow drawn on the chart where the signal fired.
+    pub fn arrow(self) -> &'static str {
+        match self {
+            Signal::EnterLong => "▲ long",
+            Signal::EnterShort => "▼ short",
+            Signal::Exit => "✕ exit",
+        }
+    }
+}
+
+/// A signal as it fired, for drawing and logging.
+#[derive(Debug, Clone, Copy)]
+pub struct SignalEvent {
+    /// Start of the candle whose close produced the signal.
+    pub time: i64,
+    pub price: f64,
+    pub signal: Signal,
+}
+
+/// A trading rule fed one closed candle at a time, by the backtester or by
+/// the live chart as candles confirm.
+pub trait Strategy {
+    fn name(&self) -> &str;
+
+    fn on_candle(&mut self, candle: &Candle) -> Option<Signal>;
+}
+
+/// Built-in strategies selectable from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StrategyKind {
+    /// Buy on the first candle and hold; the baseline to beat
+    Hold,
+    /// Fast/slow simple moving average crossover, long only
+    MaCross,
+}
+
+/// Tuning knobs of the built-in strategies.
+#[derive(Debug, Clone, Args)]
+pub struct StrategyParams {
+    /// Fast moving average period of ma-cross
+    #[arg(long, default_value_t = 10)]
+    pub fast: usize,
+
+    /// Slow moving average period of ma-cross
+    #[arg(long, default_value_t = 30)]
+    pub slow: usize,
+}
+
+impl StrategyKind {
+    pub fn build(self, params: &StrategyParams) -> Box<dyn Strategy> {
+        match self {
+            StrategyKind::Hold => Box::new(BuyAndHold::new()),
+            StrategyKind::MaCross => Box::new(MaCrossover::new(params.fast, params.slow)),
+        }
+    }
+}
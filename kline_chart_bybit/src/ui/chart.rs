@@ -1,22 +1,95 @@
 use crate::{
-    constants::MA_WINDOW_SIZE,
+    constants::{MA_WINDOW_SIZE, MIN_ZOOM},
     models::{Candle, KlineData},
 };
+use chrono::{TimeZone, Utc};
+use kline_core::chart::{self, price_precision};
+use plotters::{
+    prelude::{
+        BitMapBackend, CandleStick, ChartBuilder, IntoDrawingArea, LineSeries, PathElement,
+        RGBColor, BLACK,
+    },
+    style::Color as _,
+};
 use ratatui::{
-    layout::Rect,
-    style::Color,
+    layout::{Margin, Rect},
+    style::{Color, Style},
+    text::{Line as TextLine, Span},
     widgets::{
-        canvas::{Canvas, Context, Line, Points},
-        Block, Borders,
+        canvas::{Canvas, Context, Line},
+        Block, Borders, Clear, Paragraph,
     },
     Frame,
 };
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
+
+/// A horizontal price line drawn across the whole chart.
+#[derive(Debug, Clone)]
+pub struct PriceLevel {
+    pub price: f64,
+    pub label: String,
+    pub color: Color,
+    pub dashed: bool,
+}
+
+/// A line between two (candle start time, price) points.
+#[derive(Debug, Clone)]
+pub struct TimeLine {
+    pub start: (i64, f64),
+    pub end: (i64, f64),
+    pub color: Color,
+    /// Keep drawing past `end` up to the right edge of the chart.
+    pub extend_right: bool,
+}
+
+/// A text label pinned to a (time, price) point.
+#[derive(Debug, Clone)]
+pub struct Marker {
+    pub time: i64,
+    pub price: f64,
+    pub text: String,
+    pub color: Color,
+}
+
+/// Closes of a second symbol, overlaid on the main series.
+struct Comparison {
+    symbol: String,
+    closes: BTreeMap<i64, f64>,
+}
+
+/// Colors of bullish and bearish candles and the moving average.
+#[derive(Debug, Clone, Copy)]
+pub struct ChartColors {
+    pub up: Color,
+    pub down: Color,
+    pub ma: Color,
+}
+
+impl Default for ChartColors {
+    fn default() -> Self {
+        Self {
+            up: Color::Green,
+            down: Color::Red,
+            ma: Color::Yellow,
+        }
+    }
+}
 
 pub struct CandlestickChart {
     candles: Vec<Candle>,
     visible_range: usize,
+    /// Candles shown, at most `visible_range`.
+    zoom: usize,
+    show_ma: bool,
+    ma_window: usize,
+    colors: ChartColors,
     ma50_values: VecDeque<f64>,
+    levels: Vec<PriceLevel>,
+    time_lines: Vec<TimeLine>,
+    markers: Vec<Marker>,
+    comparison: Option<Comparison>,
+    /// Lines and candles come from a raster image; the canvas only draws text.
+    raster: bool,
 }
 
 impl CandlestickChart {
@@ -24,10 +97,115 @@ impl CandlestickChart {
         Self {
             candles: Vec::new(),
             visible_range,
+            zoom: visible_range,
+            show_ma: true,
+            ma_window: MA_WINDOW_SIZE,
+            colors: ChartColors::default(),
             ma50_values: VecDeque::new(),
+            levels: Vec::new(),
+            time_lines: Vec::new(),
+            markers: Vec::new(),
+            comparison: None,
+            raster: false,
+        }
+    }
+
+    pub fn set_levels(&mut self, levels: Vec<PriceLevel>) {
+        self.levels = levels;
+    }
+
+    pub fn set_time_lines(&mut self, time_lines: Vec<TimeLine>) {
+        self.time_lines = time_lines;
+    }
+
+    pub fn set_comparison_symbol(&mut self, symbol: String) {
+        self.comparison = Some(Comparison {
+            symbol,
+            closes: BTreeMap::new(),
+        });
+    }
+
+    pub fn update_comparison_from_kline(&mut self, kline_data: &KlineData) {
+        let Some(comparison) = self.comparison.as_mut() else {
+            return;
+        };
+        if let Ok(close) = kline_data.close.parse() {
+            comparison.closes.insert(kline_data.start, close);
+        }
+        // Keep a bit more than a screen's worth so the left edge stays populated
+        while comparison.closes.len() > self.visible_range * 2 {
+            comparison.closes.pop_first();
+        }
+    }
+
+    pub fn set_raster(&mut self, raster: bool) {
+        self.raster = raster;
+    }
+
+    pub fn set_markers(&mut self, markers: Vec<Marker>) {
+        self.markers = markers;
+    }
+
+    /// Most candles the chart holds.
+    pub fn capacity(&self) -> usize {
+        self.visible_range
+    }
+
+    pub fn zoom(&self) -> usize {
+        self.zoom
+    }
+
+    /// Shows the last `zoom` candles, clamped to what the chart holds.
+    pub fn set_zoom(&mut self, zoom: usize) {
+        self.zoom = zoom.clamp(MIN_ZOOM.min(self.visible_range), self.visible_range);
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.set_zoom(self.zoom.saturating_sub(MIN_ZOOM));
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.set_zoom(self.zoom + MIN_ZOOM);
+    }
+
+    pub fn ma_window(&self) -> usize {
+        self.ma_window
+    }
+
+    pub fn set_ma_window(&mut self, window: usize) {
+        self.ma_window = window.max(1);
+    }
+
+    pub fn set_colors(&mut self, colors: ChartColors) {
+        self.colors = colors;
+    }
+
+    /// Candle color by direction.
+    fn candle_color(&self, candle: &Candle) -> Color {
+        if candle.is_bullish() {
+            self.colors.up
+        } else {
+            self.colors.down
         }
     }
 
+    pub fn show_ma(&self) -> bool {
+        self.show_ma
+    }
+
+    pub fn set_show_ma(&mut self, show: bool) {
+        self.show_ma = show;
+    }
+
+    /// Every candle held, oldest first.
+    pub fn candles(&self) -> &[Candle] {
+        &self.candles
+    }
+
+    pub fn last_price(&self) -> Option<f64> {
+        self.candles.last().map(|c| c.close)
+    }
+
     pub fn update_from_kline(&mut self, kline_data: &KlineData) {
         if let Some(candle) = Candle::from_kline_data(kline_data) {
             if kline_data.confirm {
@@ -47,8 +225,28 @@ impl CandlestickChart {
         }
     }
 
+    /// Inserts a candle, or replaces the stored one with the same start time.
+    pub fn upsert_candle(&mut self, candle: Candle) {
+        match self.candles.last_mut() {
+            Some(last) if last.start == candle.start => *last = candle,
+            Some(last) if last.start > candle.start => {
+                match self.candles.iter_mut().find(|c| c.start == candle.start) {
+                    Some(existing) => *existing = candle,
+                    None => return,
+                }
+            }
+            _ => {
+                if self.candles.len() >= self.visible_range {
+                    self.candles.remove(0);
+                }
+                self.candles.push(candle);
+            }
+        }
+        self.calculate_ma50();
+    }
+
     fn calculate_ma50(&mut self) {
-        let start_idx = self.candles.len().saturating_sub(MA_WINDOW_SIZE);
+        let start_idx = self.candles.len().saturating_sub(self.ma_window);
         let sum: f64 = self.candles[start_idx..].iter().map(|c| c.close).sum();
         let count = self.candles.len() - start_idx;
         let ma50 = sum / count as f64;
@@ -62,13 +260,9 @@ impl CandlestickChart {
     pub fn draw(&self, frame: &mut Frame, area: Rect) {
         let chart_block = Block::default()
             .borders(Borders::ALL)
-            .title("Live Candlestick Chart with MA50 (Press 'q' to quit)");
+            .title(format!("Live Candlestick Chart with MA{} ('q' quit, 't' trades, 'd' DOM, 'p' positions, 'b' wallet, 'e' fills, 'O' orders, 'E' equity, 'P' profile, 'L' leverage, 'a' alert, 'N' alert channels, 'T' alert order, 'c' clear, 'x' export, 'w' draw, '+'/'-' zoom, 'M' MA)", self.ma_window));
 
-        let visible_candles = if !self.candles.is_empty() {
-            &self.candles[self.candles.len().saturating_sub(self.visible_range)..]
-        } else {
-            &[]
-        };
+        let visible_candles = self.visible_candles();
 
         if visible_candles.is_empty() {
             let canvas = Canvas::default()
@@ -82,145 +276,286 @@ impl CandlestickChart {
             return;
         }
 
-        let (min_price, max_price) = self.calculate_price_range(visible_candles);
-        let (y_min, y_max) = self.calculate_y_bounds(min_price, max_price);
+        let (y_min, y_max) = self.y_bounds(visible_candles);
+        let x_max = (visible_candles.len() + 2) as f64;
 
         let canvas = Canvas::default()
             .block(chart_block)
             .paint(|ctx| {
-                self.draw_price_labels(ctx, visible_candles.len() as f64, y_min, y_max);
-                self.draw_candlesticks(ctx, visible_candles);
-                self.draw_ma50_line(ctx);
-                self.draw_indicators(ctx, visible_candles, y_max);
+                chart::draw_price_labels(ctx, visible_candles.len() as f64, y_min, y_max);
+                if !self.raster {
+                    chart::draw_candlesticks(ctx, visible_candles, |candle| {
+                        self.candle_color(candle)
+                    });
+                    if self.show_ma {
+                        self.draw_ma50_line(ctx);
+                    }
+                    self.draw_time_lines(ctx, visible_candles, [0.0, x_max], [y_min, y_max]);
+                }
+                self.draw_levels(ctx, x_max, y_min, y_max);
+                self.draw_markers(ctx, visible_candles, x_max, y_min, y_max);
+                self.draw_comparison(ctx, visible_candles, y_min, y_max);
+                self.draw_indicators(ctx, visible_candles, y_min, y_max);
             })
-            .x_bounds([0.0, (visible_candles.len() + 2) as f64])
+            .x_bounds([0.0, x_max])
             .y_bounds([y_min, y_max]);
 
         frame.render_widget(canvas, area);
     }
 
-    fn calculate_price_range(&self, candles: &[Candle]) -> (f64, f64) {
-        let min_price = candles
-            .iter()
-            .map(|c| c.low)
-            .fold(f64::INFINITY, |a, b| a.min(b));
-        let max_price = candles
-            .iter()
-            .map(|c| c.high)
-            .fold(f64::NEG_INFINITY, |a, b| a.max(b));
-        (min_price, max_price)
+    /// X extent of the plot itself; everything right of it is the price label column.
+    fn plot_x_max(candles: &[Candle]) -> f64 {
+        candles.len() as f64 + 0.5
     }
 
-    fn calculate_y_bounds(&self, min_price: f64, max_price: f64) -> (f64, f64) {
-        let price_range = max_price - min_price;
-        let padding = price_range * 0.1;
-        (min_price - padding, max_price + padding)
+    /// Terminal cells covered by the plot (inside the border, left of the price labels).
+    pub fn plot_area(&self, area: Rect) -> Option<Rect> {
+        let candles = self.visible_candles();
+        if candles.is_empty() {
+            return None;
+        }
+        let inner = area.inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let x_max = (candles.len() + 2) as f64;
+        let width = (inner.width as f64 * Self::plot_x_max(candles) / x_max) as u16;
+        if width == 0 || inner.height == 0 {
+            return None;
+        }
+        Some(Rect::new(inner.x, inner.y, width, inner.height))
     }
 
-    fn draw_price_labels(&self, ctx: &mut Context, x: f64, y_min: f64, y_max: f64) {
-        let num_labels = 5;
-        for i in 0..=num_labels {
-            let price = y_min + (y_max - y_min) * (i as f64 / num_labels as f64);
-            ctx.print(x + 0.5, price, format!("{:.2}", price));
+    /// Renders candles, MA and overlay lines into a `width` x `height` RGB buffer
+    /// with the same coordinate system as the canvas.
+    pub fn render_rgb(&self, width: u32, height: u32) -> Option<Vec<u8>> {
+        let candles = self.visible_candles();
+        if candles.is_empty() || width == 0 || height == 0 {
+            return None;
+        }
+        let (y_min, y_max) = self.y_bounds(candles);
+        let x_max = Self::plot_x_max(candles);
+
+        let mut buffer = vec![0u8; (width * height * 3) as usize];
+        {
+            let root = BitMapBackend::with_buffer(&mut buffer, (width, height)).into_drawing_area();
+            root.fill(&BLACK).ok()?;
+            let mut plot = ChartBuilder::on(&root)
+                .build_cartesian_2d(0.0..x_max, y_min..y_max)
+                .ok()?;
+
+            let candle_width = (width as f64 / x_max * chart::CANDLE_WIDTH).max(1.0) as u32;
+            plot.draw_series(candles.iter().enumerate().map(|(i, candle)| {
+                let color = to_rgb(self.candle_color(candle));
+                CandleStick::new(
+                    i as f64 + 0.4,
+                    candle.open,
+                    candle.high,
+                    candle.low,
+                    candle.close,
+                    color.filled(),
+                    color.filled(),
+                    candle_width,
+                )
+            }))
+            .ok()?;
+
+            if self.show_ma {
+                plot.draw_series(LineSeries::new(
+                    self.visible_ma50()
+                        .enumerate()
+                        .map(|(i, ma)| (i as f64, *ma)),
+                    &to_rgb(self.colors.ma),
+                ))
+                .ok()?;
+            }
+
+            for level in self
+                .levels
+                .iter()
+                .filter(|l| l.price >= y_min && l.price <= y_max)
+            {
+                let color = to_rgb(level.color);
+                let dash = if level.dashed { 0.6 } else { x_max };
+                let mut x = 0.0;
+                while x < x_max {
+                    plot.draw_series(std::iter::once(PathElement::new(
+                        vec![(x, level.price), ((x + dash).min(x_max), level.price)],
+                        color,
+                    )))
+                    .ok()?;
+                    x += dash * 2.0;
+                }
+            }
+
+            for line in &self.time_lines {
+                let (Some(x1), Some(x2)) = (
+                    self.time_to_x(candles, line.start.0),
+                    self.time_to_x(candles, line.end.0),
+                ) else {
+                    continue;
+                };
+                let (y1, y2) = (line.start.1, line.end.1);
+                let x_end = if line.extend_right && x2 != x1 {
+                    x_max
+                } else {
+                    x2
+                };
+                let slope = if x2 != x1 { (y2 - y1) / (x2 - x1) } else { 0.0 };
+                let end = (x_end, y1 + slope * (x_end - x1));
+                if let Some((a, b)) = clip_line((x1, y1), end, [0.0, x_max], [y_min, y_max]) {
+                    plot.draw_series(std::iter::once(PathElement::new(
+                        vec![a, b],
+                        to_rgb(line.color),
+                    )))
+                    .ok()?;
+                }
+            }
+
+            plot.draw_series(LineSeries::new(
+                self.comparison_points(candles),
+                &to_rgb(Color::Blue),
+            ))
+            .ok()?;
+
+            root.present().ok()?;
         }
+        Some(buffer)
     }
 
-    fn draw_candlesticks(&self, ctx: &mut Context, candles: &[Candle]) {
-        let candle_width = 0.8;
-        for (i, candle) in candles.iter().enumerate() {
-            let x = i as f64;
-            let color = if candle.is_bullish() {
-                Color::Green
-            } else {
-                Color::Red
-            };
+    fn visible_candles(&self) -> &[Candle] {
+        &self.candles[self.candles.len().saturating_sub(self.zoom)..]
+    }
 
-            self.draw_candle_wick(ctx, x, candle_width, candle, color);
-            self.draw_candle_body(ctx, x, candle_width, candle, color);
+    /// Maps a terminal column inside the chart `area` back to the candle drawn there.
+    pub fn candle_at(&self, area: Rect, column: u16) -> Option<&Candle> {
+        let inner = area.inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        if column < inner.x || column >= inner.x + inner.width || inner.width < 2 {
+            return None;
         }
+
+        let candles = self.visible_candles();
+        let x_max = (candles.len() + 2) as f64;
+        let x = (column - inner.x) as f64 * x_max / (inner.width - 1) as f64;
+        candles.get(x.floor() as usize)
     }
 
-    fn draw_candle_wick(
-        &self,
-        ctx: &mut Context,
-        x: f64,
-        width: f64,
-        candle: &Candle,
-        color: Color,
-    ) {
-        ctx.draw(&Line {
-            x1: x + width / 2.0,
-            y1: candle.low,
-            x2: x + width / 2.0,
-            y2: candle.high,
-            color,
+    /// Maps a terminal row inside the chart `area` back to a price on the y axis.
+    pub fn price_at(&self, area: Rect, row: u16) -> Option<f64> {
+        let inner = area.inner(&Margin {
+            horizontal: 1,
+            vertical: 1,
         });
+        if row < inner.y || row >= inner.y + inner.height || inner.height < 2 {
+            return None;
+        }
+
+        let candles = self.visible_candles();
+        if candles.is_empty() {
+            return None;
+        }
+        let (y_min, y_max) = self.y_bounds(candles);
+        let fraction = (row - inner.y) as f64 / (inner.height - 1) as f64;
+        Some(y_max - fraction * (y_max - y_min))
     }
 
-    fn draw_candle_body(
-        &self,
-        ctx: &mut Context,
-        x: f64,
-        width: f64,
-        candle: &Candle,
-        color: Color,
-    ) {
-        let (body_top, body_bottom) = if candle.is_bullish() {
-            (candle.close, candle.open)
-        } else {
-            (candle.open, candle.close)
+    /// Draws a floating OHLCV box for the candle under the cursor.
+    pub fn draw_tooltip(&self, frame: &mut Frame, area: Rect, column: u16, row: u16) {
+        if row <= area.y || row + 1 >= area.y + area.height {
+            return;
+        }
+        let Some(candle) = self.candle_at(area, column) else {
+            return;
         };
 
-        if (body_top - body_bottom).abs() < 0.001 {
-            self.draw_flat_candle(ctx, x, width, body_top, color);
+        let time = Utc
+            .timestamp_millis_opt(candle.start)
+            .single()
+            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let change_color = self.candle_color(candle);
+        let lines = vec![
+            TextLine::from(time),
+            TextLine::from(format!("O: {:.2}", candle.open)),
+            TextLine::from(format!("H: {:.2}", candle.high)),
+            TextLine::from(format!("L: {:.2}", candle.low)),
+            TextLine::from(format!("C: {:.2}", candle.close)),
+            TextLine::from(format!("V: {:.3}", candle.volume)),
+            TextLine::from(Span::styled(
+                format!("{:+.2}%", candle.change_percent()),
+                Style::default().fg(change_color),
+            )),
+        ];
+
+        let screen = frame.size();
+        let width = 20.min(screen.width);
+        let height = (lines.len() as u16 + 2).min(screen.height);
+        // Prefer below-right of the cursor, flipping when it would leave the screen
+        let x = if column + 2 + width <= screen.width {
+            column + 2
         } else {
-            self.draw_filled_candle(ctx, x, width, body_top, body_bottom, color);
-        }
+            column.saturating_sub(width + 1)
+        };
+        let y = if row + 1 + height <= screen.height {
+            row + 1
+        } else {
+            row.saturating_sub(height)
+        };
+        let tooltip_area = Rect::new(x, y, width, height);
+
+        frame.render_widget(Clear, tooltip_area);
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL)),
+            tooltip_area,
+        );
     }
 
-    fn draw_flat_candle(&self, ctx: &mut Context, x: f64, width: f64, price: f64, color: Color) {
-        ctx.draw(&Line {
-            x1: x,
-            y1: price,
-            x2: x + width,
-            y2: price,
-            color,
-        });
+    fn y_bounds(&self, candles: &[Candle]) -> (f64, f64) {
+        let (mut min_price, mut max_price) = chart::price_range(candles);
+        for (_, y) in self.comparison_points(candles) {
+            min_price = min_price.min(y);
+            max_price = max_price.max(y);
+        }
+        chart::y_bounds(min_price, max_price)
     }
 
-    fn draw_filled_candle(
-        &self,
-        ctx: &mut Context,
-        x: f64,
-        width: f64,
-        top: f64,
-        bottom: f64,
-        color: Color,
-    ) {
-        let points = vec![(x, bottom), (x + width, bottom), (x + width, top), (x, top)];
-        ctx.draw(&Points {
-            coords: &points,
-            color,
-        });
+    /// Comparison closes rebased onto the main price axis, so both series start
+    /// from the same point at the left edge and move by their own percent change.
+    fn comparison_points(&self, candles: &[Candle]) -> Vec<(f64, f64)> {
+        let (Some(comparison), Some(first)) = (self.comparison.as_ref(), candles.first()) else {
+            return Vec::new();
+        };
+        let Some(base) = candles
+            .iter()
+            .find_map(|candle| comparison.closes.get(&candle.start))
+        else {
+            return Vec::new();
+        };
 
-        for y in (((bottom * 100.0) as i32)..=((top * 100.0) as i32)).step_by(1) {
-            let y = y as f64 / 100.0;
-            ctx.draw(&Line {
-                x1: x,
-                y1: y,
-                x2: x + width,
-                y2: y,
-                color,
-            });
-        }
+        candles
+            .iter()
+            .enumerate()
+            .filter_map(|(i, candle)| {
+                let close = comparison.closes.get(&candle.start)?;
+                Some((i as f64 + 0.4, first.close * close / base))
+            })
+            .collect()
+    }
+
+    /// MA values lined up with the visible candles.
+    fn visible_ma50(&self) -> impl Iterator<Item = &f64> {
+        let skip = self.ma50_values.len().saturating_sub(self.zoom);
+        self.ma50_values.iter().skip(skip)
     }
 
     fn draw_ma50_line(&self, ctx: &mut Context) {
-        let ma50_color = Color::Yellow;
-        for i in 1..self.ma50_values.len() {
-            if let (Some(prev_ma), Some(curr_ma)) =
-                (self.ma50_values.get(i - 1), self.ma50_values.get(i))
-            {
+        let ma50_color = self.colors.ma;
+        let values: Vec<f64> = self.visible_ma50().copied().collect();
+        for i in 1..values.len() {
+            if let (Some(prev_ma), Some(curr_ma)) = (values.get(i - 1), values.get(i)) {
                 ctx.draw(&Line {
                     x1: (i - 1) as f64,
                     y1: *prev_ma,
@@ -232,16 +567,224 @@ impl CandlestickChart {
         }
     }
 
-    fn draw_indicators(&self, ctx: &mut Context, candles: &[Candle], y_max: f64) {
+    fn draw_levels(&self, ctx: &mut Context, x_max: f64, y_min: f64, y_max: f64) {
+        for level in &self.levels {
+            if level.price < y_min || level.price > y_max {
+                continue;
+            }
+            if self.raster {
+                // Only the label; the line is part of the image
+            } else if level.dashed {
+                let dash = 0.6;
+                let mut x = 0.0;
+                while x < x_max {
+                    ctx.draw(&Line {
+                        x1: x,
+                        y1: level.price,
+                        x2: (x + dash).min(x_max),
+                        y2: level.price,
+                        color: level.color,
+                    });
+                    x += dash * 2.0;
+                }
+            } else {
+                ctx.draw(&Line {
+                    x1: 0.0,
+                    y1: level.price,
+                    x2: x_max,
+                    y2: level.price,
+                    color: level.color,
+                });
+            }
+            ctx.print(
+                0.0,
+                level.price,
+                Span::styled(level.label.clone(), Style::default().fg(level.color)),
+            );
+        }
+    }
+
+    /// X coordinate of the middle of the candle starting at `time`, extrapolated
+    /// from the candle spacing when it lies outside the visible window.
+    fn time_to_x(&self, candles: &[Candle], time: i64) -> Option<f64> {
+        let first = candles.first()?;
+        let interval = match candles {
+            [.., prev, last] => last.start - prev.start,
+            _ => return None,
+        };
+        if interval <= 0 {
+            return None;
+        }
+        Some((time - first.start) as f64 / interval as f64 + 0.4)
+    }
+
+    fn draw_time_lines(&self, ctx: &mut Context, candles: &[Candle], x: [f64; 2], y: [f64; 2]) {
+        for line in &self.time_lines {
+            let (Some(x1), Some(x2)) = (
+                self.time_to_x(candles, line.start.0),
+                self.time_to_x(candles, line.end.0),
+            ) else {
+                continue;
+            };
+            let (y1, y2) = (line.start.1, line.end.1);
+            let x_end = if line.extend_right && x2 != x1 {
+                x[1]
+            } else {
+                x2
+            };
+            let slope = if x2 != x1 { (y2 - y1) / (x2 - x1) } else { 0.0 };
+            let end = (x_end, y1 + slope * (x_end - x1));
+
+            if let Some(((cx1, cy1), (cx2, cy2))) = clip_line((x1, y1), end, x, y) {
+                ctx.draw(&Line {
+                    x1: cx1,
+                    y1: cy1,
+                    x2: cx2,
+                    y2: cy2,
+                    color: line.color,
+                });
+            }
+        }
+    }
+
+    fn draw_markers(
+        &self,
+        ctx: &mut Context,
+        candles: &[Candle],
+        x_max: f64,
+        y_min: f64,
+        y_max: f64,
+    ) {
+        for marker in &self.markers {
+            let Some(x) = self.time_to_x(candles, marker.time) else {
+                continue;
+            };
+            if x < 0.0 || x > x_max || marker.price < y_min || marker.price > y_max {
+                continue;
+            }
+            ctx.print(
+                x,
+                marker.price,
+                Span::styled(
+                    format!("◆ {}", marker.text),
+                    Style::default().fg(marker.color),
+                ),
+            );
+        }
+    }
+
+    fn draw_comparison(&self, ctx: &mut Context, candles: &[Candle], y_min: f64, y_max: f64) {
+        let Some(comparison) = self.comparison.as_ref() else {
+            return;
+        };
+        let color = Color::Blue;
+        let points = self.comparison_points(candles);
+        for pair in points.windows(2).filter(|_| !self.raster) {
+            ctx.draw(&Line {
+                x1: pair[0].0,
+                y1: pair[0].1,
+                x2: pair[1].0,
+                y2: pair[1].1,
+                color,
+            });
+        }
+
+        let (Some(first), Some(last), Some(&(_, compared))) =
+            (candles.first(), candles.last(), points.last())
+        else {
+            return;
+        };
+        let main_change = (last.close / first.close - 1.0) * 100.0;
+        let compared_change = (compared / first.close - 1.0) * 100.0;
+        ctx.print(
+            0.0,
+            y_max - (y_max - y_min) * 0.05,
+            Span::styled(
+                format!(
+                    "vs {} {:+.2}% (main {:+.2}%)",
+                    comparison.symbol, compared_change, main_change
+                ),
+                Style::default().fg(color),
+            ),
+        );
+    }
+
+    fn draw_indicators(&self, ctx: &mut Context, candles: &[Candle], y_min: f64, y_max: f64) {
+        let precision = price_precision(y_max - y_min);
         if let Some(last_candle) = candles.last() {
             ctx.print(
                 0.0,
                 y_max * 0.95,
-                format!("Current: {:.2}", last_candle.close),
+                format!("Current: {:.*}", precision, last_candle.close),
             );
-            if let Some(last_ma) = self.ma50_values.back() {
-                ctx.print(0.0, y_max * 0.90, format!("MA50: {:.2}", last_ma));
+            if let Some(last_ma) = self.ma50_values.back().filter(|_| self.show_ma) {
+                ctx.print(
+                    0.0,
+                    y_max * 0.90,
+                    format!("MA{}: {:.*}", self.ma_window, precision, last_ma),
+                );
             }
         }
     }
 }
+
+/// Approximate xterm RGB values for the named terminal colors used on the chart.
+fn to_rgb(color: Color) -> RGBColor {
+    match color {
+        Color::Rgb(r, g, b) => RGBColor(r, g, b),
+        Color::Red => RGBColor(205, 49, 49),
+        Color::Green => RGBColor(13, 188, 121),
+        Color::Yellow => RGBColor(229, 229, 16),
+        Color::Blue => RGBColor(36, 114, 200),
+        Color::Magenta => RGBColor(188, 63, 188),
+        Color::Cyan => RGBColor(17, 168, 205),
+        Color::Gray => RGBColor(150, 150, 150),
+        Color::DarkGray => RGBColor(102, 102, 102),
+        Color::LightRed => RGBColor(241, 76, 76),
+        Color::LightGreen => RGBColor(35, 209, 139),
+        Color::LightYellow => RGBColor(245, 245, 67),
+        Color::LightBlue => RGBColor(59, 142, 234),
+        Color::LightMagenta => RGBColor(214, 112, 214),
+        Color::LightCyan => RGBColor(41, 184, 219),
+        _ => RGBColor(229, 229, 229),
+    }
+}
+
+/// Clips a segment to the `x`/`y` bounds (Liang–Barsky), since the canvas drops
+/// lines with any endpoint outside its bounds.
+fn clip_line(
+    p1: (f64, f64),
+    p2: (f64, f64),
+    x: [f64; 2],
+    y: [f64; 2],
+) -> Option<((f64, f64), (f64, f64))> {
+    let (dx, dy) = (p2.0 - p1.0, p2.1 - p1.1);
+    let mut t0: f64 = 0.0;
+    let mut t1: f64 = 1.0;
+    for (p, q) in [
+        (-dx, p1.0 - x[0]),
+        (dx, x[1] - p1.0),
+        (-dy, p1.1 - y[0]),
+        (dy, y[1] - p1.1),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    Some((
+        (p1.0 + t0 * dx, p1.1 + t0 * dy),
+        (p1.0 + t1 * dx, p1.1 + t1 * dy),
+    ))
+}
@@ -0,0 +1,36 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// Centered yes/no popup over everything else on screen.
+pub fn draw_confirm(frame: &mut Frame, title: &str, lines: Vec<Line>) {
+    let screen = frame.size();
+    let width = 56.min(screen.width);
+    let height = (lines.len() as u16 + 4).min(screen.height);
+    let area = Rect::new(
+        screen.width.saturating_sub(width) / 2,
+        screen.height.saturating_sub(height) / 2,
+        width,
+        height,
+    );
+    let mut lines = lines;
+    lines.push(Line::from(""));
+    lines.push(Line::styled(
+        "y send, any other key discards",
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(title.to_string()),
+        ),
+        area,
+    );
+}
@@ -0,0 +1,118 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    symbols,
+    text::Span,
+    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType},
+    Frame,
+};
+use std::collections::VecDeque;
+
+/// Account equity sampled over time, with drawdown and return stats.
+pub struct EquityCurve {
+    /// `(timestamp ms, equity)`, oldest first.
+    points: VecDeque<(i64, f64)>,
+    capacity: usize,
+    /// Samples closer together than this replace the previous one.
+    resolution_ms: i64,
+    peak: f64,
+    max_drawdown: f64,
+}
+
+impl EquityCurve {
+    pub fn new(capacity: usize, resolution_ms: i64) -> Self {
+        Self {
+            points: VecDeque::with_capacity(capacity),
+            capacity,
+            resolution_ms,
+            peak: 0.0,
+            max_drawdown: 0.0,
+        }
+    }
+
+    pub fn record(&mut self, timestamp: i64, equity: f64) {
+        if !equity.is_finite() || equity <= 0.0 {
+            return;
+        }
+        match self.points.back_mut() {
+            Some(last) if timestamp - last.0 < self.resolution_ms => last.1 = equity,
+            _ => {
+                self.points.push_back((timestamp, equity));
+                if self.points.len() > self.capacity {
+                    self.points.pop_front();
+                }
+            }
+        }
+        self.peak = self.peak.max(equity);
+        self.max_drawdown = self.max_drawdown.max(self.drawdown());
+    }
+
+    pub fn latest(&self) -> Option<f64> {
+        self.points.back().map(|(_, equity)| *equity)
+    }
+
+    /// Change since the first sample, in percent.
+    pub fn return_percent(&self) -> f64 {
+        match (self.points.front(), self.points.back()) {
+            (Some((_, first)), Some((_, last))) => (last - first) / first * 100.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Current distance below the running peak, in percent.
+    pub fn drawdown(&self) -> f64 {
+        match self.latest() {
+            Some(equity) if self.peak > 0.0 => (self.peak - equity) / self.peak * 100.0,
+            _ => 0.0,
+        }
+    }
+
+    pub fn max_drawdown(&self) -> f64 {
+        self.max_drawdown
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let title = format!(
+            "Equity {:.2}  ret {:+.2}%  dd {:.2}%  max dd {:.2}%",
+            self.latest().unwrap_or_default(),
+            self.return_percent(),
+            self.drawdown(),
+            self.max_drawdown
+        );
+        let block = Block::default().borders(Borders::ALL).title(title);
+        let (Some(first), Some(last)) = (self.points.front(), self.points.back()) else {
+            frame.render_widget(block, area);
+            return;
+        };
+
+        let data: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .map(|(t, equity)| ((t - first.0) as f64, *equity))
+            .collect();
+        let (min, max) = data.iter().fold((f64::MAX, f64::MIN), |(lo, hi), (_, e)| {
+            (lo.min(*e), hi.max(*e))
+        });
+        // Keep a flat curve off the frame edges
+        let pad = ((max - min) * 0.05).max(max.abs() * 0.0005);
+        let color = if last.1 >= first.1 {
+            Color::Green
+        } else {
+            Color::Red
+        };
+
+        let dataset = Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(color))
+            .data(&data);
+        let chart = Chart::new(vec![dataset])
+            .block(block)
+            .x_axis(Axis::default().bounds([0.0, ((last.0 - first.0) as f64).max(1.0)]))
+            .y_axis(Axis::default().bounds([min - pad, max + pad]).labels(vec![
+                Span::raw(format!("{:.0}", min)),
+                Span::raw(format!("{:.0}", max)),
+            ]));
+        frame.render_widget(chart, area);
+    }
+}
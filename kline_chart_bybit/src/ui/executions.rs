@@ -0,0 +1,109 @@
+use crate::models::{Execution, ExecutionData, Side};
+use chrono::{TimeZone, Utc};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+use std::collections::VecDeque;
+
+/// Our own fills for one symbol, newest first, from the private `execution` topic.
+pub struct ExecutionsPanel {
+    symbol: String,
+    executions: VecDeque<Execution>,
+    capacity: usize,
+    /// Rows scrolled past the newest fill.
+    scroll: usize,
+}
+
+impl ExecutionsPanel {
+    pub fn new(symbol: &str, capacity: usize) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            executions: VecDeque::with_capacity(capacity),
+            capacity,
+            scroll: 0,
+        }
+    }
+
+    pub fn update_from_execution(&mut self, data: &ExecutionData) {
+        if let Some(execution) = Execution::from_execution_data(data) {
+            self.push(execution);
+        }
+    }
+
+    pub fn push(&mut self, execution: Execution) {
+        if execution.symbol != self.symbol {
+            return;
+        }
+        self.executions.push_front(execution);
+        self.executions.truncate(self.capacity);
+        // Keep the rows being read in place while new fills arrive on top
+        if self.scroll > 0 {
+            self.scroll = (self.scroll + 1).min(self.executions.len() - 1);
+        }
+    }
+
+    pub fn scroll_up(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
+    }
+
+    pub fn scroll_down(&mut self, rows: usize) {
+        self.scroll = (self.scroll + rows).min(self.executions.len().saturating_sub(1));
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let title = if self.scroll > 0 {
+            format!("Fills {} (+{})", self.symbol, self.scroll)
+        } else {
+            format!("Fills {}", self.symbol)
+        };
+        let block = Block::default().borders(Borders::ALL).title(title);
+
+        let header = Row::new(vec!["Time", "Side", "Price", "Qty", "Fee"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let rows_fit = area.height.saturating_sub(3) as usize;
+        let rows = self
+            .executions
+            .iter()
+            .skip(self.scroll)
+            .take(rows_fit)
+            .map(|execution| {
+                let (side, color) = match execution.side {
+                    Side::Buy => ("BUY", Color::Green),
+                    Side::Sell => ("SELL", Color::Red),
+                };
+                Row::new(vec![
+                    Cell::from(format_time(execution.timestamp)),
+                    Cell::from(side),
+                    Cell::from(format!("{:.2}", execution.price)),
+                    Cell::from(format!("{}", execution.qty)),
+                    Cell::from(format!("{:.4}", execution.fee)),
+                ])
+                .style(Style::default().fg(color))
+            });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(14),
+                Constraint::Length(4),
+                Constraint::Length(10),
+                Constraint::Length(8),
+                Constraint::Length(8),
+            ],
+        )
+        .header(header)
+        .block(block);
+        frame.render_widget(table, area);
+    }
+}
+
+fn format_time(timestamp_ms: i64) -> String {
+    Utc.timestamp_millis_opt(timestamp_ms)
+        .single()
+        .map(|t| t.format("%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,258 @@
+use super::chart::CandlestickChart;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use clap::ValueEnum;
+use crossterm::{
+    cursor::{MoveTo, RestorePosition, SavePosition},
+    queue,
+    terminal::window_size,
+};
+use flate2::{write::ZlibEncoder, Compression};
+use ratatui::layout::Rect;
+use std::{
+    collections::HashMap,
+    env,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+/// Minimum time between two raster uploads, to keep terminal traffic reasonable.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+/// Cell size assumed when the terminal does not report its pixel dimensions.
+const FALLBACK_CELL_SIZE: (u32, u32) = (10, 20);
+/// Largest image edge we are willing to render.
+const MAX_IMAGE_EDGE: u32 = 2400;
+/// Kitty transmits base64 payloads in chunks of at most this many bytes.
+const KITTY_CHUNK_SIZE: usize = 4096;
+const KITTY_IMAGE_ID: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphicsMode {
+    /// Use an image protocol if the terminal looks like it supports one
+    Auto,
+    Kitty,
+    Sixel,
+    /// Always use the braille canvas
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Sixel,
+}
+
+impl GraphicsMode {
+    pub fn protocol(self) -> Option<GraphicsProtocol> {
+        match self {
+            GraphicsMode::Auto => detect_protocol(),
+            GraphicsMode::Kitty => Some(GraphicsProtocol::Kitty),
+            GraphicsMode::Sixel => Some(GraphicsProtocol::Sixel),
+            GraphicsMode::Off => None,
+        }
+    }
+}
+
+/// Guesses the image protocol from the environment the terminal exports.
+fn detect_protocol() -> Option<GraphicsProtocol> {
+    let term = env::var("TERM").unwrap_or_default();
+    let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
+
+    if env::var_os("KITTY_WINDOW_ID").is_some()
+        || term == "xterm-kitty"
+        || term == "xterm-ghostty"
+        || matches!(term_program.as_str(), "WezTerm" | "ghostty")
+    {
+        return Some(GraphicsProtocol::Kitty);
+    }
+    if term.contains("sixel") || term.starts_with("foot") || term_program == "mlterm" {
+        return Some(GraphicsProtocol::Sixel);
+    }
+    None
+}
+
+/// Uploads the chart as a raster image on top of its terminal area.
+pub struct GraphicsRenderer {
+    protocol: GraphicsProtocol,
+    last_upload: Option<Instant>,
+    last_area: Rect,
+}
+
+impl GraphicsRenderer {
+    pub fn new(protocol: GraphicsProtocol) -> Self {
+        Self {
+            protocol,
+            last_upload: None,
+            last_area: Rect::default(),
+        }
+    }
+
+    pub fn present<W: Write>(
+        &mut self,
+        out: &mut W,
+        chart: &CandlestickChart,
+        area: Rect,
+    ) -> io::Result<()> {
+        let due = self
+            .last_upload
+            .is_none_or(|t| t.elapsed() >= REFRESH_INTERVAL);
+        if !due && area == self.last_area {
+            return Ok(());
+        }
+
+        let Some(plot) = chart.plot_area(area) else {
+            return Ok(());
+        };
+        let (cell_width, cell_height) = cell_size();
+        let width = (plot.width as u32 * cell_width).min(MAX_IMAGE_EDGE);
+        let height = (plot.height as u32 * cell_height).min(MAX_IMAGE_EDGE);
+        let Some(rgb) = chart.render_rgb(width, height) else {
+            return Ok(());
+        };
+
+        let payload = match self.protocol {
+            GraphicsProtocol::Kitty => encode_kitty(&rgb, width, height, plot)?,
+            GraphicsProtocol::Sixel => encode_sixel(&rgb, width, height),
+        };
+
+        queue!(out, SavePosition, MoveTo(plot.x, plot.y))?;
+        out.write_all(payload.as_bytes())?;
+        queue!(out, RestorePosition)?;
+        out.flush()?;
+
+        self.last_upload = Some(Instant::now());
+        self.last_area = area;
+        Ok(())
+    }
+
+    /// Removes any image left on screen.
+    pub fn clear<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        if self.protocol == GraphicsProtocol::Kitty {
+            write!(out, "\x1b_Ga=d,d=A,q=2\x1b\\")?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+fn cell_size() -> (u32, u32) {
+    match window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => (
+            (size.width / size.columns) as u32,
+            (size.height / size.rows) as u32,
+        ),
+        _ => FALLBACK_CELL_SIZE,
+    }
+}
+
+/// Kitty graphics protocol: zlib-compressed RGB, scaled to the plot cells and
+/// placed below the text layer (`z=-1`) so labels stay readable on top.
+fn encode_kitty(rgb: &[u8], width: u32, height: u32, plot: Rect) -> io::Result<String> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::fast());
+    encoder.write_all(rgb)?;
+    let data = STANDARD.encode(encoder.finish()?);
+
+    let mut out = format!("\x1b_Ga=d,d=i,i={KITTY_IMAGE_ID},q=2\x1b\\");
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        let chunk = std::str::from_utf8(chunk).unwrap_or_default();
+        if i == 0 {
+            out.push_str(&format!(
+                "\x1b_Ga=T,f=24,o=z,s={width},v={height},c={},r={},i={KITTY_IMAGE_ID},z=-1,C=1,q=2,m={more};{chunk}\x1b\\",
+                plot.width, plot.height
+            ));
+        } else {
+            out.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+        }
+    }
+    Ok(out)
+}
+
+/// DEC sixel encoding with a palette built from the (few) colors the chart uses.
+fn encode_sixel(rgb: &[u8], width: u32, height: u32) -> String {
+    let (width, height) = (width as usize, height as usize);
+    let mut palette: Vec<[u8; 3]> = Vec::new();
+    let mut lookup: HashMap<[u8; 3], usize> = HashMap::new();
+    let indices: Vec<usize> = rgb
+        .chunks_exact(3)
+        .map(|px| {
+            let color = [px[0], px[1], px[2]];
+            if let Some(&index) = lookup.get(&color) {
+                return index;
+            }
+            let index = if palette.len() < 256 {
+                palette.push(color);
+                palette.len() - 1
+            } else {
+                nearest_color(&palette, color)
+            };
+            lookup.insert(color, index);
+            index
+        })
+        .collect();
+
+    let mut out = format!("\x1bPq\"1;1;{width};{height}");
+    for (i, [r, g, b]) in palette.iter().enumerate() {
+        out.push_str(&format!(
+            "#{};2;{};{};{}",
+            i,
+            *r as u32 * 100 / 255,
+            *g as u32 * 100 / 255,
+            *b as u32 * 100 / 255
+        ));
+    }
+
+    for band in (0..height).step_by(6) {
+        let band_rows = (height - band).min(6);
+        for color in 0..palette.len() {
+            let sixels: Vec<u8> = (0..width)
+                .map(|x| {
+                    (0..band_rows).fold(0u8, |bits, dy| {
+                        if indices[(band + dy) * width + x] == color {
+                            bits | (1 << dy)
+                        } else {
+                            bits
+                        }
+                    })
+                })
+                .collect();
+            if sixels.iter().all(|&bits| bits == 0) {
+                continue;
+            }
+            out.push_str(&format!("#{color}"));
+            push_sixel_runs(&mut out, &sixels);
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_sixel_runs(out: &mut String, sixels: &[u8]) {
+    let mut i = 0;
+    while i < sixels.len() {
+        let bits = sixels[i];
+        let run = sixels[i..].iter().take_while(|&&b| b == bits).count();
+        let ch = (63 + bits) as char;
+        if run > 3 {
+            out.push_str(&format!("!{run}{ch}"));
+        } else {
+            (0..run).for_each(|_| out.push(ch));
+        }
+        i += run;
+    }
+}
+
+fn nearest_color(palette: &[[u8; 3]], color: [u8; 3]) -> usize {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, p)| {
+            p.iter()
+                .zip(color.iter())
+                .map(|(a, b)| (*a as i32 - *b as i32).pow(2))
+                .sum::<i32>()
+        })
+        .map_or(0, |(i, _)| i)
+}
@@ -0,0 +1,99 @@
+use crate::models::{OrderBook, OrderbookResponse};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+
+/// Depth-of-market ladder: one row per tick, centered on the current price.
+pub struct DomLadder {
+    book: OrderBook,
+    last_price: Option<f64>,
+}
+
+impl DomLadder {
+    pub fn new(tick_size: f64) -> Self {
+        Self {
+            book: OrderBook::new(tick_size),
+            last_price: None,
+        }
+    }
+
+    pub fn update_from_orderbook(&mut self, response: &OrderbookResponse) {
+        self.book.apply(response);
+    }
+
+    pub fn set_last_price(&mut self, price: f64) {
+        self.last_price = Some(price);
+    }
+
+    fn center_tick(&self) -> Option<i64> {
+        if let Some(price) = self.last_price {
+            return Some(self.book.to_tick(price));
+        }
+        match (self.book.best_bid(), self.book.best_ask()) {
+            (Some(bid), Some(ask)) => Some((bid + ask) / 2),
+            (bid, ask) => bid.or(ask),
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title("DOM");
+
+        let header = Row::new(vec!["Bid", "Price", "Ask"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let rows_fit = area.height.saturating_sub(3) as i64;
+        let rows: Vec<Row> = match self.center_tick() {
+            Some(center) if !self.book.is_empty() => {
+                let top = center + rows_fit / 2;
+                (0..rows_fit)
+                    .map(|i| self.ladder_row(top - i, center))
+                    .collect()
+            }
+            _ => vec![Row::new(vec!["", "Waiting...", ""])],
+        };
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+        .header(header)
+        .block(block);
+
+        frame.render_widget(table, area);
+    }
+
+    fn ladder_row(&self, tick: i64, center: i64) -> Row<'static> {
+        let price = tick as f64 * self.book.tick_size();
+        let bid = self
+            .book
+            .bid_size(tick)
+            .map(|size| format!("{:.3}", size))
+            .unwrap_or_default();
+        let ask = self
+            .book
+            .ask_size(tick)
+            .map(|size| format!("{:.3}", size))
+            .unwrap_or_default();
+
+        let price_style = if tick == center {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+
+        Row::new(vec![
+            Cell::from(bid).style(Style::default().fg(Color::Green)),
+            Cell::from(format!("{:.2}", price)).style(price_style),
+            Cell::from(ask).style(Style::default().fg(Color::Red)),
+        ])
+    }
+}
@@ -0,0 +1,90 @@
+use crate::models::{MarginMode, Position};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// Highest leverage the dialog accepts; the exchange may cap it lower per symbol.
+const MAX_LEVERAGE: f64 = 100.0;
+
+/// Small popup for changing a symbol's leverage and margin mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeverageDialog {
+    pub symbol: String,
+    pub input: String,
+    pub margin_mode: MarginMode,
+    /// Mode the position was in when the dialog opened.
+    pub current_mode: MarginMode,
+    pub error: Option<String>,
+}
+
+impl LeverageDialog {
+    pub fn new(symbol: &str, position: Option<&Position>) -> Self {
+        let margin_mode = position.map(|p| p.margin_mode).unwrap_or_default();
+        Self {
+            symbol: symbol.to_string(),
+            input: position
+                .and_then(|p| p.leverage)
+                .map(|l| l.to_string())
+                .unwrap_or_default(),
+            margin_mode,
+            current_mode: margin_mode,
+            error: None,
+        }
+    }
+
+    pub fn leverage(&self) -> Result<f64, String> {
+        self.input
+            .parse::<f64>()
+            .ok()
+            .filter(|l| (1.0..=MAX_LEVERAGE).contains(l))
+            .ok_or_else(|| format!("leverage must be between 1 and {}", MAX_LEVERAGE))
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let screen = frame.size();
+        let width = 44.min(screen.width);
+        let height = 7.min(screen.height);
+        let area = Rect::new(
+            screen.width.saturating_sub(width) / 2,
+            screen.height.saturating_sub(height) / 2,
+            width,
+            height,
+        );
+        let selected = Style::default().add_modifier(Modifier::REVERSED);
+        let mode = |m: MarginMode| {
+            let style = if m == self.margin_mode {
+                selected
+            } else {
+                Style::default()
+            };
+            Span::styled(format!(" {} ", m.label()), style)
+        };
+        let lines = vec![
+            Line::from(format!("Leverage: {}_ x", self.input)),
+            Line::from(vec![
+                Span::raw("Margin:  "),
+                mode(MarginMode::Cross),
+                Span::raw(" "),
+                mode(MarginMode::Isolated),
+            ]),
+            Line::from(Span::styled(
+                self.error.clone().unwrap_or_default(),
+                Style::default().fg(Color::Red),
+            )),
+            Line::from("Tab margin mode, Enter apply, Esc close"),
+        ];
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Leverage {}", self.symbol)),
+            ),
+            area,
+        );
+    }
+}
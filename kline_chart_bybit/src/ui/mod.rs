@@ -1,2 +1,23 @@
 pub mod chart;
-pub use chart::CandlestickChart;
+pub mod confirm;
+pub mod equity;
+pub mod executions;
+pub mod graphics;
+pub mod ladder;
+pub mod leverage;
+pub mod orders;
+pub mod positions;
+pub mod trades;
+pub mod wallet;
+
+pub use chart::{CandlestickChart, ChartColors, Marker, PriceLevel, TimeLine};
+pub use confirm::draw_confirm;
+pub use equity::EquityCurve;
+pub use executions::ExecutionsPanel;
+pub use graphics::{GraphicsMode, GraphicsRenderer};
+pub use ladder::DomLadder;
+pub use leverage::LeverageDialog;
+pub use orders::OrdersPanel;
+pub use positions::PositionsPanel;
+pub use trades::TradesTape;
+pub use wallet::WalletPanel;
@@ -0,0 +1,123 @@
+use crate::models::{OpenOrder, OrderData, Side};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table, TableState},
+    Frame,
+};
+
+/// Our working orders for one symbol, from the private `order` topic, with a
+/// selection the amend and cancel keys act on.
+pub struct OrdersPanel {
+    symbol: String,
+    orders: Vec<OpenOrder>,
+    selected: usize,
+}
+
+impl OrdersPanel {
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            orders: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Adds or updates the order, or drops it once it is no longer open.
+    pub fn update_from_order(&mut self, data: &OrderData) {
+        if data.symbol != self.symbol {
+            return;
+        }
+        let index = self.orders.iter().position(|o| o.order_id == data.order_id);
+        match (OpenOrder::from_order_data(data), index) {
+            (Some(order), Some(index)) => self.orders[index] = order,
+            (Some(order), None) => self.orders.push(order),
+            (None, Some(index)) => {
+                self.orders.remove(index);
+            }
+            (None, None) => {}
+        }
+        self.clamp_selection();
+    }
+
+    /// Replaces every order, e.g. from a REST snapshot or the paper broker.
+    pub fn set_orders(&mut self, orders: Vec<OpenOrder>) {
+        self.orders = orders
+            .into_iter()
+            .filter(|o| o.symbol == self.symbol)
+            .collect();
+        self.clamp_selection();
+    }
+
+    pub fn selected(&self) -> Option<&OpenOrder> {
+        self.orders.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.orders.is_empty() {
+            self.selected = (self.selected + 1) % self.orders.len();
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        if !self.orders.is_empty() {
+            self.selected = (self.selected + self.orders.len() - 1) % self.orders.len();
+        }
+    }
+
+    fn clamp_selection(&mut self) {
+        self.selected = self.selected.min(self.orders.len().saturating_sub(1));
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title(format!(
+            "Orders {} ({}) ↑↓ select, m amend, Del cancel, K cancel all",
+            self.symbol,
+            self.orders.len()
+        ));
+        let header = Row::new(vec!["Side", "Type", "Price", "Trigger", "Qty", "Left"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let rows = self.orders.iter().map(|order| {
+            let (side, color) = match order.side {
+                Side::Buy => ("BUY", Color::Green),
+                Side::Sell => ("SELL", Color::Red),
+            };
+            let price = |p: Option<f64>| p.map(|p| format!("{:.2}", p)).unwrap_or_default();
+            let kind = if order.reduce_only {
+                format!("{} RO", order.order_type)
+            } else {
+                order.order_type.clone()
+            };
+            Row::new(vec![
+                Cell::from(side),
+                Cell::from(kind),
+                Cell::from(price(order.price)),
+                Cell::from(price(order.trigger_price)),
+                Cell::from(format!("{}", order.qty)),
+                Cell::from(format!("{}", order.leaves_qty)),
+            ])
+            .style(Style::default().fg(color))
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(4),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(8),
+                Constraint::Length(8),
+            ],
+        )
+        .header(header)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        let mut state = TableState::default();
+        if !self.orders.is_empty() {
+            state.select(Some(self.selected));
+        }
+        frame.render_stateful_widget(table, area, &mut state);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::models::{Position, PositionData, Side};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+use std::collections::BTreeMap;
+
+/// Open positions from the private `position` stream.
+pub struct PositionsPanel {
+    positions: BTreeMap<String, Position>,
+}
+
+impl PositionsPanel {
+    pub fn new() -> Self {
+        Self {
+            positions: BTreeMap::new(),
+        }
+    }
+
+    pub fn update_from_position(&mut self, data: &PositionData) {
+        let Some(position) = Position::from_position_data(data) else {
+            return;
+        };
+        if position.size == 0.0 {
+            self.positions.remove(&position.symbol);
+        } else {
+            self.positions.insert(position.symbol.clone(), position);
+        }
+    }
+
+    /// Replaces every position, for accounts that aren't fed by the exchange.
+    pub fn set_positions(&mut self, positions: Vec<Position>) {
+        self.positions = positions
+            .into_iter()
+            .map(|position| (position.symbol.clone(), position))
+            .collect();
+    }
+
+    /// Re-marks the position in `symbol` at a fresh trade price, so uPnL moves
+    /// between the exchange's (less frequent) position pushes.
+    pub fn mark(&mut self, symbol: &str, price: f64) {
+        if let Some(position) = self.positions.get_mut(symbol) {
+            position.mark_at(price);
+        }
+    }
+
+    pub fn position(&self, symbol: &str) -> Option<&Position> {
+        self.positions.get(symbol)
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title("Positions");
+        let header = Row::new(vec![
+            "Symbol", "Side", "Size", "Entry", "Mark", "uPnL", "Liq",
+        ])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let rows = self.positions.values().map(|position| {
+            let color = if position.unrealised_pnl >= 0.0 {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            let side = match position.side {
+                Side::Buy => "Long",
+                Side::Sell => "Short",
+            };
+            Row::new(vec![
+                Cell::from(position.symbol.clone()),
+                Cell::from(side),
+                Cell::from(format!("{}", position.size)),
+                Cell::from(format!("{:.2}", position.entry_price)),
+                Cell::from(format!("{:.2}", position.mark_price)),
+                Cell::from(format!("{:+.2}", position.unrealised_pnl)),
+                Cell::from(
+                    position
+                        .liq_price
+                        .map(|p| format!("{:.2}", p))
+                        .unwrap_or_else(|| String::from("-")),
+                ),
+            ])
+            .style(Style::default().fg(color))
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(10),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+        .header(header)
+        .block(block);
+        frame.render_widget(table, area);
+    }
+}
+
+impl Default for PositionsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
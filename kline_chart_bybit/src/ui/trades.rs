@@ -0,0 +1,96 @@
+use crate::{
+    constants::{BLOCK_TRADE_SIZE, LARGE_TRADE_SIZE},
+    models::{Side, Trade, TradeData},
+};
+use chrono::{TimeZone, Utc};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+use std::collections::VecDeque;
+
+pub struct TradesTape {
+    trades: VecDeque<Trade>,
+    capacity: usize,
+}
+
+impl TradesTape {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            trades: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn update_from_trade(&mut self, trade_data: &TradeData) {
+        if let Some(trade) = Trade::from_trade_data(trade_data) {
+            self.trades.push_front(trade);
+            self.trades.truncate(self.capacity);
+        }
+    }
+
+    /// Trades held, newest first.
+    pub fn trades(&self) -> impl Iterator<Item = &Trade> {
+        self.trades.iter()
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default().borders(Borders::ALL).title("Trades");
+
+        let header = Row::new(vec!["Time", "Price", "Size", "Side"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+
+        // Only as many rows as fit inside the borders and header
+        let rows_fit = area.height.saturating_sub(3) as usize;
+        let rows = self
+            .trades
+            .iter()
+            .take(rows_fit)
+            .map(|trade| self.trade_row(trade));
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(12),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(4),
+            ],
+        )
+        .header(header)
+        .block(block);
+
+        frame.render_widget(table, area);
+    }
+
+    fn trade_row(&self, trade: &Trade) -> Row<'static> {
+        let (side, color) = match trade.side {
+            Side::Buy => ("BUY", Color::Green),
+            Side::Sell => ("SELL", Color::Red),
+        };
+
+        let mut style = Style::default().fg(color);
+        if trade.block_trade || trade.size >= BLOCK_TRADE_SIZE {
+            style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        } else if trade.size >= LARGE_TRADE_SIZE {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+
+        Row::new(vec![
+            Cell::from(format_time(trade.timestamp)),
+            Cell::from(format!("{:.2}", trade.price)),
+            Cell::from(format!("{:.3}", trade.size)),
+            Cell::from(side),
+        ])
+        .style(style)
+    }
+}
+
+fn format_time(timestamp_ms: i64) -> String {
+    Utc.timestamp_millis_opt(timestamp_ms)
+        .single()
+        .map(|t| t.format("%H:%M:%S%.3f").to_string())
+        .unwrap_or_default()
+}
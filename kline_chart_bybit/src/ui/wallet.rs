@@ -0,0 +1,84 @@
+use crate::models::{WalletBalance, WalletData};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+
+/// Balances from the private `wallet` topic. Bybit pushes it whenever the
+/// balance changes, so fills show up here without polling.
+pub struct WalletPanel {
+    balance: Option<WalletBalance>,
+}
+
+impl WalletPanel {
+    pub fn new() -> Self {
+        Self { balance: None }
+    }
+
+    pub fn update_from_wallet(&mut self, data: &WalletData) {
+        self.balance = Some(WalletBalance::from_wallet_data(data));
+    }
+
+    pub fn set_balance(&mut self, balance: WalletBalance) {
+        self.balance = Some(balance);
+    }
+
+    pub fn balance(&self) -> Option<&WalletBalance> {
+        self.balance.as_ref()
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let Some(balance) = &self.balance else {
+            let block = Block::default().borders(Borders::ALL).title("Wallet");
+            frame.render_widget(block, area);
+            return;
+        };
+        let block = Block::default().borders(Borders::ALL).title(format!(
+            "Wallet ({}) equity {:.2} avail {:.2} IM {:.2}",
+            balance.account_type,
+            balance.total_equity,
+            balance.available_balance,
+            balance.initial_margin
+        ));
+        let header = Row::new(vec!["Coin", "Balance", "Equity", "uPnL", "Realized"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let rows = balance.coins.iter().map(|coin| {
+            let color = if coin.unrealised_pnl >= 0.0 {
+                Color::Green
+            } else {
+                Color::Red
+            };
+            Row::new(vec![
+                Cell::from(coin.coin.clone()),
+                Cell::from(format!("{:.4}", coin.wallet_balance)),
+                Cell::from(format!("{:.4}", coin.equity)),
+                Cell::from(format!("{:+.2}", coin.unrealised_pnl))
+                    .style(Style::default().fg(color)),
+                Cell::from(format!("{:+.2}", coin.cum_realised_pnl)),
+            ])
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Length(14),
+                Constraint::Length(14),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+        .header(header)
+        .block(block);
+        frame.render_widget(table, area);
+    }
+}
+
+impl Default for WalletPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,232 @@
+//! Closed candles of a Bybit linear contract, polled from the public REST
+//! kline endpoint.
+
+use crate::{Candle, DataSource};
+use serde::Deserialize;
+use std::{
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+
+pub const REST_URL: &str = "https://api.bybit.com";
+
+/// Time between polls for newly closed candles.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Candles asked for per poll, enough to catch up after a few failed polls.
+const POLL_LIMIT: usize = 10;
+
+/// Bybit caps a kline page at this many candles.
+const PAGE_LIMIT: usize = 1000;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KlineResponse {
+    ret_code: i64,
+    ret_msg: String,
+    result: Option<KlineResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KlineResult {
+    /// `[start, open, high, low, close, volume, turnover]`, newest first.
+    #[serde(default)]
+    list: Vec<[String; 7]>,
+}
+
+/// A candle with the start time it is known by, in milliseconds.
+type Kline = (i64, Candle);
+
+/// Candles of one symbol and interval that Bybit has closed, handed out as
+/// they close.
+pub struct BybitSource {
+    symbol: String,
+    interval: String,
+    candles: Receiver<Result<Candle, String>>,
+    /// Why the last poll failed, until a candle arrives again.
+    error: Option<String>,
+}
+
+impl BybitSource {
+    /// Fetches the last `history` closed candles of `symbol` at `interval`
+    /// (`1`, `60`, `D`, ...), oldest first, and keeps polling for new ones in
+    /// the background. Blocks until the history is in.
+    pub fn connect(
+        symbol: &str,
+        interval: &str,
+        history: usize,
+    ) -> Result<(Self, Vec<Candle>), String> {
+        let client = reqwest::blocking::Client::new();
+        // One more than asked for, since the newest is still open
+        let limit = (history + 1).min(PAGE_LIMIT);
+        let klines = fetch(&client, &kline_url(symbol, interval, limit))?;
+        if klines.is_empty() {
+            return Err(format!("no {} candles from Bybit", symbol));
+        }
+        let last_start = klines.last().map_or(0, |(start, _)| *start);
+
+        let (sender, candles) = mpsc::channel();
+        let url = kline_url(symbol, interval, POLL_LIMIT);
+        thread::spawn(move || poll(&client, &url, last_start, &sender));
+
+        let source = Self {
+            symbol: symbol.to_uppercase(),
+            interval: interval.to_string(),
+            candles,
+            error: None,
+        };
+        Ok((
+            source,
+            klines.into_iter().map(|(_, candle)| candle).collect(),
+        ))
+    }
+}
+
+impl DataSource for BybitSource {
+    fn next_candle(&mut self, _shock: f64) -> Option<Candle> {
+        while let Ok(next) = self.candles.try_recv() {
+            match next {
+                Ok(candle) => {
+                    self.error = None;
+                    return Some(candle);
+                }
+                Err(e) => self.error = Some(e),
+            }
+        }
+        None
+    }
+
+    /// The symbol and interval, and the last error while polling fails.
+    fn describe(&self) -> Option<String> {
+        let mut description = format!("bybit {} {}", self.symbol, self.interval);
+        if let Some(error) = &self.error {
+            description.push_str(&format!(" ({})", error));
+        }
+        Some(description)
+    }
+}
+
+fn kline_url(symbol: &str, interval: &str, limit: usize) -> String {
+    format!(
+        "{}/v5/market/kline?category=linear&symbol={}&interval={}&limit={}",
+        REST_URL,
+        symbol.to_uppercase(),
+        interval,
+        limit
+    )
+}
+
+/// Sends every candle that closes after `last_start` until the receiving
+/// source is dropped.
+fn poll(
+    client: &reqwest::blocking::Client,
+    url: &str,
+    mut last_start: i64,
+    sender: &Sender<Result<Candle, String>>,
+) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let klines = match fetch(client, url) {
+            Ok(klines) => klines,
+            Err(e) => {
+                if sender.send(Err(e)).is_err() {
+                    return;
+                }
+                continue;
+            }
+        };
+        for (start, candle) in klines {
+            if start <= last_start {
+                continue;
+            }
+            last_start = start;
+            if sender.send(Ok(candle)).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+fn fetch(client: &reqwest::blocking::Client, url: &str) -> Result<Vec<Kline>, String> {
+    let body = client
+        .get(url)
+        .send()
+        .and_then(|response| response.text())
+        .map_err(|e| e.to_string())?;
+    closed_klines(&body)
+}
+
+/// The closed candles of a kline response, oldest first; the newest one is
+/// still open and is left out.
+fn closed_klines(body: &str) -> Result<Vec<Kline>, String> {
+    let response: KlineResponse = serde_json::from_str(body).map_err(|e| e.to_string())?;
+    if response.ret_code != 0 {
+        return Err(format!(
+            "Bybit error {}: {}",
+            response.ret_code, response.ret_msg
+        ));
+    }
+    let rows = response
+        .result
+        .map(|result| result.list)
+        .unwrap_or_default();
+    rows.iter()
+        .skip(1)
+        .rev()
+        .map(|row| kline_from_row(row).ok_or_else(|| format!("bad kline {:?}", row)))
+        .collect()
+}
+
+fn kline_from_row(row: &[String; 7]) -> Option<Kline> {
+    let candle = Candle::new(
+        row[1].parse().ok()?,
+        row[2].parse().ok()?,
+        row[3].parse().ok()?,
+        row[4].parse().ok()?,
+        row[5].parse().ok()?,
+    );
+    Some((row[0].parse().ok()?, candle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"{
+        "retCode": 0,
+        "retMsg": "OK",
+        "result": {
+            "category": "linear",
+            "symbol": "BTCUSDT",
+            "list": [
+                ["1700000120000", "103", "104", "102", "103.5", "7", "721"],
+                ["1700000060000", "101", "103", "100", "103", "5", "510"],
+                ["1700000000000", "100", "102", "99", "101", "4", "402"]
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn closed_klines_are_oldest_first_without_the_open_one() {
+        let klines = closed_klines(PAGE).unwrap();
+
+        assert_eq!(
+            klines,
+            vec![
+                (1700000000000, Candle::new(100.0, 102.0, 99.0, 101.0, 4.0)),
+                (1700000060000, Candle::new(101.0, 103.0, 100.0, 103.0, 5.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn error_response_is_reported() {
+        let body = r#"{"retCode":10001,"retMsg":"params error: symbol invalid","result":{}}"#;
+
+        assert_eq!(
+            closed_klines(body).unwrap_err(),
+            "Bybit error 10001: params error: symbol invalid"
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+/// Anything with open, high, low and close prices that can be drawn as a
+/// candlestick.
+pub trait Ohlc {
+    fn open(&self) -> f64;
+    fn high(&self) -> f64;
+    fn low(&self) -> f64;
+    fn close(&self) -> f64;
+
+    fn is_bullish(&self) -> bool {
+        self.close() >= self.open()
+    }
+}
+
+/// Represents a single candlestick with OHLCV data
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Candle {
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Zero when a replayed file has no volume column.
+    #[serde(default)]
+    pub volume: f64,
+}
+
+impl Candle {
+    pub fn new(open: f64, high: f64, low: f64, close: f64, volume: f64) -> Self {
+        Self {
+            open,
+            high,
+            low,
+            close,
+            volume,
+        }
+    }
+
+    pub fn is_bullish(&self) -> bool {
+        self.close >= self.open
+    }
+}
+
+impl Ohlc for Candle {
+    fn open(&self) -> f64 {
+        self.open
+    }
+
+    fn high(&self) -> f64 {
+        self.high
+    }
+
+    fn low(&self) -> f64 {
+        self.low
+    }
+
+    fn close(&self) -> f64 {
+        self.close
+    }
+}
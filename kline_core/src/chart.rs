@@ -0,0 +1,113 @@
+//! Candlestick drawing on a ratatui canvas, one candle per unit of x.
+
+use crate::Ohlc;
+use ratatui::{
+    style::Color,
+    widgets::canvas::{Context, Line, Points},
+};
+
+/// Width of a candle body in x units; the rest of the unit is the gap.
+pub const CANDLE_WIDTH: f64 = 0.8;
+
+/// Lowest low and highest high of `candles`.
+pub fn price_range<C: Ohlc>(candles: &[C]) -> (f64, f64) {
+    let min_price = candles
+        .iter()
+        .map(|c| c.low())
+        .fold(f64::INFINITY, |a, b| a.min(b));
+    let max_price = candles
+        .iter()
+        .map(|c| c.high())
+        .fold(f64::NEG_INFINITY, |a, b| a.max(b));
+    (min_price, max_price)
+}
+
+/// Y bounds with a tenth of the range as room above and below.
+pub fn y_bounds(min_price: f64, max_price: f64) -> (f64, f64) {
+    let price_range = max_price - min_price;
+    let padding = price_range * 0.1;
+    (min_price - padding, max_price + padding)
+}
+
+/// Decimal places needed to tell labels apart across a price `range`,
+/// so small-valued series such as ratios stay readable.
+pub fn price_precision(range: f64) -> usize {
+    if !range.is_finite() || range <= 0.0 {
+        return 2;
+    }
+    (2 - range.log10().floor() as i32).clamp(2, 8) as usize
+}
+
+/// Six evenly spaced price labels just right of `x`.
+pub fn draw_price_labels(ctx: &mut Context, x: f64, y_min: f64, y_max: f64) {
+    let num_labels = 5;
+    let precision = price_precision(y_max - y_min);
+    for i in 0..=num_labels {
+        let price = y_min + (y_max - y_min) * (i as f64 / num_labels as f64);
+        ctx.print(x + 0.5, price, format!("{:.*}", precision, price));
+    }
+}
+
+/// Draws `candles` from x = 0, each in the color `color` picks for it.
+pub fn draw_candlesticks<C: Ohlc>(ctx: &mut Context, candles: &[C], color: impl Fn(&C) -> Color) {
+    for (i, candle) in candles.iter().enumerate() {
+        let x = i as f64;
+        let color = color(candle);
+
+        draw_candle_wick(ctx, x, CANDLE_WIDTH, candle, color);
+        draw_candle_body(ctx, x, CANDLE_WIDTH, candle, color);
+    }
+}
+
+fn draw_candle_wick<C: Ohlc>(ctx: &mut Context, x: f64, width: f64, candle: &C, color: Color) {
+    ctx.draw(&Line {
+        x1: x + width / 2.0,
+        y1: candle.low(),
+        x2: x + width / 2.0,
+        y2: candle.high(),
+        color,
+    });
+}
+
+fn draw_candle_body<C: Ohlc>(ctx: &mut Context, x: f64, width: f64, candle: &C, color: Color) {
+    let (body_top, body_bottom) = if candle.is_bullish() {
+        (candle.close(), candle.open())
+    } else {
+        (candle.open(), candle.close())
+    };
+
+    if (body_top - body_bottom).abs() < 0.001 {
+        draw_flat_candle(ctx, x, width, body_top, color);
+    } else {
+        draw_filled_candle(ctx, x, width, body_top, body_bottom, color);
+    }
+}
+
+fn draw_flat_candle(ctx: &mut Context, x: f64, width: f64, price: f64, color: Color) {
+    ctx.draw(&Line {
+        x1: x,
+        y1: price,
+        x2: x + width,
+        y2: price,
+        color,
+    });
+}
+
+fn draw_filled_candle(ctx: &mut Context, x: f64, width: f64, top: f64, bottom: f64, color: Color) {
+    let points = vec![(x, bottom), (x + width, bottom), (x + width, top), (x, top)];
+    ctx.draw(&Points {
+        coords: &points,
+        color,
+    });
+
+    for y in (((bottom * 100.0) as i32)..=((top * 100.0) as i32)).step_by(1) {
+        let y = y as f64 / 100.0;
+        ctx.draw(&Line {
+            x1: x,
+            y1: y,
+            x2: x + width,
+            y2: y,
+            color,
+        });
+    }
+}
@@ -0,0 +1,10 @@
+//! Candles, where they come from and how they are drawn, shared by
+//! kline_chart and kline_chart_bybit.
+
+pub mod bybit;
+pub mod candle;
+pub mod chart;
+pub mod source;
+
+pub use candle::{Candle, Ohlc};
+pub use source::DataSource;
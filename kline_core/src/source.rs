@@ -0,0 +1,14 @@
+use crate::Candle;
+
+/// A feed of candles for one chart.
+pub trait DataSource {
+    /// The next candle, or `None` if there is none yet or the source has run
+    /// out. `shock` is a standard normal draw shared by all assets so
+    /// simulated ones move together; other sources ignore it.
+    fn next_candle(&mut self, shock: f64) -> Option<Candle>;
+
+    /// Shown in the chart title instead of the seed.
+    fn describe(&self) -> Option<String> {
+        None
+    }
+}